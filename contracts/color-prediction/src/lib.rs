@@ -1,39 +1,91 @@
 //! Stellarcade Color Prediction Game Contract
 //!
 //! A prediction game where players wager on which color will be chosen next.
-//! An admin resolves each game by revealing the winning color. Winners split
-//! the pot proportionally; losers forfeit their wager to the pool.
+//! Each game either uses the default four-color shared pot or a custom color
+//! table with per-color weights and fixed multipliers. The winning color is
+//! declared by the admin or sampled from the RNG contract by weight.
 //!
 //! ## Game Flow
 //! 1. Admin calls `init` to configure the contract.
-//! 2. Player calls `place_prediction(player, color, wager, game_id)` to enter.
-//!    Multiple players can predict on the same game_id. Each player may only
-//!    submit one prediction per game.
-//! 3. Admin calls `resolve_prediction(game_id)` with the winning color.
-//!    Winners are determined and the pot split equally among correct predictors.
-//! 4. Anyone calls `get_game(game_id)` to inspect the final state.
+//! 2. Optionally, admin calls `configure_game(game_id, colors)` to give a game
+//!    its own color table. Games without one are created implicitly on the
+//!    first prediction and use the default four colors.
+//! 3. Player calls `place_prediction(player, color, wager, game_id)` to enter.
+//!    Multiple players can predict on the same game_id, and a player may stake
+//!    on several colors — one separate stake per color. Each wager is escrowed
+//!    through the balance contract.
+//! 4. The outcome is decided either by the admin via
+//!    `resolve_prediction(game_id, winning_color)`, or by weighted RNG
+//!    sampling: admin calls `request_outcome(game_id)` to lock betting, then
+//!    anyone calls `resolve_from_rng(game_id)` once the RNG is fulfilled.
+//!    Color-table games are paid by the house bankroll, so only the RNG may
+//!    pick their outcome.
+//! 5. Each winner calls `claim(player, game_id)` to receive their payout.
+//! 6. Anyone calls `get_game(game_id)` to inspect the final state.
+//!
+//! ## Scheduled Rounds
+//! Instead of managing game ids by hand, the admin can call
+//! `start_rounds(first_round_id, round_duration, colors)` to run back-to-back
+//! timed rounds. Each round accepts bets from `open_at` until `lock_at`. Once
+//! `lock_at` has passed anyone may call `advance_round`, which locks the
+//! current round and requests its outcome, resolves the previous round if its
+//! RNG result is in, and opens the next round with the following id.
+//!
+//! ## Settlement
+//! Default games (shared pot):
+//! - Fee = total_pot × house_edge_bps / 10000 (set via `set_house_edge`).
+//! - Net pot = total_pot − fee.
+//! - Each winner receives: net_pot × (their_stake / winning_total), rounded
+//!   down. The fee plus the rounding remainder is moved from escrow to the
+//!   admin's balance when the game is resolved.
+//! - If nobody picked the winning color, no fee is taken and every player
+//!   may `claim` a full refund of their stakes.
+//!
+//! Color-table games (fixed odds):
+//! - Each winning stake returns stake × multiplier_bps / 10000. The house
+//!   edge is priced into the multipliers, so no fee is taken.
+//! - The admin deposits a house bankroll with `deposit_bankroll`. Every stake
+//!   raises the game's liability — the most it could owe on any one color —
+//!   and the part not covered by the game's own stakes is allocated from the
+//!   bankroll to that game before the stake is accepted. A stake that would
+//!   need more than the unallocated bankroll is rejected.
+//! - At resolution the game's stakes and allocation, minus what the winners
+//!   are owed, return to the bankroll; losing stakes stay with the house.
+//!
+//! Every wager is held in escrow by the balance contract until the game is
+//! resolved. Winnings and refunds are then paid through the prize pool: the
+//! total owed to players is reserved under the game id, and the escrowed
+//! stakes covering it move to the admin's balance, since the house funds the
+//! pool. Each `claim` pays out of that reservation. This contract must be the
+//! prize pool's admin.
 //!
 //! ## Colors
-//! Valid color values: 0 = Red, 1 = Green, 2 = Blue, 3 = Yellow.
+//! Default color values: 0 = Red, 1 = Green, 2 = Blue, 3 = Yellow. A color
+//! table replaces these with indices `0..colors.len()`.
 //!
 //! ## Storage Strategy
 //! - `instance()` storage: contract-level config (Admin, RngContract,
-//!   PrizePoolContract, BalanceContract). Small, bounded, single ledger entry.
+//!   PrizePoolContract, BalanceContract, Bankroll). Small, bounded, single
+//!   ledger entry.
 //! - `persistent()` storage: per-game and per-player data (GameData,
-//!   PlayerList, Prediction). Each is an independent ledger entry with its own
-//!   TTL extended on every write (~30 days).
+//!   ColorTable, ColorTotals, PlayerList, Prediction). Each is an independent ledger entry
+//!   with its own TTL extended on every write (~30 days).
 //!
 //! ## Security
-//! - Only admin may resolve predictions.
-//! - Each player may predict at most once per game.
+//! - Only admin may configure games, resolve predictions or request outcomes.
+//! - Betting closes as soon as randomness is requested, and at `lock_at` for
+//!   scheduled rounds. Scheduled rounds cannot be resolved before `lock_at`.
+//! - Each player may stake once per color per game and claim at most once.
 //! - Resolving an already-resolved game is rejected.
 //! - All arithmetic uses `checked_*` to prevent overflow.
 #![no_std]
 #![allow(unexpected_cfgs)]
 
 use soroban_sdk::{
-    contract, contracterror, contractevent, contractimpl, contracttype, Address, Env, Vec,
+    contract, contractclient, contracterror, contractevent, contractimpl, contracttype,
+    symbol_short, Address, Env, Symbol, Vec,
 };
+use stellarcade_random_generator::RandomGeneratorClient;
 
 // ---------------------------------------------------------------------------
 // Constants
@@ -45,6 +97,17 @@ pub const PERSISTENT_BUMP_LEDGERS: u32 = 518_400;
 /// Maximum number of players per game (bounds O(n) iteration in resolve).
 pub const MAX_PLAYERS_PER_GAME: u32 = 500;
 
+/// Upper bound for the house edge (100%).
+pub const MAX_HOUSE_EDGE_BPS: u32 = 10_000;
+
+/// Maximum number of entries in a game's color table.
+pub const MAX_COLORS: u32 = 8;
+
+/// Shortest allowed betting window for scheduled rounds, in seconds.
+pub const MIN_ROUND_DURATION: u64 = 10;
+
+const BASIS_POINTS_DIVISOR: i128 = 10_000;
+
 // ---------------------------------------------------------------------------
 // Color constants
 // ---------------------------------------------------------------------------
@@ -55,6 +118,23 @@ pub const COLOR_BLUE: u32 = 2;
 pub const COLOR_YELLOW: u32 = 3;
 pub const COLOR_MAX: u32 = COLOR_YELLOW;
 
+// ---------------------------------------------------------------------------
+// External contract clients
+// ---------------------------------------------------------------------------
+
+#[contractclient(name = "BalanceClient")]
+pub trait UserBalanceContract {
+    fn credit(env: Env, game: Address, user: Address, amount: i128, reason: Symbol);
+    fn debit(env: Env, game: Address, user: Address, amount: i128, reason: Symbol);
+    fn balance_of(env: Env, user: Address) -> i128;
+}
+
+#[contractclient(name = "PrizePoolClient")]
+pub trait PrizePoolContract {
+    fn reserve(env: Env, admin: Address, game_id: u64, amount: i128);
+    fn payout(env: Env, admin: Address, to: Address, game_id: u64, amount: i128);
+}
+
 // ---------------------------------------------------------------------------
 // Error types
 // ---------------------------------------------------------------------------
@@ -73,6 +153,23 @@ pub enum Error {
     AlreadyPredicted = 8,
     GameFull = 9,
     Overflow = 10,
+    InsufficientBalance = 11,
+    GameNotResolved = 12,
+    PredictionNotFound = 13,
+    AlreadyClaimed = 14,
+    NoPayout = 15,
+    InvalidFee = 16,
+    InvalidColorTable = 17,
+    GameAlreadyExists = 18,
+    GameLocked = 19,
+    GameNotLocked = 20,
+    RngNotReady = 21,
+    HouseInsufficientFunds = 22,
+    BettingClosed = 23,
+    RoundStillOpen = 24,
+    RoundsNotStarted = 25,
+    InvalidDuration = 26,
+    OutcomeNeedsRng = 27,
 }
 
 // ---------------------------------------------------------------------------
@@ -87,6 +184,8 @@ pub enum GameStatus {
     Open = 0,
     /// Resolved — winning color known, outcome recorded.
     Resolved = 1,
+    /// Betting closed — waiting for the RNG to pick the winning color.
+    Locked = 2,
 }
 
 /// Metadata and accumulated state for one prediction game.
@@ -97,28 +196,81 @@ pub struct GameData {
     pub total_pot: i128,
     /// Number of distinct predictors.
     pub player_count: u32,
-    /// Number of predictors who chose the winning color.
+    /// Number of predictors with a stake on the winning color.
     pub winner_count: u32,
     /// Winning color (only valid when status == Resolved).
     pub winning_color: u32,
+    /// Sum of stakes on the winning color (set at resolution).
+    pub winning_total: i128,
+    /// Amount owed to winners (set at resolution). For shared-pot games this
+    /// is the pot after the house fee, and zero when there were no winners
+    /// and every stake is refundable.
+    pub net_pot: i128,
+    /// What the house took at resolution. For shared pots this is the fee
+    /// plus rounding remainder, paid to the admin's balance; for fixed odds
+    /// it is the stakes and allocation left after winnings, returned to the
+    /// bankroll.
+    pub house_cut: i128,
+    /// Most the game could owe its winners on any single color (fixed odds).
+    pub liability: i128,
+    /// House bankroll allocated to cover `liability` beyond `total_pot`.
+    pub bankroll: i128,
+    /// True when the game uses a color table with fixed multipliers.
+    pub fixed_odds: bool,
+    /// Ledger timestamp at which the game started accepting bets.
+    pub open_at: u64,
+    /// Betting cutoff for scheduled rounds; zero for games without one.
+    pub lock_at: u64,
     pub status: GameStatus,
 }
 
-/// A single player's prediction for a game.
+/// One entry of a game's color table. The entry's index is its color value.
 #[contracttype]
-#[derive(Clone)]
-pub struct PredictionEntry {
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ColorOdds {
+    /// Relative probability weight used when sampling the outcome.
+    pub weight: u32,
+    /// Total return on a winning stake, in basis points (45_000 = 4.5x).
+    pub multiplier_bps: u32,
+}
+
+/// A wager on a single color.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ColorStake {
     pub color: u32,
     pub wager: i128,
 }
 
+/// A single player's stakes in a game, at most one per color.
+#[contracttype]
+#[derive(Clone)]
+pub struct PredictionEntry {
+    pub stakes: Vec<ColorStake>,
+    pub claimed: bool,
+}
+
+/// Configuration and cursor for scheduled rounds.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoundSchedule {
+    /// Betting window of each round, in seconds.
+    pub round_duration: u64,
+    /// Color table applied to every round, or `None` for the default colors.
+    pub colors: Option<Vec<ColorOdds>>,
+    /// Id of the round currently accepting bets.
+    pub current_round: u64,
+}
+
 /// Storage key discriminants.
 ///
-/// Instance keys (Admin, RngContract, PrizePoolContract, BalanceContract)
-/// hold small contract-level config in a single ledger entry.
+/// Instance keys (Admin, RngContract, PrizePoolContract, BalanceContract,
+/// HouseEdgeBps, Schedule, Bankroll) hold small contract-level config in a
+/// single ledger entry.
 ///
-/// Persistent keys (Game, PlayerList, Prediction) are per-game and per-player,
-/// each stored as an independent ledger entry with its own TTL.
+/// Persistent keys (Game, ColorTable, ColorTotals, PlayerList, Prediction)
+/// are per-game and
+/// per-player, each stored as an independent ledger entry with its own TTL.
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
@@ -127,9 +279,17 @@ pub enum DataKey {
     RngContract,
     PrizePoolContract,
     BalanceContract,
+    HouseEdgeBps,
+    Schedule,
+    /// Unallocated house bankroll held in escrow, as i128.
+    Bankroll,
     // --- persistent() keys ---
     /// GameData keyed by game_id.
     Game(u64),
+    /// Vec<ColorOdds> for games created with `configure_game`.
+    ColorTable(u64),
+    /// Vec<i128> of total stakes per color for color-table games.
+    ColorTotals(u64),
     /// Vec<Address> of all predictors for a game.
     PlayerList(u64),
     /// PredictionEntry keyed by (game_id, player).
@@ -140,6 +300,21 @@ pub enum DataKey {
 // Events
 // ---------------------------------------------------------------------------
 
+#[contractevent]
+pub struct GameConfigured {
+    #[topic]
+    pub game_id: u64,
+    pub color_count: u32,
+}
+
+#[contractevent]
+pub struct RoundOpened {
+    #[topic]
+    pub game_id: u64,
+    pub open_at: u64,
+    pub lock_at: u64,
+}
+
 #[contractevent]
 pub struct PredictionPlaced {
     #[topic]
@@ -150,6 +325,13 @@ pub struct PredictionPlaced {
     pub wager: i128,
 }
 
+#[contractevent]
+pub struct OutcomeRequested {
+    #[topic]
+    pub game_id: u64,
+    pub total_weight: u64,
+}
+
 #[contractevent]
 pub struct PredictionResolved {
     #[topic]
@@ -157,6 +339,27 @@ pub struct PredictionResolved {
     pub winning_color: u32,
     pub winner_count: u32,
     pub total_pot: i128,
+    pub net_pot: i128,
+    pub house_cut: i128,
+}
+
+#[contractevent]
+pub struct WinningsClaimed {
+    #[topic]
+    pub game_id: u64,
+    #[topic]
+    pub player: Address,
+    pub payout: i128,
+}
+
+#[contractevent]
+pub struct HouseEdgeUpdated {
+    pub house_edge_bps: u32,
+}
+
+#[contractevent]
+pub struct BankrollUpdated {
+    pub bankroll: i128,
 }
 
 // ---------------------------------------------------------------------------
@@ -176,6 +379,8 @@ impl ColorPrediction {
     ///
     /// Stores admin, rng_contract, prize_pool_contract, and balance_contract
     /// in instance storage. Subsequent calls return `AlreadyInitialized`.
+    /// This contract must be the admin of `prize_pool_contract`, which pays
+    /// out every claim.
     pub fn init(
         env: Env,
         admin: Address,
@@ -199,6 +404,128 @@ impl ColorPrediction {
         env.storage()
             .instance()
             .set(&DataKey::BalanceContract, &balance_contract);
+        env.storage().instance().set(&DataKey::HouseEdgeBps, &0u32);
+
+        Ok(())
+    }
+
+    // -----------------------------------------------------------------------
+    // set_house_edge
+    // -----------------------------------------------------------------------
+
+    /// Set the house edge taken from each resolved pot, in basis points
+    /// (e.g. 500 = 5%). Admin only. Applies to shared-pot games resolved
+    /// afterwards.
+    pub fn set_house_edge(env: Env, house_edge_bps: u32) -> Result<(), Error> {
+        let admin = get_admin(&env)?;
+        admin.require_auth();
+
+        if house_edge_bps > MAX_HOUSE_EDGE_BPS {
+            return Err(Error::InvalidFee);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::HouseEdgeBps, &house_edge_bps);
+
+        HouseEdgeUpdated { house_edge_bps }.publish(&env);
+        Ok(())
+    }
+
+    // -----------------------------------------------------------------------
+    // deposit_bankroll / withdraw_bankroll
+    // -----------------------------------------------------------------------
+
+    /// Move `amount` from the admin's balance into the house bankroll that
+    /// backs fixed-odds games. Admin only.
+    ///
+    /// Emits `BankrollUpdated`.
+    pub fn deposit_bankroll(env: Env, amount: i128) -> Result<(), Error> {
+        let admin = get_admin(&env)?;
+        admin.require_auth();
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let balance_client = BalanceClient::new(&env, &get_balance_contract(&env)?);
+        let game_addr = env.current_contract_address();
+        if balance_client.balance_of(&admin) < amount {
+            return Err(Error::InsufficientBalance);
+        }
+        let bankroll = get_bankroll(&env)
+            .checked_add(amount)
+            .ok_or(Error::Overflow)?;
+
+        balance_client.debit(&game_addr, &admin, &amount, &symbol_short!("bankroll"));
+        balance_client.credit(&game_addr, &game_addr, &amount, &symbol_short!("bankroll"));
+        env.storage().instance().set(&DataKey::Bankroll, &bankroll);
+
+        BankrollUpdated { bankroll }.publish(&env);
+        Ok(())
+    }
+
+    /// Return `amount` of the unallocated bankroll to the admin's balance.
+    /// Admin only. Funds allocated to unresolved games cannot be withdrawn.
+    ///
+    /// Emits `BankrollUpdated`.
+    pub fn withdraw_bankroll(env: Env, amount: i128) -> Result<(), Error> {
+        let admin = get_admin(&env)?;
+        admin.require_auth();
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        let bankroll = get_bankroll(&env);
+        if bankroll < amount {
+            return Err(Error::HouseInsufficientFunds);
+        }
+        let bankroll = bankroll - amount;
+        env.storage().instance().set(&DataKey::Bankroll, &bankroll);
+
+        let balance_client = BalanceClient::new(&env, &get_balance_contract(&env)?);
+        let game_addr = env.current_contract_address();
+        balance_client.debit(&game_addr, &game_addr, &amount, &symbol_short!("bankroll"));
+        balance_client.credit(&game_addr, &admin, &amount, &symbol_short!("bankroll"));
+
+        BankrollUpdated { bankroll }.publish(&env);
+        Ok(())
+    }
+
+    // -----------------------------------------------------------------------
+    // configure_game
+    // -----------------------------------------------------------------------
+
+    /// Create a game with its own color table. Admin only.
+    ///
+    /// `colors` must hold 2–`MAX_COLORS` entries; every entry needs a
+    /// positive weight and a multiplier above 1x (10_000 bps). The game must
+    /// not exist yet, so a table cannot change once bets are in.
+    ///
+    /// Emits `GameConfigured`.
+    pub fn configure_game(env: Env, game_id: u64, colors: Vec<ColorOdds>) -> Result<(), Error> {
+        let admin = get_admin(&env)?;
+        admin.require_auth();
+
+        validate_color_table(&colors)?;
+
+        if env.storage().persistent().has(&DataKey::Game(game_id)) {
+            return Err(Error::GameAlreadyExists);
+        }
+
+        let color_count = colors.len();
+        persist_set(&env, DataKey::ColorTable(game_id), &colors);
+        persist_set(
+            &env,
+            DataKey::Game(game_id),
+            &new_game(true, env.ledger().timestamp(), 0),
+        );
+
+        GameConfigured {
+            game_id,
+            color_count,
+        }
+        .publish(&env);
 
         Ok(())
     }
@@ -209,10 +536,19 @@ impl ColorPrediction {
 
     /// Place a color prediction for an open game.
     ///
-    /// `color` must be one of COLOR_RED (0), COLOR_GREEN (1), COLOR_BLUE (2),
-    /// COLOR_YELLOW (3). `wager` must be positive. Each player may predict
-    /// exactly once per game. The game is created implicitly on the first
-    /// prediction for a given `game_id`.
+    /// For default games `color` must be one of COLOR_RED (0), COLOR_GREEN
+    /// (1), COLOR_BLUE (2), COLOR_YELLOW (3); for color-table games it must be
+    /// an index into the table. `wager` must be positive. A player may stake
+    /// on several colors in the same game, but only once per color. The game
+    /// is created implicitly on the first prediction for a given `game_id`,
+    /// except for ids at or above the scheduled-round cursor, which are
+    /// reserved for `advance_round` and fail with `GameNotFound` until opened.
+    ///
+    /// The wager is debited from the player's balance and credited to this
+    /// contract's balance as escrow until the player claims. In color-table
+    /// games the stake is rejected with `HouseInsufficientFunds` if the
+    /// game's liability could not be covered by its stakes plus bankroll
+    /// allocated from the unallocated house bankroll.
     ///
     /// Emits `PredictionPlaced`.
     pub fn place_prediction(
@@ -225,58 +561,109 @@ impl ColorPrediction {
         require_initialized(&env)?;
         player.require_auth();
 
-        if color > COLOR_MAX {
-            return Err(Error::InvalidColor);
-        }
         if wager <= 0 {
             return Err(Error::InvalidAmount);
         }
 
         // Load or initialize the game.
-        let mut game: GameData = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Game(game_id))
-            .unwrap_or(GameData {
-                total_pot: 0,
-                player_count: 0,
-                winner_count: 0,
-                winning_color: 0,
-                status: GameStatus::Open,
-            });
+        let mut game: GameData = match env.storage().persistent().get(&DataKey::Game(game_id)) {
+            Some(game) => game,
+            None => {
+                if let Some(schedule) = env
+                    .storage()
+                    .instance()
+                    .get::<DataKey, RoundSchedule>(&DataKey::Schedule)
+                {
+                    if game_id >= schedule.current_round {
+                        return Err(Error::GameNotFound);
+                    }
+                }
+                new_game(false, env.ledger().timestamp(), 0)
+            }
+        };
 
-        if game.status != GameStatus::Open {
-            return Err(Error::GameAlreadyResolved);
+        match game.status {
+            GameStatus::Open => {}
+            GameStatus::Locked => return Err(Error::GameLocked),
+            GameStatus::Resolved => return Err(Error::GameAlreadyResolved),
+        }
+        if game.lock_at != 0 && env.ledger().timestamp() >= game.lock_at {
+            return Err(Error::BettingClosed);
         }
 
-        if game.player_count >= MAX_PLAYERS_PER_GAME {
-            return Err(Error::GameFull);
+        if color >= color_count(&env, game_id, &game) {
+            return Err(Error::InvalidColor);
         }
 
         let prediction_key = DataKey::Prediction(game_id, player.clone());
-        if env.storage().persistent().has(&prediction_key) {
+        let existing: Option<PredictionEntry> = env.storage().persistent().get(&prediction_key);
+        let is_new_player = existing.is_none();
+        let mut entry = existing.unwrap_or(PredictionEntry {
+            stakes: Vec::new(&env),
+            claimed: false,
+        });
+
+        if stake_on(&entry, color) > 0 {
             return Err(Error::AlreadyPredicted);
         }
+        if is_new_player && game.player_count >= MAX_PLAYERS_PER_GAME {
+            return Err(Error::GameFull);
+        }
 
-        // Record the prediction.
-        let entry = PredictionEntry { color, wager };
+        game.total_pot = game.total_pot.checked_add(wager).ok_or(Error::Overflow)?;
+
+        // Make sure the house can cover this game's worst outcome.
+        let mut bankroll = get_bankroll(&env);
+        if game.fixed_odds {
+            let mut totals = color_totals(&env, game_id, &game);
+            let total = totals.get(color).unwrap_or(0);
+            totals.set(color, total.checked_add(wager).ok_or(Error::Overflow)?);
+            game.liability = max_liability(&env, game_id, &totals)?;
+
+            let needed = game
+                .liability
+                .checked_sub(game.total_pot)
+                .ok_or(Error::Overflow)?;
+            if needed > game.bankroll {
+                let shortfall = needed - game.bankroll;
+                if bankroll < shortfall {
+                    return Err(Error::HouseInsufficientFunds);
+                }
+                bankroll -= shortfall;
+                game.bankroll = needed;
+            }
+            persist_set(&env, DataKey::ColorTotals(game_id), &totals);
+        }
+
+        // Escrow the wager before recording anything.
+        let balance_client = BalanceClient::new(&env, &get_balance_contract(&env)?);
+        let game_addr = env.current_contract_address();
+        if balance_client.balance_of(&player) < wager {
+            return Err(Error::InsufficientBalance);
+        }
+        balance_client.debit(&game_addr, &player, &wager, &symbol_short!("wager"));
+        balance_client.credit(&game_addr, &game_addr, &wager, &symbol_short!("escrow"));
+
+        // Record the stake.
+        entry.stakes.push_back(ColorStake { color, wager });
         persist_set(&env, prediction_key, &entry);
 
-        // Register player in the list.
-        let mut players: Vec<Address> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::PlayerList(game_id))
-            .unwrap_or_else(|| Vec::new(&env));
-        players.push_back(player.clone());
-        persist_set(&env, DataKey::PlayerList(game_id), &players);
+        // Register new players in the list.
+        if is_new_player {
+            let mut players: Vec<Address> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::PlayerList(game_id))
+                .unwrap_or_else(|| Vec::new(&env));
+            players.push_back(player.clone());
+            persist_set(&env, DataKey::PlayerList(game_id), &players);
+            game.player_count = game.player_count.checked_add(1).ok_or(Error::Overflow)?;
+        }
 
-        // Update game totals.
-        game.total_pot = game.total_pot.checked_add(wager).ok_or(Error::Overflow)?;
-        game.player_count = game.player_count.checked_add(1).ok_or(Error::Overflow)?;
         persist_set(&env, DataKey::Game(game_id), &game);
-
-        // TODO: Invoke balance_contract to transfer `wager` tokens from player to this contract.
+        if game.fixed_odds {
+            env.storage().instance().set(&DataKey::Bankroll, &bankroll);
+        }
 
         PredictionPlaced {
             game_id,
@@ -295,70 +682,294 @@ impl ColorPrediction {
 
     /// Resolve a game by declaring the winning color. Admin only.
     ///
-    /// `winning_color` must be a valid color value (0–3). Iterates all player
+    /// `winning_color` must be a valid color for the game. Iterates all player
     /// predictions (bounded by `MAX_PLAYERS_PER_GAME`) to count winners and
-    /// transitions the game to `Resolved`.
+    /// their total stake, then transitions the game to `Resolved`. A game that
+    /// is locked waiting for the RNG must be resolved with `resolve_from_rng`,
+    /// and a scheduled round cannot be resolved before its `lock_at`.
+    /// Color-table games fail with `OutcomeNeedsRng`: their winners are paid
+    /// by the house, which must not choose the outcome.
     ///
-    /// If there are no winners, the entire pot remains in the contract.
+    /// When a shared-pot game has winners, the fee and the rounding remainder
+    /// of the winners' shares are paid from escrow to the admin's balance.
+    /// If it has no winners no fee is taken and every player may claim their
+    /// stakes back. What the players are owed is reserved in the prize pool,
+    /// and the escrow covering it is paid to the admin's balance.
     ///
     /// Emits `PredictionResolved`.
     pub fn resolve_prediction(env: Env, game_id: u64, winning_color: u32) -> Result<(), Error> {
         let admin = get_admin(&env)?;
         admin.require_auth();
 
-        if winning_color > COLOR_MAX {
+        let game: GameData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Game(game_id))
+            .ok_or(Error::GameNotFound)?;
+
+        match game.status {
+            GameStatus::Open => {}
+            GameStatus::Locked => return Err(Error::GameLocked),
+            GameStatus::Resolved => return Err(Error::GameAlreadyResolved),
+        }
+
+        if game.fixed_odds {
+            return Err(Error::OutcomeNeedsRng);
+        }
+        require_past_lock(&env, &game)?;
+        if winning_color >= color_count(&env, game_id, &game) {
             return Err(Error::InvalidColor);
         }
 
-        let mut game: GameData = env
+        settle(&env, &admin, game_id, game, winning_color)
+    }
+
+    // -----------------------------------------------------------------------
+    // request_outcome
+    // -----------------------------------------------------------------------
+
+    /// Close betting and ask the RNG contract for the outcome. Admin only.
+    ///
+    /// Requests a random value in `[0, total_weight)` using `game_id` as the
+    /// request id, and moves the game to `Locked`. This contract must be an
+    /// authorized caller on the RNG contract. Scheduled rounds are locked by
+    /// `advance_round` instead, but may also be locked here after `lock_at`.
+    ///
+    /// Emits `OutcomeRequested`.
+    pub fn request_outcome(env: Env, game_id: u64) -> Result<(), Error> {
+        let admin = get_admin(&env)?;
+        admin.require_auth();
+
+        let game: GameData = env
             .storage()
             .persistent()
             .get(&DataKey::Game(game_id))
             .ok_or(Error::GameNotFound)?;
 
-        if game.status != GameStatus::Open {
-            return Err(Error::GameAlreadyResolved);
+        match game.status {
+            GameStatus::Open => {}
+            GameStatus::Locked => return Err(Error::GameLocked),
+            GameStatus::Resolved => return Err(Error::GameAlreadyResolved),
         }
+        require_past_lock(&env, &game)?;
+
+        lock_game(&env, game_id, game)
+    }
+
+    // -----------------------------------------------------------------------
+    // start_rounds
+    // -----------------------------------------------------------------------
 
-        let players: Vec<Address> = env
+    /// Start (or restart) scheduled rounds. Admin only.
+    ///
+    /// Opens round `first_round_id` immediately with a betting window of
+    /// `round_duration` seconds (at least `MIN_ROUND_DURATION`). Every round
+    /// uses `colors` as its color table, or the default four colors when
+    /// `None`. Restarting replaces the schedule; rounds already opened keep
+    /// their own timings and can still be locked and resolved manually.
+    ///
+    /// Emits `RoundOpened`.
+    pub fn start_rounds(
+        env: Env,
+        first_round_id: u64,
+        round_duration: u64,
+        colors: Option<Vec<ColorOdds>>,
+    ) -> Result<(), Error> {
+        let admin = get_admin(&env)?;
+        admin.require_auth();
+
+        if round_duration < MIN_ROUND_DURATION {
+            return Err(Error::InvalidDuration);
+        }
+        if let Some(table) = &colors {
+            validate_color_table(table)?;
+        }
+
+        let schedule = RoundSchedule {
+            round_duration,
+            colors,
+            current_round: first_round_id,
+        };
+        open_round(&env, &schedule)?;
+        env.storage().instance().set(&DataKey::Schedule, &schedule);
+
+        Ok(())
+    }
+
+    // -----------------------------------------------------------------------
+    // advance_round
+    // -----------------------------------------------------------------------
+
+    /// Move the schedule to the next round. Anyone may call this once the
+    /// current round has reached its `lock_at`; no signature is required,
+    /// so a keeper can drive the schedule without holding admin keys.
+    ///
+    /// In one call this:
+    /// 1. locks the current round and requests its outcome from the RNG;
+    /// 2. resolves the previous round if it is still locked and its RNG
+    ///    result is available (otherwise it is left for `resolve_from_rng`);
+    /// 3. opens the next round, whose id is the current id plus one.
+    ///
+    /// Returns the id of the newly opened round.
+    pub fn advance_round(env: Env) -> Result<u64, Error> {
+        let admin = get_admin(&env)?;
+        let mut schedule: RoundSchedule = env
             .storage()
-            .persistent()
-            .get(&DataKey::PlayerList(game_id))
-            .unwrap_or_else(|| Vec::new(&env));
+            .instance()
+            .get(&DataKey::Schedule)
+            .ok_or(Error::RoundsNotStarted)?;
 
-        let mut winner_count: u32 = 0;
+        let current_id = schedule.current_round;
+        let current: GameData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Game(current_id))
+            .ok_or(Error::GameNotFound)?;
+        if env.ledger().timestamp() < current.lock_at {
+            return Err(Error::RoundStillOpen);
+        }
+        if current.status == GameStatus::Open {
+            lock_game(&env, current_id, current)?;
+        }
 
-        // Count winners (bounded by MAX_PLAYERS_PER_GAME).
-        for player in players.iter() {
-            let key = DataKey::Prediction(game_id, player.clone());
-            if let Some(entry) = env
+        if let Some(previous_id) = current_id.checked_sub(1) {
+            if let Some(previous) = env
                 .storage()
                 .persistent()
-                .get::<DataKey, PredictionEntry>(&key)
+                .get::<DataKey, GameData>(&DataKey::Game(previous_id))
             {
-                if entry.color == winning_color {
-                    winner_count = winner_count.checked_add(1).ok_or(Error::Overflow)?;
+                if previous.status == GameStatus::Locked {
+                    if let Some(roll) = rng_roll(&env, previous_id)? {
+                        let winning_color =
+                            pick_color(&color_weights(&env, previous_id, &previous), roll);
+                        settle(&env, &admin, previous_id, previous, winning_color)?;
+                    }
                 }
             }
         }
 
-        // TODO: If winner_count > 0, invoke prize_pool_contract to distribute
-        // (game.total_pot / winner_count) tokens to each winner.
+        schedule.current_round = current_id.checked_add(1).ok_or(Error::Overflow)?;
+        open_round(&env, &schedule)?;
+        env.storage().instance().set(&DataKey::Schedule, &schedule);
 
-        game.status = GameStatus::Resolved;
-        game.winning_color = winning_color;
-        game.winner_count = winner_count;
-        persist_set(&env, DataKey::Game(game_id), &game);
+        Ok(schedule.current_round)
+    }
 
-        PredictionResolved {
+    // -----------------------------------------------------------------------
+    // resolve_from_rng
+    // -----------------------------------------------------------------------
+
+    /// Resolve a locked game from the fulfilled RNG result.
+    ///
+    /// Anyone may call this — the outcome is deterministic once the RNG has
+    /// been fulfilled. The roll is mapped onto the color weights in table
+    /// order, so a color with weight `w` wins with probability
+    /// `w / total_weight`. Settlement is identical to `resolve_prediction`.
+    ///
+    /// Emits `PredictionResolved`.
+    pub fn resolve_from_rng(env: Env, game_id: u64) -> Result<(), Error> {
+        let admin = get_admin(&env)?;
+
+        let game: GameData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Game(game_id))
+            .ok_or(Error::GameNotFound)?;
+
+        match game.status {
+            GameStatus::Locked => {}
+            GameStatus::Open => return Err(Error::GameNotLocked),
+            GameStatus::Resolved => return Err(Error::GameAlreadyResolved),
+        }
+
+        let roll = rng_roll(&env, game_id)?.ok_or(Error::RngNotReady)?;
+
+        let winning_color = pick_color(&color_weights(&env, game_id, &game), roll);
+        settle(&env, &admin, game_id, game, winning_color)
+    }
+
+    // -----------------------------------------------------------------------
+    // claim
+    // -----------------------------------------------------------------------
+
+    /// Claim the payout for a resolved game.
+    ///
+    /// In shared-pot games winners receive `net_pot × stake / winning_total`;
+    /// in color-table games they receive `stake × multiplier_bps / 10000`.
+    /// Either way the payout is paid by the prize pool out of the game's
+    /// reservation.
+    /// When a shared-pot game had no winners every player receives a full
+    /// refund of their stakes instead. Players without a winning stake get
+    /// `NoPayout`.
+    ///
+    /// Returns the amount paid. Emits `WinningsClaimed`.
+    pub fn claim(env: Env, player: Address, game_id: u64) -> Result<i128, Error> {
+        require_initialized(&env)?;
+        player.require_auth();
+
+        let game: GameData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Game(game_id))
+            .ok_or(Error::GameNotFound)?;
+
+        if game.status != GameStatus::Resolved {
+            return Err(Error::GameNotResolved);
+        }
+
+        let prediction_key = DataKey::Prediction(game_id, player.clone());
+        let mut entry: PredictionEntry = env
+            .storage()
+            .persistent()
+            .get(&prediction_key)
+            .ok_or(Error::PredictionNotFound)?;
+
+        if entry.claimed {
+            return Err(Error::AlreadyClaimed);
+        }
+
+        let is_refund = !game.fixed_odds && game.winner_count == 0;
+        let winning_stake = stake_on(&entry, game.winning_color);
+        let payout = if is_refund {
+            let mut total: i128 = 0;
+            for stake in entry.stakes.iter() {
+                total = total.checked_add(stake.wager).ok_or(Error::Overflow)?;
+            }
+            total
+        } else if winning_stake == 0 {
+            0
+        } else if game.fixed_odds {
+            let odds = color_odds(&env, game_id, game.winning_color)?;
+            winning_stake
+                .checked_mul(odds.multiplier_bps as i128)
+                .and_then(|v| v.checked_div(BASIS_POINTS_DIVISOR))
+                .ok_or(Error::Overflow)?
+        } else {
+            game.net_pot
+                .checked_mul(winning_stake)
+                .and_then(|v| v.checked_div(game.winning_total))
+                .ok_or(Error::Overflow)?
+        };
+
+        if payout <= 0 {
+            return Err(Error::NoPayout);
+        }
+
+        // State update before any external call that moves funds.
+        entry.claimed = true;
+        persist_set(&env, prediction_key, &entry);
+
+        let pool_client = PrizePoolClient::new(&env, &get_prize_pool_contract(&env)?);
+        pool_client.payout(&env.current_contract_address(), &player, &game_id, &payout);
+
+        WinningsClaimed {
             game_id,
-            winning_color,
-            winner_count,
-            total_pot: game.total_pot,
+            player,
+            payout,
         }
         .publish(&env);
 
-        Ok(())
+        Ok(payout)
     }
 
     // -----------------------------------------------------------------------
@@ -369,6 +980,31 @@ impl ColorPrediction {
     pub fn get_game(env: Env, game_id: u64) -> Option<GameData> {
         env.storage().persistent().get(&DataKey::Game(game_id))
     }
+
+    /// Return a game's color table, or `None` for default four-color games.
+    pub fn get_color_table(env: Env, game_id: u64) -> Option<Vec<ColorOdds>> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ColorTable(game_id))
+    }
+
+    /// Return the unallocated house bankroll.
+    pub fn get_bankroll(env: Env) -> i128 {
+        get_bankroll(&env)
+    }
+
+    /// Return the scheduled-round configuration, or `None` if rounds were
+    /// never started.
+    pub fn get_schedule(env: Env) -> Option<RoundSchedule> {
+        env.storage().instance().get(&DataKey::Schedule)
+    }
+
+    /// Return a player's stakes in a game, or `None` if they have not played.
+    pub fn get_prediction(env: Env, game_id: u64, player: Address) -> Option<PredictionEntry> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Prediction(game_id, player))
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -389,6 +1025,367 @@ fn get_admin(env: &Env) -> Result<Address, Error> {
         .ok_or(Error::NotInitialized)
 }
 
+fn get_rng_contract(env: &Env) -> Result<Address, Error> {
+    env.storage()
+        .instance()
+        .get(&DataKey::RngContract)
+        .ok_or(Error::NotInitialized)
+}
+
+fn get_prize_pool_contract(env: &Env) -> Result<Address, Error> {
+    env.storage()
+        .instance()
+        .get(&DataKey::PrizePoolContract)
+        .ok_or(Error::NotInitialized)
+}
+
+fn get_balance_contract(env: &Env) -> Result<Address, Error> {
+    env.storage()
+        .instance()
+        .get(&DataKey::BalanceContract)
+        .ok_or(Error::NotInitialized)
+}
+
+fn get_bankroll(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&DataKey::Bankroll)
+        .unwrap_or(0)
+}
+
+fn new_game(fixed_odds: bool, open_at: u64, lock_at: u64) -> GameData {
+    GameData {
+        total_pot: 0,
+        player_count: 0,
+        winner_count: 0,
+        winning_color: 0,
+        winning_total: 0,
+        net_pot: 0,
+        house_cut: 0,
+        liability: 0,
+        bankroll: 0,
+        fixed_odds,
+        open_at,
+        lock_at,
+        status: GameStatus::Open,
+    }
+}
+
+fn validate_color_table(colors: &Vec<ColorOdds>) -> Result<(), Error> {
+    if colors.len() < 2 || colors.len() > MAX_COLORS {
+        return Err(Error::InvalidColorTable);
+    }
+    for odds in colors.iter() {
+        if odds.weight == 0 || (odds.multiplier_bps as i128) <= BASIS_POINTS_DIVISOR {
+            return Err(Error::InvalidColorTable);
+        }
+    }
+    Ok(())
+}
+
+/// Reject resolving or locking a scheduled round before its betting cutoff.
+fn require_past_lock(env: &Env, game: &GameData) -> Result<(), Error> {
+    if env.ledger().timestamp() < game.lock_at {
+        return Err(Error::RoundStillOpen);
+    }
+    Ok(())
+}
+
+/// Create the schedule's current round, open from now until
+/// now + `round_duration`.
+fn open_round(env: &Env, schedule: &RoundSchedule) -> Result<(), Error> {
+    let game_id = schedule.current_round;
+    if env.storage().persistent().has(&DataKey::Game(game_id)) {
+        return Err(Error::GameAlreadyExists);
+    }
+
+    let open_at = env.ledger().timestamp();
+    let lock_at = open_at
+        .checked_add(schedule.round_duration)
+        .ok_or(Error::Overflow)?;
+    if let Some(table) = &schedule.colors {
+        persist_set(env, DataKey::ColorTable(game_id), table);
+    }
+    persist_set(
+        env,
+        DataKey::Game(game_id),
+        &new_game(schedule.colors.is_some(), open_at, lock_at),
+    );
+
+    RoundOpened {
+        game_id,
+        open_at,
+        lock_at,
+    }
+    .publish(env);
+
+    Ok(())
+}
+
+/// Request the outcome for `game_id` from the RNG and mark it `Locked`.
+fn lock_game(env: &Env, game_id: u64, mut game: GameData) -> Result<(), Error> {
+    let mut total_weight: u64 = 0;
+    for weight in color_weights(env, game_id, &game).iter() {
+        total_weight = total_weight
+            .checked_add(weight as u64)
+            .ok_or(Error::Overflow)?;
+    }
+
+    RandomGeneratorClient::new(env, &get_rng_contract(env)?).request_random(
+        &env.current_contract_address(),
+        &game_id,
+        &total_weight,
+    );
+
+    game.status = GameStatus::Locked;
+    persist_set(env, DataKey::Game(game_id), &game);
+
+    OutcomeRequested {
+        game_id,
+        total_weight,
+    }
+    .publish(env);
+
+    Ok(())
+}
+
+/// The fulfilled RNG roll for `game_id`, or `None` if not available yet.
+fn rng_roll(env: &Env, game_id: u64) -> Result<Option<u64>, Error> {
+    let rng_client = RandomGeneratorClient::new(env, &get_rng_contract(env)?);
+    match rng_client.try_get_result(&game_id) {
+        Ok(Ok(entry)) => Ok(Some(entry.result)),
+        _ => Ok(None),
+    }
+}
+
+/// Number of valid colors: the table length, or the four default colors.
+fn color_count(env: &Env, game_id: u64, game: &GameData) -> u32 {
+    color_weights(env, game_id, game).len()
+}
+
+/// Sampling weights in color order. Default games weigh all colors equally.
+fn color_weights(env: &Env, game_id: u64, game: &GameData) -> Vec<u32> {
+    let mut weights = Vec::new(env);
+    if game.fixed_odds {
+        let table: Vec<ColorOdds> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ColorTable(game_id))
+            .unwrap_or_else(|| Vec::new(env));
+        for odds in table.iter() {
+            weights.push_back(odds.weight);
+        }
+    } else {
+        for _ in COLOR_RED..=COLOR_MAX {
+            weights.push_back(1);
+        }
+    }
+    weights
+}
+
+fn color_odds(env: &Env, game_id: u64, color: u32) -> Result<ColorOdds, Error> {
+    let table: Vec<ColorOdds> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::ColorTable(game_id))
+        .ok_or(Error::GameNotFound)?;
+    table.get(color).ok_or(Error::InvalidColor)
+}
+
+/// Total stakes per color so far, zero for colors nobody backed.
+fn color_totals(env: &Env, game_id: u64, game: &GameData) -> Vec<i128> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ColorTotals(game_id))
+        .unwrap_or_else(|| {
+            let mut totals = Vec::new(env);
+            for _ in 0..color_count(env, game_id, game) {
+                totals.push_back(0);
+            }
+            totals
+        })
+}
+
+/// The most a fixed-odds game would owe if any single color won.
+fn max_liability(env: &Env, game_id: u64, totals: &Vec<i128>) -> Result<i128, Error> {
+    let mut liability: i128 = 0;
+    for (color, total) in totals.iter().enumerate() {
+        let odds = color_odds(env, game_id, color as u32)?;
+        let owed = total
+            .checked_mul(odds.multiplier_bps as i128)
+            .and_then(|v| v.checked_div(BASIS_POINTS_DIVISOR))
+            .ok_or(Error::Overflow)?;
+        liability = liability.max(owed);
+    }
+    Ok(liability)
+}
+
+/// Map a roll in `[0, sum(weights))` to the color whose cumulative weight
+/// range contains it.
+fn pick_color(weights: &Vec<u32>, roll: u64) -> u32 {
+    let mut cumulative: u64 = 0;
+    for (color, weight) in weights.iter().enumerate() {
+        cumulative += weight as u64;
+        if roll < cumulative {
+            return color as u32;
+        }
+    }
+    weights.len().saturating_sub(1)
+}
+
+/// The player's stake on `color`, or zero if they did not back it.
+fn stake_on(entry: &PredictionEntry, color: u32) -> i128 {
+    for stake in entry.stakes.iter() {
+        if stake.color == color {
+            return stake.wager;
+        }
+    }
+    0
+}
+
+/// Count winners, compute what they are owed, reserve it in the prize pool,
+/// hand the house its cut and mark the game resolved.
+fn settle(
+    env: &Env,
+    admin: &Address,
+    game_id: u64,
+    mut game: GameData,
+    winning_color: u32,
+) -> Result<(), Error> {
+    let players: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::PlayerList(game_id))
+        .unwrap_or_else(|| Vec::new(env));
+
+    let mut winner_count: u32 = 0;
+    let mut winning_total: i128 = 0;
+
+    // Count winners (bounded by MAX_PLAYERS_PER_GAME).
+    for player in players.iter() {
+        let key = DataKey::Prediction(game_id, player.clone());
+        if let Some(entry) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, PredictionEntry>(&key)
+        {
+            let stake = stake_on(&entry, winning_color);
+            if stake > 0 {
+                winner_count = winner_count.checked_add(1).ok_or(Error::Overflow)?;
+                winning_total = winning_total.checked_add(stake).ok_or(Error::Overflow)?;
+            }
+        }
+    }
+
+    let multiplier_bps = if game.fixed_odds {
+        color_odds(env, game_id, winning_color)?.multiplier_bps as i128
+    } else {
+        0
+    };
+
+    let mut net_pot = if winner_count == 0 || game.fixed_odds {
+        0
+    } else {
+        let house_edge_bps: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::HouseEdgeBps)
+            .unwrap_or(0);
+        let fee = game
+            .total_pot
+            .checked_mul(house_edge_bps as i128)
+            .and_then(|v| v.checked_div(BASIS_POINTS_DIVISOR))
+            .ok_or(Error::Overflow)?;
+        game.total_pot.checked_sub(fee).ok_or(Error::Overflow)?
+    };
+
+    // Each winner's payout is rounded down, so the house keeps whatever the
+    // payouts leave over (bounded by MAX_PLAYERS_PER_GAME).
+    let mut owed: i128 = 0;
+    if winner_count > 0 {
+        for player in players.iter() {
+            let key = DataKey::Prediction(game_id, player.clone());
+            if let Some(entry) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, PredictionEntry>(&key)
+            {
+                let stake = stake_on(&entry, winning_color);
+                let payout = if game.fixed_odds {
+                    stake
+                        .checked_mul(multiplier_bps)
+                        .and_then(|v| v.checked_div(BASIS_POINTS_DIVISOR))
+                } else {
+                    net_pot
+                        .checked_mul(stake)
+                        .and_then(|v| v.checked_div(winning_total))
+                }
+                .ok_or(Error::Overflow)?;
+                owed = owed.checked_add(payout).ok_or(Error::Overflow)?;
+            }
+        }
+    }
+
+    let balance_client = BalanceClient::new(env, &get_balance_contract(env)?);
+    let game_addr = env.current_contract_address();
+
+    // The prize pool pays the players; the escrow covering them goes to the
+    // house, which funds the pool.
+    let payable = if !game.fixed_odds && winner_count == 0 {
+        game.total_pot
+    } else {
+        owed
+    };
+    if payable > 0 {
+        let pool_client = PrizePoolClient::new(env, &get_prize_pool_contract(env)?);
+        pool_client.reserve(&game_addr, &game_id, &payable);
+        balance_client.debit(&game_addr, &game_addr, &payable, &symbol_short!("pool"));
+        balance_client.credit(&game_addr, admin, &payable, &symbol_short!("pool"));
+    }
+
+    let mut house_cut: i128 = 0;
+    if game.fixed_odds {
+        // Stakes plus allocation always cover the liability, which bounds
+        // what is owed; the rest goes back to the bankroll.
+        net_pot = owed;
+        house_cut = game
+            .total_pot
+            .checked_add(game.bankroll)
+            .and_then(|v| v.checked_sub(owed))
+            .ok_or(Error::Overflow)?;
+        let bankroll = get_bankroll(env)
+            .checked_add(house_cut)
+            .ok_or(Error::Overflow)?;
+        env.storage().instance().set(&DataKey::Bankroll, &bankroll);
+    } else if winner_count > 0 {
+        house_cut = game.total_pot.checked_sub(owed).ok_or(Error::Overflow)?;
+        if house_cut > 0 {
+            balance_client.debit(&game_addr, &game_addr, &house_cut, &symbol_short!("fee"));
+            balance_client.credit(&game_addr, admin, &house_cut, &symbol_short!("fee"));
+        }
+    }
+
+    game.status = GameStatus::Resolved;
+    game.winning_color = winning_color;
+    game.winner_count = winner_count;
+    game.winning_total = winning_total;
+    game.net_pot = net_pot;
+    game.house_cut = house_cut;
+    persist_set(env, DataKey::Game(game_id), &game);
+
+    PredictionResolved {
+        game_id,
+        winning_color,
+        winner_count,
+        total_pot: game.total_pot,
+        net_pot,
+        house_cut,
+    }
+    .publish(env);
+
+    Ok(())
+}
+
 /// Persist a value in persistent storage and extend its TTL.
 fn persist_set<V: soroban_sdk::IntoVal<Env, soroban_sdk::Val>>(env: &Env, key: DataKey, val: &V) {
     env.storage().persistent().set(&key, val);
@@ -397,14 +1394,119 @@ fn persist_set<V: soroban_sdk::IntoVal<Env, soroban_sdk::Val>>(env: &Env, key: D
         .extend_ttl(&key, PERSISTENT_BUMP_LEDGERS, PERSISTENT_BUMP_LEDGERS);
 }
 
-// ---------------------------------------------------------------------------
-// Tests
-// ---------------------------------------------------------------------------
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::{
+        contract, contractimpl,
+        testutils::{Address as _, Ledger, MockAuth, MockAuthInvoke},
+        Bytes, BytesN, Env, IntoVal,
+    };
+    use stellarcade_random_generator::{RandomGenerator, RandomGeneratorClient};
+
+    #[contract]
+    pub struct MockBalance;
+
+    #[contracttype]
+    pub enum BalanceKey {
+        Balance(Address),
+    }
+
+    #[contractimpl]
+    impl MockBalance {
+        pub fn set_balance(env: Env, user: Address, amount: i128) {
+            env.storage()
+                .persistent()
+                .set(&BalanceKey::Balance(user), &amount);
+        }
+
+        pub fn credit(env: Env, _game: Address, user: Address, amount: i128, _reason: Symbol) {
+            let bal = Self::balance_of(env.clone(), user.clone());
+            env.storage()
+                .persistent()
+                .set(&BalanceKey::Balance(user), &(bal + amount));
+        }
+
+        pub fn debit(env: Env, _game: Address, user: Address, amount: i128, _reason: Symbol) {
+            let bal = Self::balance_of(env.clone(), user.clone());
+            if bal < amount {
+                panic!("insufficient balance");
+            }
+            env.storage()
+                .persistent()
+                .set(&BalanceKey::Balance(user), &(bal - amount));
+        }
+
+        pub fn balance_of(env: Env, user: Address) -> i128 {
+            env.storage()
+                .persistent()
+                .get(&BalanceKey::Balance(user))
+                .unwrap_or(0)
+        }
+    }
+
+    /// Prize pool stand-in that tracks each game's reservation and what it
+    /// paid to each player. Like the real pool, both calls need the admin's
+    /// signature.
+    #[contract]
+    pub struct MockPrizePool;
+
+    #[contracttype]
+    pub enum PoolKey {
+        Reserved(u64),
+        Paid(Address),
+    }
+
+    #[contractimpl]
+    impl MockPrizePool {
+        pub fn reserve(env: Env, admin: Address, game_id: u64, amount: i128) {
+            admin.require_auth();
+            if Self::reserved(env.clone(), game_id) > 0 {
+                panic!("game already reserved");
+            }
+            env.storage()
+                .persistent()
+                .set(&PoolKey::Reserved(game_id), &amount);
+        }
+
+        pub fn payout(env: Env, admin: Address, to: Address, game_id: u64, amount: i128) {
+            admin.require_auth();
+            let reserved = Self::reserved(env.clone(), game_id);
+            if amount > reserved {
+                panic!("payout exceeds reservation");
+            }
+            env.storage()
+                .persistent()
+                .set(&PoolKey::Reserved(game_id), &(reserved - amount));
+            let paid = Self::paid(env.clone(), to.clone()) + amount;
+            env.storage().persistent().set(&PoolKey::Paid(to), &paid);
+        }
+
+        pub fn reserved(env: Env, game_id: u64) -> i128 {
+            env.storage()
+                .persistent()
+                .get(&PoolKey::Reserved(game_id))
+                .unwrap_or(0)
+        }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::{testutils::Address as _, Env};
+        pub fn paid(env: Env, to: Address) -> i128 {
+            env.storage()
+                .persistent()
+                .get(&PoolKey::Paid(to))
+                .unwrap_or(0)
+        }
+    }
+
+    /// Generate a player address holding `1_000` in the mock balance contract.
+    fn funded_player(env: &Env, balance: &Address) -> Address {
+        let player = Address::generate(env);
+        MockBalanceClient::new(env, balance).set_balance(&player, &1_000);
+        player
+    }
 
     fn setup(
         env: &Env,
@@ -418,14 +1520,88 @@ mod test {
         let id = env.register(ColorPrediction, ());
         let client = ColorPredictionClient::new(env, &id);
         let admin = Address::generate(env);
-        let rng = Address::generate(env);
-        let prize_pool = Address::generate(env);
-        let balance = Address::generate(env);
+        let rng = env.register(RandomGenerator, ());
+        let prize_pool = env.register(MockPrizePool, ());
+        let balance = env.register(MockBalance, ());
         env.mock_all_auths();
+        // The admin doubles as the RNG oracle in tests.
+        let rng_client = RandomGeneratorClient::new(env, &rng);
+        rng_client.init(&admin, &admin);
+        rng_client.authorize(&admin, &id);
         client.init(&admin, &rng, &prize_pool, &balance);
         (client, admin, rng, prize_pool, balance)
     }
 
+    /// Fulfill the RNG request for `game_id` and return the raw roll.
+    fn fulfill(env: &Env, rng: &Address, oracle: &Address, game_id: u64, byte: u8) -> u64 {
+        let mut seed = [0u8; 32];
+        seed[31] = byte;
+        let rng_client = RandomGeneratorClient::new(env, rng);
+        rng_client.fulfill_random(oracle, &game_id, &BytesN::from_array(env, &seed));
+        rng_client.get_result(&game_id).result
+    }
+
+    /// Lock `game_id`, fulfill its RNG request with the first seed whose roll
+    /// lands on `color`, and resolve it. Rolls are derived as in the RNG
+    /// contract: the first 8 bytes of sha256(seed ‖ request id), mod total.
+    fn resolve_to(
+        env: &Env,
+        client: &ColorPredictionClient,
+        rng: &Address,
+        oracle: &Address,
+        game_id: u64,
+        color: u32,
+    ) {
+        client.request_outcome(&game_id);
+        let game = client.get_game(&game_id).unwrap();
+        let weights = env.as_contract(&client.address, || color_weights(env, game_id, &game));
+        let total: u64 = weights.iter().map(u64::from).sum();
+        for byte in 0..=u8::MAX {
+            let mut preimage = [0u8; 40];
+            preimage[31] = byte;
+            preimage[32..].copy_from_slice(&game_id.to_be_bytes());
+            let digest = env.crypto().sha256(&Bytes::from_slice(env, &preimage));
+            let raw = u64::from_be_bytes(digest.to_array()[..8].try_into().unwrap());
+            if pick_color(&weights, raw % total) == color {
+                fulfill(env, rng, oracle, game_id, byte);
+                client.resolve_from_rng(&game_id);
+                return;
+            }
+        }
+        panic!("no seed lands on color {}", color);
+    }
+
+    /// Give the admin `amount` and deposit all of it into the house bankroll.
+    fn fund_bankroll(
+        env: &Env,
+        client: &ColorPredictionClient,
+        balance: &Address,
+        admin: &Address,
+        amount: i128,
+    ) {
+        MockBalanceClient::new(env, balance).set_balance(admin, &amount);
+        client.deposit_bankroll(&amount);
+    }
+
+    /// A three-color table: two common colors at 1.9x and a rare one at 4.5x.
+    fn three_color_table(env: &Env) -> Vec<ColorOdds> {
+        soroban_sdk::vec![
+            env,
+            ColorOdds {
+                weight: 45,
+                multiplier_bps: 19_000,
+            },
+            ColorOdds {
+                weight: 45,
+                multiplier_bps: 19_000,
+            },
+            ColorOdds {
+                weight: 10,
+                multiplier_bps: 45_000,
+            },
+        ]
+    }
+
     // ------------------------------------------------------------------
     // 1. Happy path: place predictions → resolve → inspect state
     // ------------------------------------------------------------------
@@ -433,12 +1609,12 @@ mod test {
     #[test]
     fn test_full_happy_path() {
         let env = Env::default();
-        let (client, _, _, _, _) = setup(&env);
+        let (client, _, _, _, balance) = setup(&env);
         env.mock_all_auths();
 
         let game_id: u64 = 1;
-        let winner = Address::generate(&env);
-        let loser = Address::generate(&env);
+        let winner = funded_player(&env, &balance);
+        let loser = funded_player(&env, &balance);
 
         client.place_prediction(&winner, &COLOR_RED, &100i128, &game_id);
         client.place_prediction(&loser, &COLOR_BLUE, &100i128, &game_id);
@@ -460,13 +1636,13 @@ mod test {
     #[test]
     fn test_all_winners() {
         let env = Env::default();
-        let (client, _, _, _, _) = setup(&env);
+        let (client, _, _, _, balance) = setup(&env);
         env.mock_all_auths();
 
         let game_id: u64 = 2;
-        let p1 = Address::generate(&env);
-        let p2 = Address::generate(&env);
-        let p3 = Address::generate(&env);
+        let p1 = funded_player(&env, &balance);
+        let p2 = funded_player(&env, &balance);
+        let p3 = funded_player(&env, &balance);
 
         client.place_prediction(&p1, &COLOR_GREEN, &50i128, &game_id);
         client.place_prediction(&p2, &COLOR_GREEN, &50i128, &game_id);
@@ -486,11 +1662,11 @@ mod test {
     #[test]
     fn test_no_winners() {
         let env = Env::default();
-        let (client, _, _, _, _) = setup(&env);
+        let (client, _, _, _, balance) = setup(&env);
         env.mock_all_auths();
 
         let game_id: u64 = 3;
-        let player = Address::generate(&env);
+        let player = funded_player(&env, &balance);
         client.place_prediction(&player, &COLOR_RED, &200i128, &game_id);
 
         client.resolve_prediction(&game_id, &COLOR_BLUE);
@@ -501,21 +1677,21 @@ mod test {
     }
 
     // ------------------------------------------------------------------
-    // 4. Duplicate prediction rejected
+    // 4. Duplicate stake on the same color rejected
     // ------------------------------------------------------------------
 
     #[test]
     fn test_duplicate_prediction_rejected() {
         let env = Env::default();
-        let (client, _, _, _, _) = setup(&env);
+        let (client, _, _, _, balance) = setup(&env);
         env.mock_all_auths();
 
         let game_id: u64 = 4;
-        let player = Address::generate(&env);
+        let player = funded_player(&env, &balance);
         client.place_prediction(&player, &COLOR_RED, &100i128, &game_id);
 
-        let result = client.try_place_prediction(&player, &COLOR_GREEN, &100i128, &game_id);
-        assert!(result.is_err());
+        let result = client.try_place_prediction(&player, &COLOR_RED, &100i128, &game_id);
+        assert_eq!(result, Err(Ok(Error::AlreadyPredicted)));
     }
 
     // ------------------------------------------------------------------
@@ -525,15 +1701,15 @@ mod test {
     #[test]
     fn test_predict_on_resolved_game_rejected() {
         let env = Env::default();
-        let (client, _, _, _, _) = setup(&env);
+        let (client, _, _, _, balance) = setup(&env);
         env.mock_all_auths();
 
         let game_id: u64 = 5;
-        let p1 = Address::generate(&env);
+        let p1 = funded_player(&env, &balance);
         client.place_prediction(&p1, &COLOR_RED, &100i128, &game_id);
         client.resolve_prediction(&game_id, &COLOR_RED);
 
-        let late = Address::generate(&env);
+        let late = funded_player(&env, &balance);
         let result = client.try_place_prediction(&late, &COLOR_RED, &100i128, &game_id);
         assert!(result.is_err());
     }
@@ -545,11 +1721,11 @@ mod test {
     #[test]
     fn test_double_resolve_rejected() {
         let env = Env::default();
-        let (client, _, _, _, _) = setup(&env);
+        let (client, _, _, _, balance) = setup(&env);
         env.mock_all_auths();
 
         let game_id: u64 = 6;
-        let player = Address::generate(&env);
+        let player = funded_player(&env, &balance);
         client.place_prediction(&player, &COLOR_YELLOW, &10i128, &game_id);
         client.resolve_prediction(&game_id, &COLOR_YELLOW);
 
@@ -564,11 +1740,11 @@ mod test {
     #[test]
     fn test_invalid_color_on_place_rejected() {
         let env = Env::default();
-        let (client, _, _, _, _) = setup(&env);
+        let (client, _, _, _, balance) = setup(&env);
         env.mock_all_auths();
 
         let game_id: u64 = 7;
-        let player = Address::generate(&env);
+        let player = funded_player(&env, &balance);
         let result = client.try_place_prediction(&player, &99u32, &100i128, &game_id);
         assert!(result.is_err());
     }
@@ -580,11 +1756,11 @@ mod test {
     #[test]
     fn test_invalid_color_on_resolve_rejected() {
         let env = Env::default();
-        let (client, _, _, _, _) = setup(&env);
+        let (client, _, _, _, balance) = setup(&env);
         env.mock_all_auths();
 
         let game_id: u64 = 8;
-        let player = Address::generate(&env);
+        let player = funded_player(&env, &balance);
         client.place_prediction(&player, &COLOR_RED, &100i128, &game_id);
 
         let result = client.try_resolve_prediction(&game_id, &99u32);
@@ -598,11 +1774,11 @@ mod test {
     #[test]
     fn test_zero_wager_rejected() {
         let env = Env::default();
-        let (client, _, _, _, _) = setup(&env);
+        let (client, _, _, _, balance) = setup(&env);
         env.mock_all_auths();
 
         let game_id: u64 = 9;
-        let player = Address::generate(&env);
+        let player = funded_player(&env, &balance);
         let result = client.try_place_prediction(&player, &COLOR_RED, &0i128, &game_id);
         assert!(result.is_err());
     }
@@ -614,11 +1790,11 @@ mod test {
     #[test]
     fn test_negative_wager_rejected() {
         let env = Env::default();
-        let (client, _, _, _, _) = setup(&env);
+        let (client, _, _, _, balance) = setup(&env);
         env.mock_all_auths();
 
         let game_id: u64 = 10;
-        let player = Address::generate(&env);
+        let player = funded_player(&env, &balance);
         let result = client.try_place_prediction(&player, &COLOR_RED, &-50i128, &game_id);
         assert!(result.is_err());
     }
@@ -638,7 +1814,7 @@ mod test {
         client2.init(&admin, &rng, &prize_pool, &balance);
 
         let game_id: u64 = 11;
-        let player = Address::generate(&env);
+        let player = funded_player(&env, &balance);
         client2.place_prediction(&player, &COLOR_RED, &100i128, &game_id);
 
         let imposter = Address::generate(&env);
@@ -711,11 +1887,11 @@ mod test {
     #[test]
     fn test_multiple_games_independent() {
         let env = Env::default();
-        let (client, _, _, _, _) = setup(&env);
+        let (client, _, _, _, balance) = setup(&env);
         env.mock_all_auths();
 
-        let p1 = Address::generate(&env);
-        let p2 = Address::generate(&env);
+        let p1 = funded_player(&env, &balance);
+        let p2 = funded_player(&env, &balance);
 
         client.place_prediction(&p1, &COLOR_RED, &100i128, &1u64);
         client.place_prediction(&p2, &COLOR_BLUE, &200i128, &2u64);
@@ -739,7 +1915,7 @@ mod test {
     #[test]
     fn test_all_valid_colors_accepted() {
         let env = Env::default();
-        let (client, _, _, _, _) = setup(&env);
+        let (client, _, _, _, balance) = setup(&env);
         env.mock_all_auths();
 
         for (game_id, color) in [
@@ -748,11 +1924,717 @@ mod test {
             (22u64, COLOR_BLUE),
             (23u64, COLOR_YELLOW),
         ] {
-            let player = Address::generate(&env);
+            let player = funded_player(&env, &balance);
             client.place_prediction(&player, &color, &10i128, &game_id);
             client.resolve_prediction(&game_id, &color);
             let game = client.get_game(&game_id).unwrap();
             assert_eq!(game.winner_count, 1);
         }
     }
+
+    // ------------------------------------------------------------------
+    // 17. Wagers are escrowed through the balance contract
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_wager_escrowed_on_placement() {
+        let env = Env::default();
+        let (client, _, _, _, balance) = setup(&env);
+        env.mock_all_auths();
+        let balance_client = MockBalanceClient::new(&env, &balance);
+
+        let player = funded_player(&env, &balance);
+        client.place_prediction(&player, &COLOR_RED, &300i128, &30u64);
+
+        assert_eq!(balance_client.balance_of(&player), 700);
+        assert_eq!(balance_client.balance_of(&client.address), 300);
+    }
+
+    // ------------------------------------------------------------------
+    // 18. Insufficient balance rejected
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_insufficient_balance_rejected() {
+        let env = Env::default();
+        let (client, _, _, _, balance) = setup(&env);
+        env.mock_all_auths();
+
+        let player = funded_player(&env, &balance);
+        let result = client.try_place_prediction(&player, &COLOR_RED, &1_001i128, &31u64);
+        assert_eq!(result, Err(Ok(Error::InsufficientBalance)));
+        assert!(client.get_game(&31u64).is_none());
+    }
+
+    // ------------------------------------------------------------------
+    // 19. Winners split the net pot proportionally to their wagers
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_claim_proportional_payout_minus_fee() {
+        let env = Env::default();
+        let (client, admin, _, prize_pool, balance) = setup(&env);
+        env.mock_all_auths();
+        let balance_client = MockBalanceClient::new(&env, &balance);
+        let pool = MockPrizePoolClient::new(&env, &prize_pool);
+
+        client.set_house_edge(&500u32);
+
+        let game_id: u64 = 32;
+        let big = funded_player(&env, &balance);
+        let small = funded_player(&env, &balance);
+        let loser = funded_player(&env, &balance);
+        client.place_prediction(&big, &COLOR_GREEN, &300i128, &game_id);
+        client.place_prediction(&small, &COLOR_GREEN, &100i128, &game_id);
+        client.place_prediction(&loser, &COLOR_RED, &400i128, &game_id);
+
+        client.resolve_prediction(&game_id, &COLOR_GREEN);
+
+        // pot 800, fee 5% = 40; the net 760 is reserved in the prize pool
+        // and the whole escrow goes to the admin.
+        let game = client.get_game(&game_id).unwrap();
+        assert_eq!(game.winning_total, 400);
+        assert_eq!(game.net_pot, 760);
+        assert_eq!(game.house_cut, 40);
+        assert_eq!(pool.reserved(&game_id), 760);
+        assert_eq!(balance_client.balance_of(&admin), 800);
+        assert_eq!(balance_client.balance_of(&client.address), 0);
+
+        assert_eq!(client.claim(&big, &game_id), 570);
+        assert_eq!(client.claim(&small, &game_id), 190);
+        assert_eq!(pool.paid(&big), 570);
+        assert_eq!(pool.paid(&small), 190);
+        assert_eq!(balance_client.balance_of(&big), 700);
+
+        // The reservation is used up once every winner has claimed.
+        assert_eq!(pool.reserved(&game_id), 0);
+
+        let result = client.try_claim(&loser, &game_id);
+        assert_eq!(result, Err(Ok(Error::NoPayout)));
+    }
+
+    // ------------------------------------------------------------------
+    // 20. No-winner games refund every wager without a fee
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_no_winner_game_refunds_wagers() {
+        let env = Env::default();
+        let (client, admin, _, prize_pool, balance) = setup(&env);
+        env.mock_all_auths();
+        let balance_client = MockBalanceClient::new(&env, &balance);
+        let pool = MockPrizePoolClient::new(&env, &prize_pool);
+
+        client.set_house_edge(&1_000u32);
+
+        let game_id: u64 = 33;
+        let p1 = funded_player(&env, &balance);
+        let p2 = funded_player(&env, &balance);
+        client.place_prediction(&p1, &COLOR_RED, &150i128, &game_id);
+        client.place_prediction(&p2, &COLOR_BLUE, &250i128, &game_id);
+
+        client.resolve_prediction(&game_id, &COLOR_YELLOW);
+
+        let game = client.get_game(&game_id).unwrap();
+        assert_eq!(game.net_pot, 0);
+        assert_eq!(game.house_cut, 0);
+        // The full pot is reserved for refunds.
+        assert_eq!(pool.reserved(&game_id), 400);
+        assert_eq!(balance_client.balance_of(&admin), 400);
+
+        assert_eq!(client.claim(&p1, &game_id), 150);
+        assert_eq!(client.claim(&p2, &game_id), 250);
+        assert_eq!(pool.paid(&p1), 150);
+        assert_eq!(pool.paid(&p2), 250);
+        assert_eq!(pool.reserved(&game_id), 0);
+    }
+
+    // ------------------------------------------------------------------
+    // 21. Claim guards: unresolved, double claim, non-participant
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_claim_guards() {
+        let env = Env::default();
+        let (client, _, _, _, balance) = setup(&env);
+        env.mock_all_auths();
+
+        let game_id: u64 = 34;
+        let player = funded_player(&env, &balance);
+        client.place_prediction(&player, &COLOR_BLUE, &100i128, &game_id);
+
+        let early = client.try_claim(&player, &game_id);
+        assert_eq!(early, Err(Ok(Error::GameNotResolved)));
+
+        client.resolve_prediction(&game_id, &COLOR_BLUE);
+        assert_eq!(client.claim(&player, &game_id), 100);
+
+        let again = client.try_claim(&player, &game_id);
+        assert_eq!(again, Err(Ok(Error::AlreadyClaimed)));
+
+        let stranger = Address::generate(&env);
+        let result = client.try_claim(&stranger, &game_id);
+        assert_eq!(result, Err(Ok(Error::PredictionNotFound)));
+    }
+
+    // ------------------------------------------------------------------
+    // 22. House edge above 100% rejected
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_invalid_house_edge_rejected() {
+        let env = Env::default();
+        let (client, _, _, _, _) = setup(&env);
+        env.mock_all_auths();
+
+        let result = client.try_set_house_edge(&(MAX_HOUSE_EDGE_BPS + 1));
+        assert_eq!(result, Err(Ok(Error::InvalidFee)));
+    }
+
+    // ------------------------------------------------------------------
+    // 23. A player can stake on several colors in one game
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_multiple_color_stakes() {
+        let env = Env::default();
+        let (client, admin, _, prize_pool, balance) = setup(&env);
+        env.mock_all_auths();
+        let balance_client = MockBalanceClient::new(&env, &balance);
+        let pool = MockPrizePoolClient::new(&env, &prize_pool);
+
+        let game_id: u64 = 40;
+        let hedger = funded_player(&env, &balance);
+        let rival = funded_player(&env, &balance);
+        client.place_prediction(&hedger, &COLOR_RED, &100i128, &game_id);
+        client.place_prediction(&hedger, &COLOR_BLUE, &50i128, &game_id);
+        client.place_prediction(&rival, &COLOR_RED, &300i128, &game_id);
+
+        let game = client.get_game(&game_id).unwrap();
+        assert_eq!(game.player_count, 2);
+        assert_eq!(game.total_pot, 450);
+        let entry = client.get_prediction(&game_id, &hedger).unwrap();
+        assert_eq!(entry.stakes.len(), 2);
+
+        client.resolve_prediction(&game_id, &COLOR_RED);
+        let game = client.get_game(&game_id).unwrap();
+        assert_eq!(game.winner_count, 2);
+        assert_eq!(game.winning_total, 400);
+
+        // Only the red stake counts: 450 × 100 / 400.
+        assert_eq!(client.claim(&hedger, &game_id), 112);
+        assert_eq!(pool.paid(&hedger), 112);
+        assert_eq!(client.claim(&rival, &game_id), 337);
+
+        // The remainder left by rounding both shares down goes to the house.
+        assert_eq!(client.get_game(&game_id).unwrap().house_cut, 1);
+        assert_eq!(pool.reserved(&game_id), 0);
+        assert_eq!(balance_client.balance_of(&admin), 450);
+        assert_eq!(balance_client.balance_of(&client.address), 0);
+    }
+
+    // ------------------------------------------------------------------
+    // 24. Color tables are validated and fixed once created
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_configure_game_validation() {
+        let env = Env::default();
+        let (client, _, _, _, balance) = setup(&env);
+        env.mock_all_auths();
+
+        let single = soroban_sdk::vec![
+            &env,
+            ColorOdds {
+                weight: 1,
+                multiplier_bps: 20_000,
+            },
+        ];
+        assert_eq!(
+            client.try_configure_game(&41u64, &single),
+            Err(Ok(Error::InvalidColorTable))
+        );
+
+        let zero_weight = soroban_sdk::vec![
+            &env,
+            ColorOdds {
+                weight: 0,
+                multiplier_bps: 20_000,
+            },
+            ColorOdds {
+                weight: 1,
+                multiplier_bps: 20_000,
+            },
+        ];
+        assert_eq!(
+            client.try_configure_game(&41u64, &zero_weight),
+            Err(Ok(Error::InvalidColorTable))
+        );
+
+        let no_profit = soroban_sdk::vec![
+            &env,
+            ColorOdds {
+                weight: 1,
+                multiplier_bps: 10_000,
+            },
+            ColorOdds {
+                weight: 1,
+                multiplier_bps: 20_000,
+            },
+        ];
+        assert_eq!(
+            client.try_configure_game(&41u64, &no_profit),
+            Err(Ok(Error::InvalidColorTable))
+        );
+
+        client.configure_game(&41u64, &three_color_table(&env));
+        assert_eq!(client.get_color_table(&41u64).unwrap().len(), 3);
+        assert!(client.get_game(&41u64).unwrap().fixed_odds);
+        assert_eq!(
+            client.try_configure_game(&41u64, &three_color_table(&env)),
+            Err(Ok(Error::GameAlreadyExists))
+        );
+
+        // Existing default games cannot be given a table either.
+        let player = funded_player(&env, &balance);
+        client.place_prediction(&player, &COLOR_RED, &10i128, &42u64);
+        assert_eq!(
+            client.try_configure_game(&42u64, &three_color_table(&env)),
+            Err(Ok(Error::GameAlreadyExists))
+        );
+    }
+
+    // ------------------------------------------------------------------
+    // 25. Color-table games pay fixed multipliers
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_fixed_odds_payout() {
+        let env = Env::default();
+        let (client, admin, rng, prize_pool, balance) = setup(&env);
+        env.mock_all_auths();
+        let balance_client = MockBalanceClient::new(&env, &balance);
+        let pool = MockPrizePoolClient::new(&env, &prize_pool);
+
+        fund_bankroll(&env, &client, &balance, &admin, 1_000);
+        assert_eq!(balance_client.balance_of(&client.address), 1_000);
+
+        let game_id: u64 = 43;
+        client.configure_game(&game_id, &three_color_table(&env));
+
+        let p1 = funded_player(&env, &balance);
+        let p2 = funded_player(&env, &balance);
+        client.place_prediction(&p1, &2u32, &100i128, &game_id);
+        // 100 at 4.5x can owe 450; 350 of it is allocated from the bankroll.
+        assert_eq!(client.get_game(&game_id).unwrap().bankroll, 350);
+        assert_eq!(client.get_bankroll(), 650);
+        client.place_prediction(&p2, &0u32, &200i128, &game_id);
+        let game = client.get_game(&game_id).unwrap();
+        assert_eq!(game.liability, 450);
+        assert_eq!(game.bankroll, 350);
+
+        // Index 3 is outside the three-color table.
+        let result = client.try_place_prediction(&p2, &3u32, &10i128, &game_id);
+        assert_eq!(result, Err(Ok(Error::InvalidColor)));
+
+        resolve_to(&env, &client, &rng, &admin, game_id, 2);
+        let game = client.get_game(&game_id).unwrap();
+        assert_eq!(game.net_pot, 450);
+        // 300 stakes + 350 allocation − 450 owed goes back to the bankroll.
+        assert_eq!(game.house_cut, 200);
+        assert_eq!(client.get_bankroll(), 850);
+        // The 450 owed is reserved in the pool and its escrow paid to the admin.
+        assert_eq!(pool.reserved(&game_id), 450);
+        assert_eq!(balance_client.balance_of(&admin), 450);
+
+        assert_eq!(client.claim(&p1, &game_id), 450);
+        assert_eq!(pool.paid(&p1), 450);
+        assert_eq!(client.try_claim(&p2, &game_id), Err(Ok(Error::NoPayout)));
+        // Only the bankroll is left in escrow, and the admin can take it out.
+        assert_eq!(balance_client.balance_of(&client.address), 850);
+        assert_eq!(
+            client.try_withdraw_bankroll(&851i128),
+            Err(Ok(Error::HouseInsufficientFunds))
+        );
+        client.withdraw_bankroll(&850i128);
+        assert_eq!(balance_client.balance_of(&admin), 1_300);
+        assert_eq!(balance_client.balance_of(&client.address), 0);
+    }
+
+    // ------------------------------------------------------------------
+    // 26. Fixed-odds games without a winner keep the stakes
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_fixed_odds_no_winner_no_refund() {
+        let env = Env::default();
+        let (client, admin, rng, _, balance) = setup(&env);
+        env.mock_all_auths();
+        fund_bankroll(&env, &client, &balance, &admin, 1_000);
+
+        let game_id: u64 = 44;
+        client.configure_game(&game_id, &three_color_table(&env));
+        let player = funded_player(&env, &balance);
+        client.place_prediction(&player, &0u32, &100i128, &game_id);
+
+        resolve_to(&env, &client, &rng, &admin, game_id, 1);
+        assert_eq!(client.get_game(&game_id).unwrap().net_pot, 0);
+        assert_eq!(
+            client.try_claim(&player, &game_id),
+            Err(Ok(Error::NoPayout))
+        );
+        // The losing stake and the 90 allocation return to the bankroll.
+        assert_eq!(client.get_game(&game_id).unwrap().house_cut, 190);
+        assert_eq!(client.get_bankroll(), 1_100);
+    }
+
+    // ------------------------------------------------------------------
+    // 27. RNG outcome follows the color weights
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_resolve_from_rng_weighted() {
+        let env = Env::default();
+        let (client, admin, rng, _, balance) = setup(&env);
+        env.mock_all_auths();
+        fund_bankroll(&env, &client, &balance, &admin, 1_000);
+
+        let game_id: u64 = 45;
+        client.configure_game(&game_id, &three_color_table(&env));
+        let player = funded_player(&env, &balance);
+        client.place_prediction(&player, &0u32, &100i128, &game_id);
+
+        // Resolving before the request or before fulfilment fails.
+        assert_eq!(
+            client.try_resolve_from_rng(&game_id),
+            Err(Ok(Error::GameNotLocked))
+        );
+        client.request_outcome(&game_id);
+        assert_eq!(
+            client.get_game(&game_id).unwrap().status,
+            GameStatus::Locked
+        );
+        assert_eq!(
+            client.try_resolve_from_rng(&game_id),
+            Err(Ok(Error::RngNotReady))
+        );
+
+        // Betting and manual resolution are closed while locked.
+        let late = funded_player(&env, &balance);
+        assert_eq!(
+            client.try_place_prediction(&late, &1u32, &10i128, &game_id),
+            Err(Ok(Error::GameLocked))
+        );
+        assert_eq!(
+            client.try_resolve_prediction(&game_id, &0u32),
+            Err(Ok(Error::GameLocked))
+        );
+
+        let roll = fulfill(&env, &rng, &admin, game_id, 7);
+        assert!(roll < 100);
+        client.resolve_from_rng(&game_id);
+
+        let expected = if roll < 45 {
+            0
+        } else if roll < 90 {
+            1
+        } else {
+            2
+        };
+        let game = client.get_game(&game_id).unwrap();
+        assert_eq!(game.status, GameStatus::Resolved);
+        assert_eq!(game.winning_color, expected);
+    }
+
+    // ------------------------------------------------------------------
+    // 28. Default games sample the four colors uniformly
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_resolve_from_rng_default_colors() {
+        let env = Env::default();
+        let (client, admin, rng, _, balance) = setup(&env);
+        env.mock_all_auths();
+
+        let game_id: u64 = 46;
+        let player = funded_player(&env, &balance);
+        client.place_prediction(&player, &COLOR_BLUE, &100i128, &game_id);
+        client.request_outcome(&game_id);
+
+        let roll = fulfill(&env, &rng, &admin, game_id, 3);
+        client.resolve_from_rng(&game_id);
+
+        let game = client.get_game(&game_id).unwrap();
+        assert_eq!(game.winning_color, roll as u32);
+        assert_eq!(
+            client.try_resolve_from_rng(&game_id),
+            Err(Ok(Error::GameAlreadyResolved))
+        );
+    }
+
+    // ------------------------------------------------------------------
+    // 29. Scheduled rounds enforce the betting cutoff
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_scheduled_round_betting_cutoff() {
+        let env = Env::default();
+        let (client, _, _, _, balance) = setup(&env);
+        env.mock_all_auths();
+        env.ledger().with_mut(|l| l.timestamp = 1_000);
+
+        client.start_rounds(&100u64, &60u64, &None);
+        let game = client.get_game(&100u64).unwrap();
+        assert_eq!(game.open_at, 1_000);
+        assert_eq!(game.lock_at, 1_060);
+        assert_eq!(client.get_schedule().unwrap().current_round, 100);
+
+        let player = funded_player(&env, &balance);
+        env.ledger().with_mut(|l| l.timestamp = 1_059);
+        client.place_prediction(&player, &COLOR_RED, &100i128, &100u64);
+
+        // Neither resolution path is available before lock_at.
+        assert_eq!(
+            client.try_resolve_prediction(&100u64, &COLOR_RED),
+            Err(Ok(Error::RoundStillOpen))
+        );
+        assert_eq!(
+            client.try_request_outcome(&100u64),
+            Err(Ok(Error::RoundStillOpen))
+        );
+        assert_eq!(client.try_advance_round(), Err(Ok(Error::RoundStillOpen)));
+
+        env.ledger().with_mut(|l| l.timestamp = 1_060);
+        assert_eq!(
+            client.try_place_prediction(&player, &COLOR_GREEN, &100i128, &100u64),
+            Err(Ok(Error::BettingClosed))
+        );
+    }
+
+    // ------------------------------------------------------------------
+    // 30. advance_round locks, resolves the previous round and opens the next
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_advance_round_cycle() {
+        let env = Env::default();
+        let (client, admin, rng, _, balance) = setup(&env);
+        env.mock_all_auths();
+        env.ledger().with_mut(|l| l.timestamp = 1_000);
+        fund_bankroll(&env, &client, &balance, &admin, 1_000);
+
+        client.start_rounds(&1u64, &60u64, &Some(three_color_table(&env)));
+        assert!(client.get_game(&1u64).unwrap().fixed_odds);
+
+        let player = funded_player(&env, &balance);
+        client.place_prediction(&player, &0u32, &100i128, &1u64);
+
+        env.ledger().with_mut(|l| l.timestamp = 1_060);
+        assert_eq!(client.advance_round(), 2);
+        assert_eq!(client.get_game(&1u64).unwrap().status, GameStatus::Locked);
+        let round2 = client.get_game(&2u64).unwrap();
+        assert_eq!(round2.status, GameStatus::Open);
+        assert_eq!(round2.lock_at, 1_120);
+        assert_eq!(client.get_color_table(&2u64).unwrap().len(), 3);
+
+        let roll = fulfill(&env, &rng, &admin, 1, 9);
+
+        env.ledger().with_mut(|l| l.timestamp = 1_120);
+        assert_eq!(client.advance_round(), 3);
+
+        let round1 = client.get_game(&1u64).unwrap();
+        assert_eq!(round1.status, GameStatus::Resolved);
+        let expected = if roll < 45 {
+            0
+        } else if roll < 90 {
+            1
+        } else {
+            2
+        };
+        assert_eq!(round1.winning_color, expected);
+        assert_eq!(client.get_game(&2u64).unwrap().status, GameStatus::Locked);
+        assert_eq!(client.get_game(&3u64).unwrap().status, GameStatus::Open);
+    }
+
+    // ------------------------------------------------------------------
+    // 31. advance_round leaves unfulfilled rounds locked
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_advance_round_skips_unfulfilled_previous() {
+        let env = Env::default();
+        let (client, _, _, _, _) = setup(&env);
+        env.mock_all_auths();
+        env.ledger().with_mut(|l| l.timestamp = 1_000);
+
+        assert_eq!(client.try_advance_round(), Err(Ok(Error::RoundsNotStarted)));
+        assert_eq!(
+            client.try_start_rounds(&1u64, &(MIN_ROUND_DURATION - 1), &None),
+            Err(Ok(Error::InvalidDuration))
+        );
+
+        client.start_rounds(&1u64, &60u64, &None);
+        env.ledger().with_mut(|l| l.timestamp = 1_060);
+        client.advance_round();
+        env.ledger().with_mut(|l| l.timestamp = 1_120);
+        client.advance_round();
+
+        // Round 1 was never fulfilled, so it is still waiting for the RNG.
+        assert_eq!(client.get_game(&1u64).unwrap().status, GameStatus::Locked);
+        assert_eq!(client.get_game(&2u64).unwrap().status, GameStatus::Locked);
+        assert_eq!(client.get_schedule().unwrap().current_round, 3);
+    }
+
+    // ------------------------------------------------------------------
+    // 32. Fixed-odds stakes need bankroll allocated to their own game
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_fixed_odds_stake_needs_bankroll() {
+        let env = Env::default();
+        let (client, admin, _, _, balance) = setup(&env);
+        env.mock_all_auths();
+        let balance_client = MockBalanceClient::new(&env, &balance);
+
+        client.configure_game(&50u64, &three_color_table(&env));
+        client.configure_game(&51u64, &three_color_table(&env));
+        let player = funded_player(&env, &balance);
+
+        // Without a bankroll nothing beyond the stakes can be owed.
+        assert_eq!(
+            client.try_place_prediction(&player, &0u32, &100i128, &50u64),
+            Err(Ok(Error::HouseInsufficientFunds))
+        );
+        assert_eq!(balance_client.balance_of(&player), 1_000);
+
+        // 100 at 1.9x needs 90 on top of the stake.
+        fund_bankroll(&env, &client, &balance, &admin, 90);
+        client.place_prediction(&player, &0u32, &100i128, &50u64);
+        assert_eq!(client.get_bankroll(), 0);
+
+        // A bet on another color is covered by the stakes already in.
+        client.place_prediction(&player, &1u32, &50i128, &50u64);
+        assert_eq!(client.get_game(&50u64).unwrap().bankroll, 90);
+
+        // Game 50's allocation cannot back a bet on game 51, even though the
+        // contract's escrow holds more than enough.
+        assert_eq!(balance_client.balance_of(&client.address), 240);
+        assert_eq!(
+            client.try_place_prediction(&player, &0u32, &100i128, &51u64),
+            Err(Ok(Error::HouseInsufficientFunds))
+        );
+        assert_eq!(
+            client.try_withdraw_bankroll(&1i128),
+            Err(Ok(Error::HouseInsufficientFunds))
+        );
+    }
+
+    // ------------------------------------------------------------------
+    // 33. Future round ids cannot be created by a prediction
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_future_round_not_created_by_prediction() {
+        let env = Env::default();
+        let (client, _, _, _, balance) = setup(&env);
+        env.mock_all_auths();
+        env.ledger().with_mut(|l| l.timestamp = 1_000);
+
+        // Ids below the first round stay free for ad-hoc games.
+        let player = funded_player(&env, &balance);
+        client.start_rounds(&10u64, &60u64, &None);
+        client.place_prediction(&player, &COLOR_RED, &10i128, &9u64);
+
+        // The next round id is reserved until advance_round opens it.
+        assert_eq!(
+            client.try_place_prediction(&player, &COLOR_RED, &10i128, &11u64),
+            Err(Ok(Error::GameNotFound))
+        );
+        assert!(client.get_game(&11u64).is_none());
+
+        env.ledger().with_mut(|l| l.timestamp = 1_060);
+        assert_eq!(client.advance_round(), 11);
+        client.place_prediction(&player, &COLOR_RED, &10i128, &11u64);
+    }
+
+    // ------------------------------------------------------------------
+    // 34. A keeper can settle rounds with winners without admin auth
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_keeper_advances_round_with_winners() {
+        let env = Env::default();
+        let (client, admin, rng, prize_pool, balance) = setup(&env);
+        env.mock_all_auths();
+        env.ledger().with_mut(|l| l.timestamp = 1_000);
+        let balance_client = MockBalanceClient::new(&env, &balance);
+
+        client.set_house_edge(&500u32);
+        client.start_rounds(&1u64, &60u64, &None);
+        let red = funded_player(&env, &balance);
+        let green = funded_player(&env, &balance);
+        let blue = funded_player(&env, &balance);
+        let yellow = funded_player(&env, &balance);
+        client.place_prediction(&red, &COLOR_RED, &100i128, &1u64);
+        client.place_prediction(&green, &COLOR_GREEN, &100i128, &1u64);
+        client.place_prediction(&blue, &COLOR_BLUE, &100i128, &1u64);
+        client.place_prediction(&yellow, &COLOR_YELLOW, &100i128, &1u64);
+
+        env.ledger().with_mut(|l| l.timestamp = 1_060);
+        client.advance_round();
+        fulfill(&env, &rng, &admin, 1, 7);
+
+        // From here on only the keeper's own call is authorized.
+        let keeper = Address::generate(&env);
+        env.mock_auths(&[MockAuth {
+            address: &keeper,
+            invoke: &MockAuthInvoke {
+                contract: &client.address,
+                fn_name: "advance_round",
+                args: ().into_val(&env),
+                sub_invokes: &[],
+            },
+        }]);
+        env.ledger().with_mut(|l| l.timestamp = 1_120);
+        assert_eq!(client.advance_round(), 3);
+
+        // Every color was backed, so round 1 has a winner and a house cut.
+        let round1 = client.get_game(&1u64).unwrap();
+        assert_eq!(round1.status, GameStatus::Resolved);
+        assert_eq!(round1.winner_count, 1);
+        assert_eq!(round1.house_cut, 20);
+        assert_eq!(balance_client.balance_of(&admin), 400);
+        // The game contract reserved the winner's share without admin auth.
+        let pool = MockPrizePoolClient::new(&env, &prize_pool);
+        assert_eq!(pool.reserved(&1u64), 380);
+    }
+
+    // ------------------------------------------------------------------
+    // 35. Only the RNG picks the outcome of a color-table game
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_fixed_odds_rejects_declared_outcome() {
+        let env = Env::default();
+        let (client, admin, rng, _, balance) = setup(&env);
+        env.mock_all_auths();
+        fund_bankroll(&env, &client, &balance, &admin, 1_000);
+
+        let game_id: u64 = 52;
+        client.configure_game(&game_id, &three_color_table(&env));
+        let player = funded_player(&env, &balance);
+        client.place_prediction(&player, &2u32, &100i128, &game_id);
+
+        // The house cannot declare the color it pays the least on.
+        assert_eq!(
+            client.try_resolve_prediction(&game_id, &0u32),
+            Err(Ok(Error::OutcomeNeedsRng))
+        );
+        assert_eq!(client.get_game(&game_id).unwrap().status, GameStatus::Open);
+
+        resolve_to(&env, &client, &rng, &admin, game_id, 0);
+        let game = client.get_game(&game_id).unwrap();
+        assert_eq!(game.status, GameStatus::Resolved);
+        assert_eq!(game.winning_color, 0);
+    }
 }
@@ -1,15 +1,45 @@
 //! Stellarcade Daily Trivia Contract
 //!
-//! Players can submit one answer per round/day. Correct answers share a
-//! fixed reward amount reserved for that round.
+//! Players can submit one entry per round/day. A round holds up to
+//! `MAX_QUESTIONS` questions and rewards are split by how many each player
+//! answered correctly, either proportionally or by tiers set per round.
+//!
+//! Answers use salted commit-reveal so they cannot be copied from pending
+//! transactions or events, nor brute-forced from the stored commitments:
+//! 1. The admin opens a round with `sha256(answer_i || salt)` per question.
+//! 2. Players submit `sha256(answers_xdr || salt || player)` while it is open.
+//! 3. The admin closes the round by revealing the answers and salt.
+//! 4. Players reveal their own answers and salt within the reveal window;
+//!    scores are only known at this point.
+//! 5. After the window, the admin finalizes and winners claim.
+//!
+//! Winners have until the claim deadline to claim; the admin can then sweep
+//! whatever is left of the reservation back to the prize pool.
+//!
+//! Rounds may charge an entry fee, debited through the balance contract at
+//! submit. At finalize, fee management charges the game's rake on the entry
+//! pot and the rest is added to the seeded reward. Claims draw on the prize
+//! pool reservation first; entry funds nobody wins go to the admin.
 #![no_std]
 #![allow(unexpected_cfgs)]
 
 use soroban_sdk::{
     contract, contractclient, contracterror, contractevent, contractimpl, contracttype,
-    symbol_short, Address, Bytes, BytesN, Env, Symbol,
+    symbol_short, xdr::ToXdr, Address, Bytes, BytesN, Env, Symbol, Vec,
 };
 
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+/// Default time players have to reveal after a round is closed, in seconds.
+pub const DEFAULT_REVEAL_WINDOW: u64 = 3_600;
+/// Default time winners have to claim after a round is finalized, in seconds.
+pub const DEFAULT_CLAIM_WINDOW: u64 = 604_800;
+/// Maximum number of questions in a round.
+pub const MAX_QUESTIONS: u32 = 10;
+const BASIS_POINTS_DIVISOR: i128 = 10_000;
+
 // ---------------------------------------------------------------------------
 // External contract clients
 // ---------------------------------------------------------------------------
@@ -28,6 +58,17 @@ pub trait UserBalanceContract {
     fn balance_of(env: Env, user: Address) -> i128;
 }
 
+#[contractclient(name = "FeeManagementClient")]
+pub trait FeeManagementContract {
+    fn charge_fee_with_id(
+        env: Env,
+        game_id: Symbol,
+        amount: i128,
+        charge_id: u64,
+        token: Option<Address>,
+    ) -> i128;
+}
+
 // ---------------------------------------------------------------------------
 // Errors
 // ---------------------------------------------------------------------------
@@ -48,6 +89,19 @@ pub enum Error {
     NoRewardAvailable = 10,
     InvalidAmount = 11,
     Overflow = 12,
+    InvalidReveal = 13,
+    AlreadyRevealed = 14,
+    RevealWindowOpen = 15,
+    RevealWindowClosed = 16,
+    NotRevealPhase = 17,
+    SubmissionNotFound = 18,
+    InvalidQuestions = 19,
+    InvalidTiers = 20,
+    InvalidAnswerCount = 21,
+    ClaimWindowOpen = 22,
+    ClaimWindowClosed = 23,
+    AlreadySwept = 24,
+    FeeNotConfigured = 25,
 }
 
 // ---------------------------------------------------------------------------
@@ -59,25 +113,76 @@ pub enum Error {
 pub enum RoundStatus {
     Open = 0,
     Closed = 1,
+    Revealing = 2,
+    /// Finalized and past the claim deadline; unclaimed reward returned.
+    Swept = 3,
+}
+
+/// A reward tier: players with at least `min_correct` correct answers (and
+/// below the next tier) share `share_bps` of the round reward equally.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RewardTier {
+    pub min_correct: u32,
+    pub share_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum ScoringMode {
+    /// Each player gets `reward × score / total_score`.
+    Proportional,
+    /// Tiers with strictly increasing `min_correct`; shares sum to at most
+    /// 10000 bps.
+    Tiered(Vec<RewardTier>),
 }
 
 #[contracttype]
 #[derive(Clone)]
 pub struct RoundData {
-    pub answer_commitment: BytesN<32>,
+    /// `sha256(answer_i || salt)` per question, published by the admin at open.
+    pub answer_commitments: Vec<BytesN<32>>,
+    /// The answers revealed at close; empty while the round is open.
+    pub revealed_answers: Vec<Bytes>,
+    pub scoring: ScoringMode,
+    /// Seeded reward reserved in the prize pool; may be 0 for paid rounds.
     pub reward_amount: i128,
-    pub payout_per_winner: i128,
+    /// Entry fee debited from each player at submit; 0 for free rounds.
+    pub entry_fee: i128,
+    /// Entry fees collected so far.
+    pub entry_pot: i128,
+    /// Rake charged by fee management on the entry pot at finalize.
+    pub rake: i128,
+    /// Players whose score earns a payout.
     pub winner_count: u32,
+    /// Sum of all revealed scores.
+    pub total_score: u32,
+    /// Tiered rounds only: number of players in each tier.
+    pub tier_counts: Vec<u32>,
     pub status: RoundStatus,
     pub opened_at: u64,
     pub closed_at: u64,
+    pub reveal_deadline: u64,
+    /// Last timestamp at which rewards can be claimed; set at finalize.
+    pub claim_deadline: u64,
+    /// Total paid out to players so far.
+    pub claimed_total: i128,
+    /// Total released back to the prize pool so far.
+    pub released: i128,
+    /// Unawarded entry funds credited to the admin so far.
+    pub forwarded: i128,
+    /// Part of `claimed_total` paid out of the prize pool reservation.
+    pub pool_paid: i128,
 }
 
 #[contracttype]
 #[derive(Clone)]
 pub struct Submission {
-    pub answer_hash: BytesN<32>,
-    pub correct: bool,
+    /// `sha256(answers_xdr || salt || player)`.
+    pub commitment: BytesN<32>,
+    pub revealed: bool,
+    /// Number of correct answers, set at reveal.
+    pub score: u32,
     pub claimed: bool,
 }
 
@@ -86,6 +191,10 @@ pub enum DataKey {
     Admin,
     PrizePoolContract,
     BalanceContract,
+    RevealWindow,
+    ClaimWindow,
+    FeeContract,
+    FeeGameId,
     Round(u64),
     Submission(u64, Address),
 }
@@ -99,6 +208,8 @@ pub struct RoundOpened {
     #[topic]
     pub round_id: u64,
     pub reward_amount: i128,
+    pub entry_fee: i128,
+    pub questions: u32,
 }
 
 #[contractevent]
@@ -106,7 +217,22 @@ pub struct AnswerSubmitted {
     #[topic]
     pub round_id: u64,
     pub player: Address,
-    pub correct: bool,
+}
+
+#[contractevent]
+pub struct AnswerKeyRevealed {
+    #[topic]
+    pub round_id: u64,
+    pub answers: Vec<Bytes>,
+    pub reveal_deadline: u64,
+}
+
+#[contractevent]
+pub struct AnswerRevealed {
+    #[topic]
+    pub round_id: u64,
+    pub player: Address,
+    pub score: u32,
 }
 
 #[contractevent]
@@ -114,7 +240,11 @@ pub struct RoundClosed {
     #[topic]
     pub round_id: u64,
     pub winners: u32,
-    pub payout_per_winner: i128,
+    pub total_score: u32,
+    pub released: i128,
+    pub rake: i128,
+    pub forwarded: i128,
+    pub claim_deadline: u64,
 }
 
 #[contractevent]
@@ -125,6 +255,13 @@ pub struct RewardClaimed {
     pub amount: i128,
 }
 
+#[contractevent]
+pub struct UnclaimedSwept {
+    #[topic]
+    pub round_id: u64,
+    pub amount: i128,
+}
+
 // ---------------------------------------------------------------------------
 // Contract
 // ---------------------------------------------------------------------------
@@ -154,49 +291,120 @@ impl DailyTrivia {
         Ok(())
     }
 
+    /// Set how long players have to reveal after a round closes. Admin only.
+    pub fn set_reveal_window(env: Env, window: u64) -> Result<(), Error> {
+        require_admin(&env)?;
+        env.storage().instance().set(&DataKey::RevealWindow, &window);
+        Ok(())
+    }
+
+    /// Set how long winners have to claim after a round is finalized.
+    /// Admin only; applies to rounds finalized afterwards.
+    pub fn set_claim_window(env: Env, window: u64) -> Result<(), Error> {
+        require_admin(&env)?;
+        env.storage().instance().set(&DataKey::ClaimWindow, &window);
+        Ok(())
+    }
+
+    /// Set the fee management contract that charges the entry-fee rake and
+    /// the game id whose fee config sets it. Admin only; applies to rounds
+    /// finalized afterwards.
+    pub fn set_fee_config(env: Env, fee_contract: Address, game_id: Symbol) -> Result<(), Error> {
+        require_admin(&env)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::FeeContract, &fee_contract);
+        env.storage().instance().set(&DataKey::FeeGameId, &game_id);
+        Ok(())
+    }
+
+    /// Open a round of 1 to `MAX_QUESTIONS` questions. Each entry of
+    /// `answer_commitments` must be `sha256(answer_i || salt)` with one salt
+    /// the admin keeps secret until `close_round`.
+    ///
+    /// `reward_amount` is reserved in the prize pool and `entry_fee` is
+    /// charged to every player; either may be 0, but not both. Paid rounds
+    /// need the fee config to be set.
     pub fn open_round(
         env: Env,
         round_id: u64,
-        answer_commitment: BytesN<32>,
+        answer_commitments: Vec<BytesN<32>>,
         reward_amount: i128,
+        entry_fee: i128,
+        scoring: ScoringMode,
     ) -> Result<(), Error> {
         let admin = require_admin(&env)?;
-        require_positive(reward_amount)?;
+        validate_funding(reward_amount, entry_fee)?;
+        if entry_fee > 0 && !env.storage().instance().has(&DataKey::FeeContract) {
+            return Err(Error::FeeNotConfigured);
+        }
+
+        let questions = answer_commitments.len();
+        if questions == 0 || questions > MAX_QUESTIONS {
+            return Err(Error::InvalidQuestions);
+        }
+        let mut tier_counts = Vec::new(&env);
+        if let ScoringMode::Tiered(tiers) = &scoring {
+            validate_tiers(tiers, questions)?;
+            for _ in tiers.iter() {
+                tier_counts.push_back(0u32);
+            }
+        }
 
         let key = DataKey::Round(round_id);
         if env.storage().persistent().has(&key) {
             return Err(Error::RoundAlreadyExists);
         }
 
-        let prize_pool = get_prize_pool(&env)?;
-        let pool_client = PrizePoolClient::new(&env, &prize_pool);
-        pool_client.reserve(&admin, &round_id, &reward_amount);
+        if reward_amount > 0 {
+            let prize_pool = get_prize_pool(&env)?;
+            let pool_client = PrizePoolClient::new(&env, &prize_pool);
+            pool_client.reserve(&admin, &round_id, &reward_amount);
+        }
 
         let now = env.ledger().timestamp();
         let round = RoundData {
-            answer_commitment,
+            answer_commitments,
+            revealed_answers: Vec::new(&env),
+            scoring,
             reward_amount,
-            payout_per_winner: 0,
+            entry_fee,
+            entry_pot: 0,
+            rake: 0,
             winner_count: 0,
+            total_score: 0,
+            tier_counts,
             status: RoundStatus::Open,
             opened_at: now,
             closed_at: 0,
+            reveal_deadline: 0,
+            claim_deadline: 0,
+            claimed_total: 0,
+            released: 0,
+            forwarded: 0,
+            pool_paid: 0,
         };
         env.storage().persistent().set(&key, &round);
 
         RoundOpened {
             round_id,
             reward_amount,
+            entry_fee,
+            questions,
         }
         .publish(&env);
         Ok(())
     }
 
+    /// Commit to a set of answers while the round is open. `commitment` must
+    /// be `sha256(answers_xdr || salt || player_xdr)`, with a salt only the
+    /// player knows. Nothing about correctness is revealed until
+    /// `reveal_answer`. Paid rounds debit the entry fee from the player.
     pub fn submit_answer(
         env: Env,
         player: Address,
         round_id: u64,
-        answer_payload: Bytes,
+        commitment: BytesN<32>,
     ) -> Result<(), Error> {
         require_initialized(&env)?;
         player.require_auth();
@@ -220,35 +428,52 @@ impl DailyTrivia {
             return Err(Error::AlreadySubmitted);
         }
 
-        let answer_hash: BytesN<32> = env.crypto().sha256(&answer_payload).into();
-        let correct = answer_hash == round.answer_commitment;
-
-        if correct {
-            round.winner_count = round
-                .winner_count
-                .checked_add(1)
+        if round.entry_fee > 0 {
+            let balance_contract = get_balance_contract(&env)?;
+            let balance_client = BalanceClient::new(&env, &balance_contract);
+            let contract_addr = env.current_contract_address();
+            balance_client.debit(
+                &contract_addr,
+                &player,
+                &round.entry_fee,
+                &symbol_short!("entry"),
+            );
+            balance_client.credit(
+                &contract_addr,
+                &contract_addr,
+                &round.entry_fee,
+                &symbol_short!("entry"),
+            );
+            round.entry_pot = round
+                .entry_pot
+                .checked_add(round.entry_fee)
                 .ok_or(Error::Overflow)?;
             env.storage().persistent().set(&key, &round);
         }
 
         let submission = Submission {
-            answer_hash,
-            correct,
+            commitment,
+            revealed: false,
+            score: 0,
             claimed: false,
         };
         env.storage().persistent().set(&submission_key, &submission);
 
-        AnswerSubmitted {
-            round_id,
-            player,
-            correct,
-        }
-        .publish(&env);
+        AnswerSubmitted { round_id, player }.publish(&env);
         Ok(())
     }
 
-    pub fn close_round(env: Env, round_id: u64) -> Result<(), Error> {
-        let admin = require_admin(&env)?;
+    /// Close submissions and reveal the answer key. Admin only.
+    ///
+    /// `sha256(answers[i] || salt)` must match every commitment given at
+    /// open. Players then have the reveal window to call `reveal_answer`.
+    pub fn close_round(
+        env: Env,
+        round_id: u64,
+        answers: Vec<Bytes>,
+        salt: Bytes,
+    ) -> Result<(), Error> {
+        require_admin(&env)?;
         let key = DataKey::Round(round_id);
         let mut round: RoundData = env
             .storage()
@@ -263,31 +488,198 @@ impl DailyTrivia {
         if now < round.opened_at {
             return Err(Error::RoundNotOpen);
         }
+        if answers.len() != round.answer_commitments.len() {
+            return Err(Error::InvalidAnswerCount);
+        }
+        for (answer, commitment) in answers.iter().zip(round.answer_commitments.iter()) {
+            if answer_commitment(&env, &answer, &salt) != commitment {
+                return Err(Error::InvalidReveal);
+            }
+        }
+
+        let window: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::RevealWindow)
+            .unwrap_or(DEFAULT_REVEAL_WINDOW);
+        let reveal_deadline = now.checked_add(window).ok_or(Error::Overflow)?;
+
+        round.status = RoundStatus::Revealing;
+        round.closed_at = now;
+        round.reveal_deadline = reveal_deadline;
+        round.revealed_answers = answers.clone();
+        env.storage().persistent().set(&key, &round);
+
+        AnswerKeyRevealed {
+            round_id,
+            answers,
+            reveal_deadline,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Reveal committed answers after the round is closed, up to and
+    /// including `reveal_deadline`. `answers` holds one entry per question
+    /// and the returned score is the number matching the answer key.
+    pub fn reveal_answer(
+        env: Env,
+        player: Address,
+        round_id: u64,
+        answers: Vec<Bytes>,
+        salt: Bytes,
+    ) -> Result<u32, Error> {
+        require_initialized(&env)?;
+        player.require_auth();
+
+        let key = DataKey::Round(round_id);
+        let mut round: RoundData = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::RoundNotFound)?;
 
-        let payout_per_winner = if round.winner_count == 0 {
-            0
-        } else {
-            round
-                .reward_amount
-                .checked_div(round.winner_count as i128)
-                .ok_or(Error::Overflow)?
+        if round.status != RoundStatus::Revealing {
+            return Err(Error::NotRevealPhase);
+        }
+        if env.ledger().timestamp() > round.reveal_deadline {
+            return Err(Error::RevealWindowClosed);
+        }
+
+        let submission_key = DataKey::Submission(round_id, player.clone());
+        let mut submission: Submission = env
+            .storage()
+            .persistent()
+            .get(&submission_key)
+            .ok_or(Error::SubmissionNotFound)?;
+
+        if submission.revealed {
+            return Err(Error::AlreadyRevealed);
+        }
+        if player_commitment(&env, &answers, &salt, &player) != submission.commitment {
+            return Err(Error::InvalidReveal);
+        }
+        if answers.len() != round.revealed_answers.len() {
+            return Err(Error::InvalidAnswerCount);
+        }
+
+        let mut score = 0u32;
+        for (answer, expected) in answers.iter().zip(round.revealed_answers.iter()) {
+            if answer == expected {
+                score += 1;
+            }
+        }
+
+        let winner = match &round.scoring {
+            ScoringMode::Proportional => score > 0,
+            ScoringMode::Tiered(tiers) => match tier_for(tiers, score) {
+                Some(index) => {
+                    let count = round.tier_counts.get(index).unwrap_or(0);
+                    round
+                        .tier_counts
+                        .set(index, count.checked_add(1).ok_or(Error::Overflow)?);
+                    true
+                }
+                None => false,
+            },
         };
+        if winner {
+            round.winner_count = round
+                .winner_count
+                .checked_add(1)
+                .ok_or(Error::Overflow)?;
+        }
+        round.total_score = round
+            .total_score
+            .checked_add(score)
+            .ok_or(Error::Overflow)?;
+        env.storage().persistent().set(&key, &round);
 
-        if round.winner_count == 0 {
-            let prize_pool = get_prize_pool(&env)?;
-            let pool_client = PrizePoolClient::new(&env, &prize_pool);
-            pool_client.release(&admin, &round_id, &round.reward_amount);
+        submission.revealed = true;
+        submission.score = score;
+        env.storage().persistent().set(&submission_key, &submission);
+
+        AnswerRevealed {
+            round_id,
+            player,
+            score,
         }
+        .publish(&env);
+        Ok(score)
+    }
+
+    /// Finalize a round once its reveal window has passed. Admin only.
+    /// Unrevealed answers score zero. Fee management charges the rake on the
+    /// entry pot, and the part of the pot no player can earn (no winners,
+    /// empty tiers, unallocated tier share) is returned: to the prize pool up
+    /// to the seeded reward, the rest to the admin. The claim window starts.
+    ///
+    /// The rake is charged with the round id as its charge id, so rounds with
+    /// equal entry pots can be finalized in the same ledger.
+    pub fn finalize_round(env: Env, round_id: u64) -> Result<(), Error> {
+        let admin = require_admin(&env)?;
+        let key = DataKey::Round(round_id);
+        let mut round: RoundData = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::RoundNotFound)?;
+
+        if round.status != RoundStatus::Revealing {
+            return Err(Error::NotRevealPhase);
+        }
+        let now = env.ledger().timestamp();
+        if now <= round.reveal_deadline {
+            return Err(Error::RevealWindowOpen);
+        }
+
+        round.rake = charge_rake(&env, round_id, round.entry_pot)?;
+        let pot = prize_pot(&round)?;
+
+        let allocated = match &round.scoring {
+            ScoringMode::Proportional => {
+                if round.total_score == 0 {
+                    0
+                } else {
+                    pot
+                }
+            }
+            ScoringMode::Tiered(tiers) => {
+                let mut allocated = 0i128;
+                for (tier, count) in tiers.iter().zip(round.tier_counts.iter()) {
+                    if count > 0 {
+                        allocated = allocated
+                            .checked_add(tier_share(pot, &tier)?)
+                            .ok_or(Error::Overflow)?;
+                    }
+                }
+                allocated
+            }
+        };
+        let unawarded = pot.checked_sub(allocated).ok_or(Error::Overflow)?;
+        let (released, forwarded) =
+            return_unawarded(&env, &admin, round_id, &mut round, unawarded)?;
+        forward_to_admin(&env, &admin, forwarded)?;
+
+        let window: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ClaimWindow)
+            .unwrap_or(DEFAULT_CLAIM_WINDOW);
+        let claim_deadline = now.checked_add(window).ok_or(Error::Overflow)?;
 
         round.status = RoundStatus::Closed;
-        round.closed_at = now;
-        round.payout_per_winner = payout_per_winner;
+        round.claim_deadline = claim_deadline;
         env.storage().persistent().set(&key, &round);
 
         RoundClosed {
             round_id,
             winners: round.winner_count,
-            payout_per_winner,
+            total_score: round.total_score,
+            released,
+            rake: round.rake,
+            forwarded,
+            claim_deadline,
         }
         .publish(&env);
         Ok(())
@@ -297,15 +689,22 @@ impl DailyTrivia {
         require_initialized(&env)?;
         player.require_auth();
 
-        let round: RoundData = env
+        let key = DataKey::Round(round_id);
+        let mut round: RoundData = env
             .storage()
             .persistent()
-            .get(&DataKey::Round(round_id))
+            .get(&key)
             .ok_or(Error::RoundNotFound)?;
 
+        if round.status == RoundStatus::Swept {
+            return Err(Error::ClaimWindowClosed);
+        }
         if round.status != RoundStatus::Closed {
             return Err(Error::RoundNotOpen);
         }
+        if env.ledger().timestamp() > round.claim_deadline {
+            return Err(Error::ClaimWindowClosed);
+        }
 
         let submission_key = DataKey::Submission(round_id, player.clone());
         let mut submission: Submission = env
@@ -318,52 +717,105 @@ impl DailyTrivia {
             return Err(Error::AlreadyClaimed);
         }
 
-        if !submission.correct || round.payout_per_winner <= 0 {
+        let payout = payout_for(&round, submission.score)?;
+        if payout <= 0 {
             return Err(Error::NoRewardAvailable);
         }
 
-        let prize_pool = get_prize_pool(&env)?;
-        let pool_client = PrizePoolClient::new(&env, &prize_pool);
-        let admin = get_admin(&env)?;
-        pool_client.payout(&admin, &player, &round_id, &round.payout_per_winner);
+        // The seeded reward is paid out first; the rest comes from entries.
+        let from_pool = payout.min(pool_remaining(&round)?);
+        if from_pool > 0 {
+            let prize_pool = get_prize_pool(&env)?;
+            let pool_client = PrizePoolClient::new(&env, &prize_pool);
+            let admin = get_admin(&env)?;
+            pool_client.payout(&admin, &player, &round_id, &from_pool);
+        }
 
         let balance_contract = get_balance_contract(&env)?;
         let balance_client = BalanceClient::new(&env, &balance_contract);
         let contract_addr = env.current_contract_address();
 
         let contract_balance = balance_client.balance_of(&contract_addr);
-        if contract_balance < round.payout_per_winner {
+        if contract_balance < payout {
             return Err(Error::InvalidAmount);
         }
 
         balance_client.debit(
             &contract_addr,
             &contract_addr,
-            &round.payout_per_winner,
+            &payout,
             &symbol_short!("payout"),
         );
-        balance_client.credit(
-            &contract_addr,
-            &player,
-            &round.payout_per_winner,
-            &symbol_short!("win"),
-        );
+        balance_client.credit(&contract_addr, &player, &payout, &symbol_short!("win"));
 
         submission.claimed = true;
         env.storage().persistent().set(&submission_key, &submission);
 
+        round.claimed_total = round
+            .claimed_total
+            .checked_add(payout)
+            .ok_or(Error::Overflow)?;
+        round.pool_paid = round
+            .pool_paid
+            .checked_add(from_pool)
+            .ok_or(Error::Overflow)?;
+        env.storage().persistent().set(&key, &round);
+
         RewardClaimed {
             round_id,
             player,
-            amount: round.payout_per_winner,
+            amount: payout,
         }
         .publish(&env);
-        Ok(round.payout_per_winner)
+        Ok(payout)
+    }
+
+    /// Return the reward left unclaimed after the claim deadline, including
+    /// rounding remainders: to the prize pool up to the unpaid part of its
+    /// reservation, the rest to the admin. Admin only.
+    pub fn sweep_unclaimed(env: Env, round_id: u64) -> Result<i128, Error> {
+        let admin = require_admin(&env)?;
+        let key = DataKey::Round(round_id);
+        let mut round: RoundData = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::RoundNotFound)?;
+
+        if round.status == RoundStatus::Swept {
+            return Err(Error::AlreadySwept);
+        }
+        if round.status != RoundStatus::Closed {
+            return Err(Error::RoundNotOpen);
+        }
+        if env.ledger().timestamp() <= round.claim_deadline {
+            return Err(Error::ClaimWindowOpen);
+        }
+
+        let amount = prize_pot(&round)?
+            .checked_sub(round.released)
+            .and_then(|v| v.checked_sub(round.forwarded))
+            .and_then(|v| v.checked_sub(round.claimed_total))
+            .ok_or(Error::Overflow)?;
+        let (_, forwarded) = return_unawarded(&env, &admin, round_id, &mut round, amount)?;
+        forward_to_admin(&env, &admin, forwarded)?;
+
+        round.status = RoundStatus::Swept;
+        env.storage().persistent().set(&key, &round);
+
+        UnclaimedSwept { round_id, amount }.publish(&env);
+        Ok(amount)
     }
 
     pub fn get_round(env: Env, round_id: u64) -> Option<RoundData> {
         env.storage().persistent().get(&DataKey::Round(round_id))
     }
+
+    pub fn get_submission(env: Env, round_id: u64, player: Address) -> Option<Submission> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Submission(round_id, player))
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -383,13 +835,192 @@ fn require_admin(env: &Env) -> Result<Address, Error> {
     Ok(admin)
 }
 
-fn require_positive(amount: i128) -> Result<(), Error> {
-    if amount <= 0 {
+fn validate_funding(reward_amount: i128, entry_fee: i128) -> Result<(), Error> {
+    if reward_amount < 0 || entry_fee < 0 || (reward_amount == 0 && entry_fee == 0) {
+        return Err(Error::InvalidAmount);
+    }
+    Ok(())
+}
+
+fn validate_tiers(tiers: &Vec<RewardTier>, questions: u32) -> Result<(), Error> {
+    if tiers.is_empty() || tiers.len() > questions {
+        return Err(Error::InvalidTiers);
+    }
+    let mut prev_min = 0u32;
+    let mut total_bps = 0u32;
+    for tier in tiers.iter() {
+        if tier.min_correct <= prev_min || tier.min_correct > questions {
+            return Err(Error::InvalidTiers);
+        }
+        prev_min = tier.min_correct;
+        total_bps = total_bps
+            .checked_add(tier.share_bps)
+            .ok_or(Error::InvalidTiers)?;
+    }
+    if total_bps as i128 > BASIS_POINTS_DIVISOR {
+        return Err(Error::InvalidTiers);
+    }
+    Ok(())
+}
+
+/// Index of the highest tier `score` qualifies for.
+fn tier_for(tiers: &Vec<RewardTier>, score: u32) -> Option<u32> {
+    let mut found = None;
+    for (index, tier) in tiers.iter().enumerate() {
+        if score >= tier.min_correct {
+            found = Some(index as u32);
+        }
+    }
+    found
+}
+
+fn tier_share(reward_amount: i128, tier: &RewardTier) -> Result<i128, Error> {
+    reward_amount
+        .checked_mul(tier.share_bps as i128)
+        .and_then(|v| v.checked_div(BASIS_POINTS_DIVISOR))
+        .ok_or(Error::Overflow)
+}
+
+/// Seeded reward plus the entry pot net of rake.
+fn prize_pot(round: &RoundData) -> Result<i128, Error> {
+    round
+        .reward_amount
+        .checked_add(round.entry_pot)
+        .and_then(|v| v.checked_sub(round.rake))
+        .ok_or(Error::Overflow)
+}
+
+/// Part of the prize pool reservation neither paid out nor released.
+fn pool_remaining(round: &RoundData) -> Result<i128, Error> {
+    round
+        .reward_amount
+        .checked_sub(round.released)
+        .and_then(|v| v.checked_sub(round.pool_paid))
+        .ok_or(Error::Overflow)
+}
+
+/// Return `amount` of unawarded pot: released to the prize pool up to its
+/// remaining reservation, the rest recorded as forwarded entry funds.
+/// Returns `(released, forwarded)`; the caller sends forwarded funds on.
+fn return_unawarded(
+    env: &Env,
+    admin: &Address,
+    round_id: u64,
+    round: &mut RoundData,
+    amount: i128,
+) -> Result<(i128, i128), Error> {
+    let released = amount.min(pool_remaining(round)?);
+    let forwarded = amount.checked_sub(released).ok_or(Error::Overflow)?;
+    if released > 0 {
+        let prize_pool = get_prize_pool(env)?;
+        let pool_client = PrizePoolClient::new(env, &prize_pool);
+        pool_client.release(admin, &round_id, &released);
+    }
+    round.released = round
+        .released
+        .checked_add(released)
+        .ok_or(Error::Overflow)?;
+    round.forwarded = round
+        .forwarded
+        .checked_add(forwarded)
+        .ok_or(Error::Overflow)?;
+    Ok((released, forwarded))
+}
+
+/// Have fee management charge the game's rake on a round's `entry_pot`, so
+/// it shows in its accrued fees, and move the rake to the fee contract's
+/// balance.
+fn charge_rake(env: &Env, round_id: u64, entry_pot: i128) -> Result<i128, Error> {
+    if entry_pot <= 0 {
+        return Ok(0);
+    }
+    let fee_contract: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::FeeContract)
+        .ok_or(Error::FeeNotConfigured)?;
+    let game_id: Symbol = env
+        .storage()
+        .instance()
+        .get(&DataKey::FeeGameId)
+        .ok_or(Error::FeeNotConfigured)?;
+    let fee_client = FeeManagementClient::new(env, &fee_contract);
+    let net = fee_client.charge_fee_with_id(&game_id, &entry_pot, &round_id, &None);
+    let rake = entry_pot.checked_sub(net).ok_or(Error::Overflow)?;
+    if rake < 0 || rake > entry_pot {
         return Err(Error::InvalidAmount);
     }
+    if rake > 0 {
+        let balance_client = BalanceClient::new(env, &get_balance_contract(env)?);
+        let contract_addr = env.current_contract_address();
+        balance_client.debit(&contract_addr, &contract_addr, &rake, &symbol_short!("rake"));
+        balance_client.credit(&contract_addr, &fee_contract, &rake, &symbol_short!("rake"));
+    }
+    Ok(rake)
+}
+
+/// Credit `amount` of unawarded entry funds to the admin.
+fn forward_to_admin(env: &Env, admin: &Address, amount: i128) -> Result<(), Error> {
+    if amount <= 0 {
+        return Ok(());
+    }
+    let balance_client = BalanceClient::new(env, &get_balance_contract(env)?);
+    let contract_addr = env.current_contract_address();
+    balance_client.debit(&contract_addr, &contract_addr, &amount, &symbol_short!("unawarded"));
+    balance_client.credit(&contract_addr, admin, &amount, &symbol_short!("unawarded"));
     Ok(())
 }
 
+/// Reward owed for `score` in a finalized round.
+fn payout_for(round: &RoundData, score: u32) -> Result<i128, Error> {
+    let pot = prize_pot(round)?;
+    match &round.scoring {
+        ScoringMode::Proportional => {
+            if score == 0 || round.total_score == 0 {
+                return Ok(0);
+            }
+            pot.checked_mul(score as i128)
+                .and_then(|v| v.checked_div(round.total_score as i128))
+                .ok_or(Error::Overflow)
+        }
+        ScoringMode::Tiered(tiers) => {
+            let Some(index) = tier_for(tiers, score) else {
+                return Ok(0);
+            };
+            let tier = tiers.get(index).ok_or(Error::InvalidTiers)?;
+            let count = round.tier_counts.get(index).unwrap_or(0);
+            if count == 0 {
+                return Ok(0);
+            }
+            tier_share(pot, &tier)?
+                .checked_div(count as i128)
+                .ok_or(Error::Overflow)
+        }
+    }
+}
+
+/// Admin answer key commitment: `sha256(answer || salt)`.
+fn answer_commitment(env: &Env, answer: &Bytes, salt: &Bytes) -> BytesN<32> {
+    let mut preimage = answer.clone();
+    preimage.append(salt);
+    env.crypto().sha256(&preimage).into()
+}
+
+/// Player answer commitment: `sha256(answers_xdr || salt || player_xdr)`.
+/// Binding the player address stops others from replaying a copied
+/// commitment.
+fn player_commitment(
+    env: &Env,
+    answers: &Vec<Bytes>,
+    salt: &Bytes,
+    player: &Address,
+) -> BytesN<32> {
+    let mut preimage = answers.clone().to_xdr(env);
+    preimage.append(salt);
+    preimage.append(&player.clone().to_xdr(env));
+    env.crypto().sha256(&preimage).into()
+}
+
 fn get_admin(env: &Env) -> Result<Address, Error> {
     env.storage()
         .instance()
@@ -419,7 +1050,9 @@ fn get_balance_contract(env: &Env) -> Result<Address, Error> {
 mod test {
     use super::*;
     use soroban_sdk::{
-        contract, contractimpl, contracttype, testutils::Address as _, Address, Env, IntoVal,
+        contract, contractimpl, contracttype,
+        testutils::{Address as _, Ledger},
+        Address, Env, IntoVal,
     };
 
     #[contract]
@@ -488,6 +1121,61 @@ mod test {
         }
     }
 
+    #[contract]
+    pub struct MockFeeManagement;
+
+    #[contracttype]
+    pub enum FeeKey {
+        Bps(Symbol),
+        Accrued(Symbol),
+        Charged(Symbol, i128, u64, u64),
+    }
+
+    #[contractimpl]
+    impl MockFeeManagement {
+        pub fn set_fee_bps(env: Env, game_id: Symbol, bps: u32) {
+            env.storage().persistent().set(&FeeKey::Bps(game_id), &bps);
+        }
+
+        pub fn charge_fee_with_id(
+            env: Env,
+            game_id: Symbol,
+            amount: i128,
+            charge_id: u64,
+            _token: Option<Address>,
+        ) -> i128 {
+            // Like fee management, refuse a repeated charge in one ledger
+            let op = FeeKey::Charged(
+                game_id.clone(),
+                amount,
+                env.ledger().timestamp(),
+                charge_id,
+            );
+            if env.storage().persistent().has(&op) {
+                panic!("duplicate operation");
+            }
+            env.storage().persistent().set(&op, &true);
+            let bps: u32 = env
+                .storage()
+                .persistent()
+                .get(&FeeKey::Bps(game_id.clone()))
+                .expect("game not found");
+            let fee = amount * bps as i128 / 10_000;
+            let accrued = Self::accrued_fees(env.clone(), game_id.clone());
+            env.storage()
+                .persistent()
+                .set(&FeeKey::Accrued(game_id), &(accrued + fee));
+            amount - fee
+        }
+
+        pub fn accrued_fees(env: Env, game_id: Symbol) -> i128 {
+            env.storage()
+                .persistent()
+                .get(&FeeKey::Accrued(game_id))
+                .unwrap_or(0)
+        }
+    }
+
     fn setup(
         env: &Env,
     ) -> (
@@ -516,8 +1204,66 @@ mod test {
         (trivia_client, admin, player, trivia_id, balance_client)
     }
 
-    fn hash_answer(env: &Env, payload: &Bytes) -> BytesN<32> {
-        env.crypto().sha256(payload).into()
+    fn salt(env: &Env, seed: u8) -> Bytes {
+        Bytes::from_array(env, &[seed; 16])
+    }
+
+    fn answers(env: &Env, values: &[u8]) -> Vec<Bytes> {
+        let mut out = Vec::new(env);
+        for value in values {
+            out.push_back(Bytes::from_array(env, &[*value]));
+        }
+        out
+    }
+
+    fn commit_key(env: &Env, key: &Vec<Bytes>) -> Vec<BytesN<32>> {
+        let mut out = Vec::new(env);
+        for answer in key.iter() {
+            out.push_back(answer_commitment(env, &answer, &salt(env, 0xAA)));
+        }
+        out
+    }
+
+    fn commit(env: &Env, answers: &Vec<Bytes>, player: &Address) -> BytesN<32> {
+        player_commitment(env, answers, &salt(env, 0x11), player)
+    }
+
+    fn close(env: &Env, client: &DailyTriviaClient, round_id: u64, key: &Vec<Bytes>) {
+        client.close_round(&round_id, key, &salt(env, 0xAA));
+    }
+
+    fn reveal(
+        env: &Env,
+        client: &DailyTriviaClient,
+        player: &Address,
+        round_id: u64,
+        answers: &Vec<Bytes>,
+    ) -> u32 {
+        client.reveal_answer(player, &round_id, answers, &salt(env, 0x11))
+    }
+
+    fn past_reveal_window(env: &Env) {
+        env.ledger()
+            .with_mut(|l| l.timestamp += DEFAULT_REVEAL_WINDOW + 1);
+    }
+
+    /// Commit, close, reveal for each `(player, answers)` and finalize.
+    fn play_round(
+        env: &Env,
+        client: &DailyTriviaClient,
+        round_id: u64,
+        key: &Vec<Bytes>,
+        entries: &[(&Address, &Vec<Bytes>)],
+    ) {
+        for (player, answers) in entries {
+            client.submit_answer(player, &round_id, &commit(env, answers, player));
+        }
+        close(env, client, round_id, key);
+        for (player, answers) in entries {
+            reveal(env, client, player, round_id, answers);
+        }
+        past_reveal_window(env);
+        client.finalize_round(&round_id);
     }
 
     #[test]
@@ -525,13 +1271,21 @@ mod test {
         let env = Env::default();
         let (client, _admin, _player, _trivia_id, _balance) = setup(&env);
 
-        let commitment = hash_answer(&env, &Bytes::from_array(&env, &[1, 2, 3]));
-        client.open_round(&1, &commitment, &100);
+        let key = answers(&env, &[1, 2, 3]);
+        client.open_round(&1, &commit_key(&env, &key), &100, &0, &ScoringMode::Proportional);
 
         let round = client.get_round(&1).unwrap();
         assert_eq!(round.status, RoundStatus::Open);
+        assert_eq!(round.answer_commitments.len(), 3);
 
-        client.close_round(&1);
+        close(&env, &client, 1, &key);
+        let round = client.get_round(&1).unwrap();
+        assert_eq!(round.status, RoundStatus::Revealing);
+        assert_eq!(round.revealed_answers, key);
+        assert_eq!(round.reveal_deadline, DEFAULT_REVEAL_WINDOW);
+
+        past_reveal_window(&env);
+        client.finalize_round(&1);
         let round = client.get_round(&1).unwrap();
         assert_eq!(round.status, RoundStatus::Closed);
     }
@@ -541,12 +1295,11 @@ mod test {
         let env = Env::default();
         let (client, _admin, player, _trivia_id, _balance) = setup(&env);
 
-        let payload = Bytes::from_array(&env, &[9]);
-        let commitment = hash_answer(&env, &payload);
-        client.open_round(&2, &commitment, &100);
+        let key = answers(&env, &[9]);
+        client.open_round(&2, &commit_key(&env, &key), &100, &0, &ScoringMode::Proportional);
 
-        client.submit_answer(&player, &2, &payload);
-        let result = client.try_submit_answer(&player, &2, &payload);
+        client.submit_answer(&player, &2, &commit(&env, &key, &player));
+        let result = client.try_submit_answer(&player, &2, &commit(&env, &key, &player));
         assert!(result.is_err());
     }
 
@@ -555,12 +1308,9 @@ mod test {
         let env = Env::default();
         let (client, _admin, player, _trivia_id, balance) = setup(&env);
 
-        let payload = Bytes::from_array(&env, &[7, 7]);
-        let commitment = hash_answer(&env, &payload);
-        client.open_round(&3, &commitment, &100);
-
-        client.submit_answer(&player, &3, &payload);
-        client.close_round(&3);
+        let key = answers(&env, &[7]);
+        client.open_round(&3, &commit_key(&env, &key), &100, &0, &ScoringMode::Proportional);
+        play_round(&env, &client, 3, &key, &[(&player, &key)]);
 
         let reward = client.claim_reward(&player, &3);
         assert_eq!(reward, 100);
@@ -572,12 +1322,15 @@ mod test {
         let env = Env::default();
         let (client, _admin, player, _trivia_id, _balance) = setup(&env);
 
-        let commitment = hash_answer(&env, &Bytes::from_array(&env, &[1]));
-        client.open_round(&4, &commitment, &100);
+        let key = answers(&env, &[1]);
+        client.open_round(&4, &commit_key(&env, &key), &100, &0, &ScoringMode::Proportional);
 
-        let wrong = Bytes::from_array(&env, &[2]);
-        client.submit_answer(&player, &4, &wrong);
-        client.close_round(&4);
+        let wrong = answers(&env, &[2]);
+        client.submit_answer(&player, &4, &commit(&env, &wrong, &player));
+        close(&env, &client, 4, &key);
+        assert_eq!(reveal(&env, &client, &player, 4, &wrong), 0);
+        past_reveal_window(&env);
+        client.finalize_round(&4);
 
         let result = client.try_claim_reward(&player, &4);
         assert!(result.is_err());
@@ -588,12 +1341,9 @@ mod test {
         let env = Env::default();
         let (client, _admin, player, _trivia_id, _balance) = setup(&env);
 
-        let payload = Bytes::from_array(&env, &[4, 4]);
-        let commitment = hash_answer(&env, &payload);
-        client.open_round(&5, &commitment, &100);
-
-        client.submit_answer(&player, &5, &payload);
-        client.close_round(&5);
+        let key = answers(&env, &[4]);
+        client.open_round(&5, &commit_key(&env, &key), &100, &0, &ScoringMode::Proportional);
+        play_round(&env, &client, 5, &key, &[(&player, &key)]);
 
         client.claim_reward(&player, &5);
         let result = client.try_claim_reward(&player, &5);
@@ -606,7 +1356,8 @@ mod test {
         let (client, _admin, _player, trivia_id, _balance) = setup(&env);
 
         let other = Address::generate(&env);
-        let commitment = hash_answer(&env, &Bytes::from_array(&env, &[8]));
+        let commitments = commit_key(&env, &answers(&env, &[8]));
+        let scoring = ScoringMode::Proportional;
 
         env.mock_auths(&[soroban_sdk::testutils::MockAuth {
             address: &other,
@@ -616,14 +1367,398 @@ mod test {
                 args: soroban_sdk::vec![
                     &env,
                     6u64.into_val(&env),
-                    commitment.into_val(&env),
-                    100i128.into_val(&env)
+                    commitments.into_val(&env),
+                    100i128.into_val(&env),
+                    0i128.into_val(&env),
+                    scoring.into_val(&env)
                 ],
                 sub_invokes: &[],
             },
         }]);
 
-        let result = client.try_open_round(&6, &commitment, &100);
+        let result = client.try_open_round(&6, &commitments, &100, &0, &scoring);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_close_with_wrong_answer_key_rejected() {
+        let env = Env::default();
+        let (client, _admin, _player, _trivia_id, _balance) = setup(&env);
+
+        let key = answers(&env, &[3, 4]);
+        client.open_round(&7, &commit_key(&env, &key), &100, &0, &ScoringMode::Proportional);
+
+        let wrong = answers(&env, &[3, 5]);
+        let result = client.try_close_round(&7, &wrong, &salt(&env, 0xAA));
+        assert_eq!(result, Err(Ok(Error::InvalidReveal)));
+        let result = client.try_close_round(&7, &key, &salt(&env, 0xAB));
+        assert_eq!(result, Err(Ok(Error::InvalidReveal)));
+        let result = client.try_close_round(&7, &answers(&env, &[3]), &salt(&env, 0xAA));
+        assert_eq!(result, Err(Ok(Error::InvalidAnswerCount)));
+    }
+
+    #[test]
+    fn test_copied_commitment_cannot_be_revealed() {
+        let env = Env::default();
+        let (client, _admin, player, _trivia_id, _balance) = setup(&env);
+        let copier = Address::generate(&env);
+
+        let key = answers(&env, &[5, 5]);
+        client.open_round(&8, &commit_key(&env, &key), &100, &0, &ScoringMode::Proportional);
+
+        // The copier replays the honest player's commitment
+        let honest = commit(&env, &key, &player);
+        client.submit_answer(&player, &8, &honest);
+        client.submit_answer(&copier, &8, &honest);
+        close(&env, &client, 8, &key);
+
+        assert_eq!(reveal(&env, &client, &player, 8, &key), 2);
+        let result = client.try_reveal_answer(&copier, &8, &key, &salt(&env, 0x11));
+        assert_eq!(result, Err(Ok(Error::InvalidReveal)));
+        assert_eq!(client.get_round(&8).unwrap().winner_count, 1);
+    }
+
+    #[test]
+    fn test_reveal_phase_rules() {
+        let env = Env::default();
+        let (client, _admin, player, _trivia_id, _balance) = setup(&env);
+
+        let key = answers(&env, &[6]);
+        client.open_round(&9, &commit_key(&env, &key), &100, &0, &ScoringMode::Proportional);
+        client.submit_answer(&player, &9, &commit(&env, &key, &player));
+
+        // No reveals while submissions are open
+        let result = client.try_reveal_answer(&player, &9, &key, &salt(&env, 0x11));
+        assert_eq!(result, Err(Ok(Error::NotRevealPhase)));
+        assert_eq!(client.try_finalize_round(&9), Err(Ok(Error::NotRevealPhase)));
+
+        close(&env, &client, 9, &key);
+        let late = Address::generate(&env);
+        let result = client.try_submit_answer(&late, &9, &commit(&env, &key, &late));
+        assert_eq!(result, Err(Ok(Error::RoundClosed)));
+        assert_eq!(client.try_finalize_round(&9), Err(Ok(Error::RevealWindowOpen)));
+
+        reveal(&env, &client, &player, 9, &key);
+        let result = client.try_reveal_answer(&player, &9, &key, &salt(&env, 0x11));
+        assert_eq!(result, Err(Ok(Error::AlreadyRevealed)));
+
+        let stranger = Address::generate(&env);
+        let result = client.try_reveal_answer(&stranger, &9, &key, &salt(&env, 0x11));
+        assert_eq!(result, Err(Ok(Error::SubmissionNotFound)));
+    }
+
+    #[test]
+    fn test_late_reveal_rejected_and_counts_as_wrong() {
+        let env = Env::default();
+        let (client, _admin, player, _trivia_id, balance) = setup(&env);
+
+        client.set_reveal_window(&60);
+        let key = answers(&env, &[2]);
+        client.open_round(&10, &commit_key(&env, &key), &100, &0, &ScoringMode::Proportional);
+        client.submit_answer(&player, &10, &commit(&env, &key, &player));
+        close(&env, &client, 10, &key);
+
+        env.ledger().with_mut(|l| l.timestamp = 61);
+        let result = client.try_reveal_answer(&player, &10, &key, &salt(&env, 0x11));
+        assert_eq!(result, Err(Ok(Error::RevealWindowClosed)));
+
+        client.finalize_round(&10);
+        let round = client.get_round(&10).unwrap();
+        assert_eq!(round.winner_count, 0);
+        assert!(client.try_claim_reward(&player, &10).is_err());
+        assert_eq!(balance.balance_of(&player), 0);
+    }
+
+    #[test]
+    fn test_open_round_validates_questions_and_tiers() {
+        let env = Env::default();
+        let (client, _admin, _player, _trivia_id, _balance) = setup(&env);
+
+        let none = Vec::new(&env);
+        let result = client.try_open_round(&11, &none, &100, &0, &ScoringMode::Proportional);
+        assert_eq!(result, Err(Ok(Error::InvalidQuestions)));
+
+        let eleven = commit_key(&env, &answers(&env, &[0; 11]));
+        let result = client.try_open_round(&11, &eleven, &100, &0, &ScoringMode::Proportional);
+        assert_eq!(result, Err(Ok(Error::InvalidQuestions)));
+
+        let three = commit_key(&env, &answers(&env, &[1, 2, 3]));
+        let tiers = |list: &[(u32, u32)]| {
+            let mut out = Vec::new(&env);
+            for (min_correct, share_bps) in list {
+                out.push_back(RewardTier {
+                    min_correct: *min_correct,
+                    share_bps: *share_bps,
+                });
+            }
+            ScoringMode::Tiered(out)
+        };
+        for bad in [
+            tiers(&[]),
+            tiers(&[(0, 5000)]),
+            tiers(&[(2, 5000), (2, 5000)]),
+            tiers(&[(3, 5000), (1, 5000)]),
+            tiers(&[(4, 5000)]),
+            tiers(&[(1, 6000), (3, 5000)]),
+        ] {
+            let result = client.try_open_round(&11, &three, &100, &0, &bad);
+            assert_eq!(result, Err(Ok(Error::InvalidTiers)));
+        }
+
+        client.open_round(&11, &three, &100, &0, &tiers(&[(2, 3000), (3, 7000)]));
+        assert_eq!(client.get_round(&11).unwrap().tier_counts.len(), 2);
+    }
+
+    #[test]
+    fn test_multi_question_proportional_scoring() {
+        let env = Env::default();
+        let (client, _admin, _player, _trivia_id, balance) = setup(&env);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let carol = Address::generate(&env);
+
+        let key = answers(&env, &[1, 2, 3, 4, 5]);
+        client.open_round(&12, &commit_key(&env, &key), &900, &0, &ScoringMode::Proportional);
+
+        let alice_answers = key.clone();
+        let bob_answers = answers(&env, &[1, 2, 3, 9, 9]);
+        let carol_answers = answers(&env, &[9, 9, 9, 9, 9]);
+        play_round(
+            &env,
+            &client,
+            12,
+            &key,
+            &[(&alice, &alice_answers), (&bob, &bob_answers), (&carol, &carol_answers)],
+        );
+
+        let round = client.get_round(&12).unwrap();
+        assert_eq!(round.total_score, 8);
+        assert_eq!(round.winner_count, 2);
+        assert_eq!(client.get_submission(&12, &bob).unwrap().score, 3);
+
+        // 900 × 5/8 = 562, 900 × 3/8 = 337
+        assert_eq!(client.claim_reward(&alice, &12), 562);
+        assert_eq!(client.claim_reward(&bob, &12), 337);
+        assert_eq!(balance.balance_of(&alice), 562);
+        assert_eq!(
+            client.try_claim_reward(&carol, &12),
+            Err(Ok(Error::NoRewardAvailable))
+        );
+    }
+
+    #[test]
+    fn test_multi_question_tiered_scoring() {
+        let env = Env::default();
+        let (client, _admin, _player, _trivia_id, _balance) = setup(&env);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let carol = Address::generate(&env);
+        let dave = Address::generate(&env);
+
+        // 3+ correct share 30%, 5 correct share 70%
+        let mut tiers = Vec::new(&env);
+        tiers.push_back(RewardTier {
+            min_correct: 3,
+            share_bps: 3000,
+        });
+        tiers.push_back(RewardTier {
+            min_correct: 5,
+            share_bps: 7000,
+        });
+
+        let key = answers(&env, &[1, 2, 3, 4, 5]);
+        client.open_round(&13, &commit_key(&env, &key), &1000, &0, &ScoringMode::Tiered(tiers));
+
+        let perfect = key.clone();
+        let three = answers(&env, &[1, 2, 3, 0, 0]);
+        let four = answers(&env, &[1, 2, 3, 4, 0]);
+        let two = answers(&env, &[1, 2, 0, 0, 0]);
+        play_round(
+            &env,
+            &client,
+            13,
+            &key,
+            &[(&alice, &perfect), (&bob, &three), (&carol, &four), (&dave, &two)],
+        );
+
+        let round = client.get_round(&13).unwrap();
+        assert_eq!(round.winner_count, 3);
+        assert_eq!(round.tier_counts, soroban_sdk::vec![&env, 2u32, 1]);
+
+        assert_eq!(client.claim_reward(&alice, &13), 700);
+        assert_eq!(client.claim_reward(&bob, &13), 150);
+        assert_eq!(client.claim_reward(&carol, &13), 150);
+        assert!(client.try_claim_reward(&dave, &13).is_err());
+    }
+
+    #[test]
+    fn test_reveal_with_wrong_answer_count_rejected() {
+        let env = Env::default();
+        let (client, _admin, player, _trivia_id, _balance) = setup(&env);
+
+        let key = answers(&env, &[1, 2]);
+        client.open_round(&14, &commit_key(&env, &key), &100, &0, &ScoringMode::Proportional);
+
+        let short = answers(&env, &[1]);
+        client.submit_answer(&player, &14, &commit(&env, &short, &player));
+        close(&env, &client, 14, &key);
+
+        let result = client.try_reveal_answer(&player, &14, &short, &salt(&env, 0x11));
+        assert_eq!(result, Err(Ok(Error::InvalidAnswerCount)));
+    }
+
+    #[test]
+    fn test_unclaimed_reward_swept_after_claim_deadline() {
+        let env = Env::default();
+        let (client, _admin, _player, _trivia_id, balance) = setup(&env);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+
+        client.set_claim_window(&1_000);
+        let key = answers(&env, &[1, 2, 3]);
+        client.open_round(&15, &commit_key(&env, &key), &900, &0, &ScoringMode::Proportional);
+        let bob_answers = answers(&env, &[1, 9, 9]);
+        play_round(&env, &client, 15, &key, &[(&alice, &key), (&bob, &bob_answers)]);
+
+        let claim_deadline = client.get_round(&15).unwrap().claim_deadline;
+        assert_eq!(claim_deadline, env.ledger().timestamp() + 1_000);
+        assert_eq!(client.try_sweep_unclaimed(&15), Err(Ok(Error::ClaimWindowOpen)));
+
+        // 900 × 3/4 = 675; bob's 225 is never claimed
+        assert_eq!(client.claim_reward(&alice, &15), 675);
+
+        env.ledger().with_mut(|l| l.timestamp = claim_deadline + 1);
+        assert_eq!(client.try_claim_reward(&bob, &15), Err(Ok(Error::ClaimWindowClosed)));
+
+        assert_eq!(client.sweep_unclaimed(&15), 225);
+        let round = client.get_round(&15).unwrap();
+        assert_eq!(round.status, RoundStatus::Swept);
+        assert_eq!(round.claimed_total, 675);
+        assert_eq!(round.released, 225);
+        assert_eq!(client.try_sweep_unclaimed(&15), Err(Ok(Error::AlreadySwept)));
+        assert_eq!(client.try_claim_reward(&bob, &15), Err(Ok(Error::ClaimWindowClosed)));
+        assert_eq!(balance.balance_of(&bob), 0);
+    }
+
+    #[test]
+    fn test_round_without_winners_releases_reservation() {
+        let env = Env::default();
+        let (client, _admin, player, _trivia_id, _balance) = setup(&env);
+
+        let key = answers(&env, &[1, 2]);
+        client.open_round(&16, &commit_key(&env, &key), &400, &0, &ScoringMode::Proportional);
+        let wrong = answers(&env, &[3, 4]);
+        play_round(&env, &client, 16, &key, &[(&player, &wrong)]);
+
+        let round = client.get_round(&16).unwrap();
+        assert_eq!(round.winner_count, 0);
+        assert_eq!(round.released, 400);
+
+        env.ledger().with_mut(|l| l.timestamp = round.claim_deadline + 1);
+        assert_eq!(client.sweep_unclaimed(&16), 0);
+    }
+
+    #[test]
+    fn test_paid_entry_round_funds_pot_and_rake() {
+        let env = Env::default();
+        let (client, admin, _player, _trivia_id, balance) = setup(&env);
+        let fees = env.register(MockFeeManagement, ());
+        let fee_client = MockFeeManagementClient::new(&env, &fees);
+        let game = symbol_short!("trivia");
+        fee_client.set_fee_bps(&game, &1_000);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let carol = Address::generate(&env);
+        for p in [&alice, &bob, &carol] {
+            balance.set_balance(p, &50);
+        }
+
+        let key = answers(&env, &[1, 2]);
+        let commitments = commit_key(&env, &key);
+        let scoring = ScoringMode::Proportional;
+        let result = client.try_open_round(&17, &commitments, &100, &50, &scoring);
+        assert_eq!(result, Err(Ok(Error::FeeNotConfigured)));
+        assert_eq!(
+            client.try_open_round(&17, &commitments, &0, &0, &scoring),
+            Err(Ok(Error::InvalidAmount))
+        );
+
+        client.set_fee_config(&fees, &game);
+        client.open_round(&17, &commitments, &100, &50, &scoring);
+        let bob_answers = answers(&env, &[1, 9]);
+        let carol_answers = answers(&env, &[9, 9]);
+        play_round(
+            &env,
+            &client,
+            17,
+            &key,
+            &[(&alice, &key), (&bob, &bob_answers), (&carol, &carol_answers)],
+        );
+        assert_eq!(balance.balance_of(&carol), 0);
+
+        // 150 in entries, 15 raked: pot = 100 + 135 = 235
+        let round = client.get_round(&17).unwrap();
+        assert_eq!(round.entry_pot, 150);
+        assert_eq!(round.rake, 15);
+        assert_eq!(balance.balance_of(&fees), 15);
+        assert_eq!(fee_client.accrued_fees(&game), 15);
+
+        // 235 × 2/3 = 156, of which the 100 reservation is paid first
+        assert_eq!(client.claim_reward(&alice, &17), 156);
+        assert_eq!(client.claim_reward(&bob, &17), 78);
+        let round = client.get_round(&17).unwrap();
+        assert_eq!(round.pool_paid, 100);
+
+        // The rounding remainder is entry money, so it goes to the admin
+        env.ledger().with_mut(|l| l.timestamp = round.claim_deadline + 1);
+        assert_eq!(client.sweep_unclaimed(&17), 1);
+        assert_eq!(balance.balance_of(&admin), 1);
+        assert_eq!(balance.balance_of(&fees), 15);
+        assert_eq!(fee_client.accrued_fees(&game), 15);
+        assert_eq!(balance.balance_of(&alice), 156);
+    }
+
+    #[test]
+    fn test_equal_entry_pots_finalized_in_one_ledger() {
+        let env = Env::default();
+        let (client, _admin, player, _trivia_id, balance) = setup(&env);
+        let fees = env.register(MockFeeManagement, ());
+        let fee_client = MockFeeManagementClient::new(&env, &fees);
+        let game = symbol_short!("trivia");
+        fee_client.set_fee_bps(&game, &1_000);
+        client.set_fee_config(&fees, &game);
+
+        let key = answers(&env, &[1]);
+        let scoring = ScoringMode::Proportional;
+        for round_id in [19, 20] {
+            client.open_round(&round_id, &commit_key(&env, &key), &0, &50, &scoring);
+            balance.set_balance(&player, &50);
+            client.submit_answer(&player, &round_id, &commit(&env, &key, &player));
+        }
+        for round_id in [19, 20] {
+            close(&env, &client, round_id, &key);
+            reveal(&env, &client, &player, round_id, &key);
+        }
+
+        // Both 50 pots are raked in the same ledger
+        past_reveal_window(&env);
+        client.finalize_round(&19);
+        client.finalize_round(&20);
+        assert_eq!(client.get_round(&19).unwrap().rake, 5);
+        assert_eq!(client.get_round(&20).unwrap().rake, 5);
+        assert_eq!(fee_client.accrued_fees(&game), 10);
+    }
+
+    #[test]
+    fn test_paid_entry_requires_balance() {
+        let env = Env::default();
+        let (client, _admin, player, _trivia_id, _balance) = setup(&env);
+
+        client.set_fee_config(&Address::generate(&env), &symbol_short!("trivia"));
+        let key = answers(&env, &[1]);
+        client.open_round(&18, &commit_key(&env, &key), &0, &25, &ScoringMode::Proportional);
+        let result = client.try_submit_answer(&player, &18, &commit(&env, &key, &player));
         assert!(result.is_err());
+        assert!(client.get_submission(&18, &player).is_none());
     }
 }
@@ -97,6 +97,7 @@ pub struct ChargeOp {
     pub game_id: Symbol,
     pub amount: i128,
     pub timestamp: u64,
+    pub charge_id: u64,
 }
 
 #[contracttype]
@@ -204,6 +205,20 @@ impl FeeManagerContract {
         game_id: Symbol,
         amount: i128,
         token: Option<Address>,
+    ) -> Result<i128, Error> {
+        Self::charge_fee_with_id(env, game_id, amount, 0, token)
+    }
+
+    /// Charge fee for a game transaction identified by `charge_id`
+    ///
+    /// Only a repeated `charge_id` counts as a duplicate, so a game can charge
+    /// equal amounts for separate rounds in the same ledger.
+    pub fn charge_fee_with_id(
+        env: Env,
+        game_id: Symbol,
+        amount: i128,
+        charge_id: u64,
+        token: Option<Address>,
     ) -> Result<i128, Error> {
         if !env.storage().instance().has(&DataKey::Admin) {
             return Err(Error::NotInitialized);
@@ -244,6 +259,7 @@ impl FeeManagerContract {
             game_id: game_id.clone(),
             amount,
             timestamp: env.ledger().timestamp(),
+            charge_id,
         };
 
         // Check for duplicate operations
@@ -181,6 +181,26 @@ fn test_charge_fee_game_not_found() {
         assert_eq!(result2, Err(Ok(Error::DuplicateOperation)));
     }
 
+    #[test]
+    fn test_charge_fee_with_id() {
+        let (env, contract_id, admin, _) = setup_contract();
+        let client = FeeManagerContractClient::new(&env, &contract_id);
+        let game_id = symbol_short!("game1");
+        let recipient = Address::generate(&env);
+        let amount = 1000i128;
+
+        client.set_fee_config(&admin, &game_id, &250, &recipient);
+
+        // Equal charges with different ids in one ledger both go through
+        assert_eq!(client.charge_fee_with_id(&game_id, &amount, &1, &None::<Address>), 975);
+        assert_eq!(client.charge_fee_with_id(&game_id, &amount, &2, &None::<Address>), 975);
+        assert_eq!(client.accrued_fees(&game_id), 50);
+
+        // Repeating an id is still a duplicate
+        let result = client.try_charge_fee_with_id(&game_id, &amount, &2, &None::<Address>);
+        assert_eq!(result, Err(Ok(Error::DuplicateOperation)));
+    }
+
     #[test]
     fn test_accrued_fees() {
         let (env, contract_id, admin, _) = setup_contract();
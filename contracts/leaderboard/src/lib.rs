@@ -1,8 +1,31 @@
 //! Stellarcade Leaderboard Contract
 //!
-//! Tracks player scores across different games and maintains a top-players list.
+//! Tracks player scores across different games and ranks every participant.
 //! The contract is permissioned, allowing only the admin or authorized game
 //! contracts to submit scores.
+//!
+//! Each game aggregates a player's submissions according to its `ScoreMode`,
+//! chosen when the game is activated: best (highest or lowest) score, running
+//! total, or latest score.
+//!
+//! Scores are kept per season. Closing a season (by the admin, or once its
+//! scheduled duration has passed) freezes its top list into an immutable
+//! archive and starts the next season with empty scores. The closed season's
+//! ranking storage is then deleted in batches through `prune_season`.
+//!
+//! Each season's ranking is a sorted list of every scored player, split into
+//! segments of at most `SEGMENT_SIZE` entries. The segments are listed in
+//! shards of at most `SHARD_SIZE` segments, and the index lists the shards.
+//! Neighbouring segments, and neighbouring shards, are merged whenever they
+//! fit in one, so the index holds one small entry per thousand or so
+//! players. A score update rewrites the index, at most two shards and a few
+//! segments, and any player's rank is found from the index, one shard and
+//! one segment.
+//!
+//! Games given a `GlobalWeight` also feed a platform-wide "arcade points"
+//! ranking: a player earns `weight * points_per_rank[rank - 1]` from each such
+//! game's current season, and their global total is re-ranked whenever one
+//! of those ranks changes.
 
 #![no_std]
 #![allow(unexpected_cfgs)]
@@ -15,7 +38,17 @@ use soroban_sdk::{
 // Constants
 // ---------------------------------------------------------------------------
 
+/// Largest page returned by `top_players` / `players_between`, and the
+/// largest season archive.
 const MAX_LEADERBOARD_SIZE: u32 = 100;
+/// Entries per ranking segment before it is split in two.
+const SEGMENT_SIZE: u32 = 64;
+/// Segments per ranking shard before it is split in two.
+const SHARD_SIZE: u32 = 32;
+/// Most segments deleted by one `prune_season` call.
+const MAX_PRUNE_BATCH: u32 = 50;
+/// Longest points-per-rank table of a game's `GlobalWeight`.
+const MAX_POINTS_RANKS: u32 = 20;
 const PERSISTENT_BUMP_LEDGERS: u32 = 518_400; // ~30 days
 
 // ---------------------------------------------------------------------------
@@ -32,17 +65,115 @@ pub enum Error {
     InvalidLimit = 4,
     Overflow = 5,
     GameNotFound = 6,
+    ModeLocked = 7,
+    SeasonNotFound = 8,
+    SeasonNotEnded = 9,
+    InvalidPointsTable = 10,
 }
 
 // ---------------------------------------------------------------------------
 // Storage Types
 // ---------------------------------------------------------------------------
 
+/// How a player's submissions for one game combine into their ranked score.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ScoreMode {
+    /// Keep the highest score; higher ranks first (default).
+    BestHigh,
+    /// Keep the lowest score; lower ranks first (e.g. time trials).
+    BestLow,
+    /// Add every submission to a running total; higher ranks first.
+    Cumulative,
+    /// Keep the most recent score; higher ranks first.
+    Latest,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ScoreEntry {
     pub player: Address,
     pub score: u64,
+    /// Ledger sequence at which the player reached `score`.
+    pub ledger: u32,
+    /// Ledger timestamp at which the player reached `score`.
+    pub timestamp: u64,
+}
+
+/// One segment of a season's ranking, as recorded in its `RankIndex`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SegmentInfo {
+    pub id: u32,
+    pub len: u32,
+    /// Score of the segment's last (lowest ranked) entry.
+    pub worst: u64,
+}
+
+/// One shard of a season's ranking, as recorded in its `RankIndex`. The
+/// shard itself stores the `SegmentInfo` of its segments, in order. Any two
+/// neighbouring segments of a shard hold more than `SEGMENT_SIZE` entries
+/// together; otherwise they are merged.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ShardInfo {
+    pub id: u32,
+    /// Number of entries across the shard's segments.
+    pub len: u32,
+    /// Number of segments in the shard.
+    pub segments: u32,
+    /// Score of the shard's last (lowest ranked) entry.
+    pub worst: u64,
+}
+
+/// Ordered shards of a season's ranking. Any two neighbouring shards hold
+/// more than `SHARD_SIZE` segments together; otherwise they are merged.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RankIndex {
+    /// Next free segment or shard id.
+    pub next_id: u32,
+    /// Number of ranked players across all shards.
+    pub total: u32,
+    pub shards: Vec<ShardInfo>,
+}
+
+/// How a game's ranks convert into global arcade points.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GlobalWeight {
+    /// Multiplier applied to every point of this game.
+    pub weight: u32,
+    /// Points for rank 1, 2, ...; ranks past the end earn nothing.
+    pub points_per_rank: Vec<u32>,
+}
+
+/// Per-game season settings.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SeasonConfig {
+    /// Season length in seconds; 0 means seasons are only closed by the admin.
+    pub duration: u64,
+    /// Number of top entries frozen into the archive when a season closes.
+    pub archive_size: u32,
+}
+
+/// The season currently accepting scores for a game.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SeasonState {
+    pub season: u32,
+    pub started_at: u64,
+}
+
+/// Immutable top list of a closed season.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SeasonArchive {
+    pub season: u32,
+    pub started_at: u64,
+    pub closed_at: u64,
+    pub entries: Vec<ScoreEntry>,
 }
 
 #[contracttype]
@@ -50,8 +181,20 @@ pub enum DataKey {
     Admin,
     Authorized(Address),
     GameActive(Symbol),
-    PlayerScore(Symbol, Address),
-    Leaderboard(Symbol),
+    GameMode(Symbol),
+    SeasonConfig(Symbol),
+    SeasonState(Symbol),
+    PlayerScore(Symbol, u32, Address),
+    RankIndex(Symbol, u32),
+    RankShard(Symbol, u32, u32),
+    RankSegment(Symbol, u32, u32),
+    SeasonArchive(Symbol, u32),
+    GlobalWeight(Symbol),
+    GlobalContribution(Symbol, Address),
+    GlobalPoints(Address),
+    GlobalIndex,
+    GlobalShard(u32),
+    GlobalSegment(u32),
 }
 
 // ---------------------------------------------------------------------------
@@ -65,6 +208,8 @@ pub struct ScoreSubmitted {
     #[topic]
     pub player: Address,
     pub score: u64,
+    pub ledger: u32,
+    pub timestamp: u64,
 }
 
 #[contractevent]
@@ -80,6 +225,43 @@ pub struct GameStatusChanged {
     pub active: bool,
 }
 
+#[contractevent]
+pub struct SeasonClosed {
+    #[topic]
+    pub game_id: Symbol,
+    pub season: u32,
+    pub closed_at: u64,
+    pub archived: u32,
+}
+
+#[contractevent]
+pub struct SeasonConfigSet {
+    #[topic]
+    pub game_id: Symbol,
+    pub config: SeasonConfig,
+}
+
+#[contractevent]
+pub struct GlobalWeightSet {
+    #[topic]
+    pub game_id: Symbol,
+    pub weight: GlobalWeight,
+}
+
+#[contractevent]
+pub struct GlobalPointsUpdated {
+    #[topic]
+    pub player: Address,
+    pub points: u64,
+}
+
+#[contractevent]
+pub struct GameModeSet {
+    #[topic]
+    pub game_id: Symbol,
+    pub mode: ScoreMode,
+}
+
 // ---------------------------------------------------------------------------
 // Contract
 // ---------------------------------------------------------------------------
@@ -132,8 +314,46 @@ impl LeaderboardContract {
         Ok(())
     }
 
+    /// Activate a game with the given score aggregation mode.
+    /// The mode cannot change once the game has ranked players in the
+    /// current season.
+    pub fn activate_game(
+        env: Env,
+        admin: Address,
+        game_id: Symbol,
+        mode: ScoreMode,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+
+        if mode != game_mode(&env, &game_id) {
+            let season = season_state(&env, &game_id).season;
+            if load_index(&env, &Ranking::game(&env, &game_id, season)).total > 0 {
+                return Err(Error::ModeLocked);
+            }
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::GameMode(game_id.clone()), &mode);
+        env.storage()
+            .instance()
+            .set(&DataKey::GameActive(game_id.clone()), &true);
+        GameModeSet {
+            game_id: game_id.clone(),
+            mode,
+        }
+        .publish(&env);
+        GameStatusChanged {
+            game_id,
+            active: true,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
     /// Submit a score for a player in a game.
-    /// Only authorized callers can submit scores.
+    /// Only authorized callers can submit scores. The score is combined with
+    /// the player's previous one according to the game's `ScoreMode`.
     pub fn submit_score(
         env: Env,
         caller: Address,
@@ -163,11 +383,39 @@ impl LeaderboardContract {
             return Err(Error::GameNotFound);
         }
 
-        let score_key = DataKey::PlayerScore(game_id.clone(), player.clone());
-        let current_score: u64 = env.storage().persistent().get(&score_key).unwrap_or(0);
+        // Scores submitted after a scheduled season end open the next season
+        let season = match season_expired(&env, &game_id) {
+            Some(_) => close_current_season(&env, &game_id)?,
+            None => season_state(&env, &game_id).season,
+        };
+
+        let mode = game_mode(&env, &game_id);
+        let score_key = DataKey::PlayerScore(game_id.clone(), season, player.clone());
+        let current_score: Option<u64> = env.storage().persistent().get(&score_key);
+
+        let new_score = match (mode, current_score) {
+            // Only update if the new score is higher
+            (ScoreMode::BestHigh, current) if score > current.unwrap_or(0) => Some(score),
+            // Only update if the new score is lower
+            (ScoreMode::BestLow, Some(current)) if score >= current => None,
+            (ScoreMode::BestLow, _) => Some(score),
+            (ScoreMode::Cumulative, current) => Some(
+                current
+                    .unwrap_or(0)
+                    .checked_add(score)
+                    .ok_or(Error::Overflow)?,
+            ),
+            (ScoreMode::Latest, _) => Some(score),
+            _ => None,
+        };
 
-        // Only update if the new score is higher
-        if score > current_score {
+        if let Some(score) = new_score {
+            let entry = ScoreEntry {
+                player: player.clone(),
+                score,
+                ledger: env.ledger().sequence(),
+                timestamp: env.ledger().timestamp(),
+            };
             env.storage().persistent().set(&score_key, &score);
             env.storage().persistent().extend_ttl(
                 &score_key,
@@ -179,11 +427,39 @@ impl LeaderboardContract {
                 game_id: game_id.clone(),
                 player: player.clone(),
                 score,
+                ledger: entry.ledger,
+                timestamp: entry.timestamp,
             }
             .publish(&env);
 
-            // Automatically update rankings if the score is high enough
-            update_leaderboard_internal(&env, &game_id, player, score)?;
+            // Automatically move the player to their new rank
+            let ranking = Ranking::game(&env, &game_id, season);
+            let weight = global_weight(&env, &game_id);
+            let old_rank = match (&weight, current_score) {
+                (Some(_), Some(previous)) => rank_of(&env, &ranking, &player, previous),
+                _ => 0,
+            };
+            update_leaderboard_internal(&env, &ranking, entry, current_score)?;
+            LeaderboardUpdated {
+                game_id: game_id.clone(),
+            }
+            .publish(&env);
+
+            // Re-score everyone whose rank in this game moved
+            if let Some(weight) = weight {
+                let new_rank = rank_of(&env, &ranking, &player, score);
+                let total = load_index(&env, &ranking).total;
+                let (first, last) = if old_rank == 0 {
+                    (new_rank, total)
+                } else {
+                    (old_rank.min(new_rank), old_rank.max(new_rank))
+                };
+                // Other players shift by one rank, so only those up to one
+                // rank past the points table can change
+                let last = last.min(weight.points_per_rank.len() + 1);
+                refresh_global_points(&env, &ranking, &weight, first, last)?;
+                set_contribution(&env, &game_id, &player, points_for(&weight, new_rank))?;
+            }
         }
 
         Ok(())
@@ -199,55 +475,251 @@ impl LeaderboardContract {
         Ok(())
     }
 
-    /// Get the top players for a game, up to a certain limit.
+    /// Get the top players for a game's current season, up to a certain limit.
     pub fn top_players(env: Env, game_id: Symbol, limit: u32) -> Result<Vec<ScoreEntry>, Error> {
-        if limit == 0 || limit > MAX_LEADERBOARD_SIZE {
+        Self::players_between(env, game_id, 0, limit)
+    }
+
+    /// Get up to `limit` players of a game's current season, starting at the
+    /// 0-based position `offset`.
+    pub fn players_between(
+        env: Env,
+        game_id: Symbol,
+        offset: u32,
+        limit: u32,
+    ) -> Result<Vec<ScoreEntry>, Error> {
+        let season = season_state(&env, &game_id).season;
+        page(&env, &Ranking::game(&env, &game_id, season), offset, limit)
+    }
+
+    /// Get the rank of a player in a game's current season (1-indexed).
+    /// Returns 0 if player has no score this season.
+    pub fn player_rank(env: Env, game_id: Symbol, player: Address) -> Result<u32, Error> {
+        let season = season_state(&env, &game_id).season;
+        let score: Option<u64> = env.storage().persistent().get(&DataKey::PlayerScore(
+            game_id.clone(),
+            season,
+            player.clone(),
+        ));
+        Ok(score.map_or(0, |score| {
+            rank_of(&env, &Ranking::game(&env, &game_id, season), &player, score)
+        }))
+    }
+
+    /// Get the number of ranked players in a game's current season.
+    pub fn player_count(env: Env, game_id: Symbol) -> u32 {
+        let season = season_state(&env, &game_id).season;
+        load_index(&env, &Ranking::game(&env, &game_id, season)).total
+    }
+
+    /// Set how a game's ranks convert into global arcade points. Admin only.
+    /// `points_per_rank` must be non-increasing with at most
+    /// `MAX_POINTS_RANKS` entries. Points of the game's current top players
+    /// are recomputed immediately.
+    pub fn set_global_weight(
+        env: Env,
+        admin: Address,
+        game_id: Symbol,
+        weight: u32,
+        points_per_rank: Vec<u32>,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        if points_per_rank.len() > MAX_POINTS_RANKS {
+            return Err(Error::InvalidPointsTable);
+        }
+        for i in 1..points_per_rank.len() {
+            if points_per_rank.get_unchecked(i) > points_per_rank.get_unchecked(i - 1) {
+                return Err(Error::InvalidPointsTable);
+            }
+        }
+
+        let old_len = global_weight(&env, &game_id).map_or(0, |w| w.points_per_rank.len());
+        let config = GlobalWeight {
+            weight,
+            points_per_rank,
+        };
+        env.storage()
+            .instance()
+            .set(&DataKey::GlobalWeight(game_id.clone()), &config);
+
+        let season = season_state(&env, &game_id).season;
+        let ranking = Ranking::game(&env, &game_id, season);
+        let last = old_len.max(config.points_per_rank.len());
+        refresh_global_points(&env, &ranking, &config, 1, last)?;
+
+        GlobalWeightSet {
+            game_id,
+            weight: config,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Get how a game's ranks convert into global arcade points.
+    pub fn get_global_weight(env: Env, game_id: Symbol) -> Option<GlobalWeight> {
+        global_weight(&env, &game_id)
+    }
+
+    /// Get the top players by global arcade points, up to a certain limit.
+    pub fn global_top_players(env: Env, limit: u32) -> Result<Vec<ScoreEntry>, Error> {
+        page(&env, &Ranking::global(), 0, limit)
+    }
+
+    /// Get up to `limit` players of the global ranking, starting at the
+    /// 0-based position `offset`.
+    pub fn global_players_between(
+        env: Env,
+        offset: u32,
+        limit: u32,
+    ) -> Result<Vec<ScoreEntry>, Error> {
+        page(&env, &Ranking::global(), offset, limit)
+    }
+
+    /// Get a player's global rank (1-indexed). Returns 0 without points.
+    pub fn global_rank(env: Env, player: Address) -> u32 {
+        let points = global_points(&env, &player);
+        points.map_or(0, |points| {
+            rank_of(&env, &Ranking::global(), &player, points)
+        })
+    }
+
+    /// Get a player's global arcade points.
+    pub fn get_global_points(env: Env, player: Address) -> u64 {
+        global_points(&env, &player).unwrap_or(0)
+    }
+
+    /// Get the number of players with global arcade points.
+    pub fn global_player_count(env: Env) -> u32 {
+        load_index(&env, &Ranking::global()).total
+    }
+
+    /// Configure a game's season length and archive size.
+    /// `archive_size` must be between 1 and `MAX_LEADERBOARD_SIZE`.
+    pub fn set_season_config(
+        env: Env,
+        admin: Address,
+        game_id: Symbol,
+        duration: u64,
+        archive_size: u32,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        if archive_size == 0 || archive_size > MAX_LEADERBOARD_SIZE {
             return Err(Error::InvalidLimit);
         }
 
-        let leaderboard: Vec<ScoreEntry> = env
+        // The schedule of a game's first season starts when it is configured
+        let state = season_state(&env, &game_id);
+        if !env
             .storage()
-            .persistent()
-            .get(&DataKey::Leaderboard(game_id))
-            .unwrap_or(Vec::new(&env));
+            .instance()
+            .has(&DataKey::SeasonState(game_id.clone()))
+        {
+            let state = SeasonState {
+                started_at: env.ledger().timestamp(),
+                ..state
+            };
+            env.storage()
+                .instance()
+                .set(&DataKey::SeasonState(game_id.clone()), &state);
+        }
 
-        let mut result = Vec::new(&env);
-        let actual_limit = if limit < leaderboard.len() {
-            limit
-        } else {
-            leaderboard.len()
+        let config = SeasonConfig {
+            duration,
+            archive_size,
         };
+        env.storage()
+            .instance()
+            .set(&DataKey::SeasonConfig(game_id.clone()), &config);
+        SeasonConfigSet { game_id, config }.publish(&env);
+        Ok(())
+    }
+
+    /// Close a game's current season, archive its top list and start the next
+    /// season with empty scores. Returns the new season number.
+    pub fn close_season(env: Env, admin: Address, game_id: Symbol) -> Result<u32, Error> {
+        require_admin(&env, &admin)?;
+        close_current_season(&env, &game_id)
+    }
 
-        for i in 0..actual_limit {
-            result.push_back(leaderboard.get_unchecked(i));
+    /// Close a game's current season once its scheduled duration has passed.
+    /// Callable by anyone. Returns the new season number.
+    pub fn close_expired_season(env: Env, game_id: Symbol) -> Result<u32, Error> {
+        if season_expired(&env, &game_id).is_none() {
+            return Err(Error::SeasonNotEnded);
         }
+        close_current_season(&env, &game_id)
+    }
 
-        Ok(result)
+    /// Delete up to `limit` (1 to `MAX_PRUNE_BATCH`) segments of a closed
+    /// season's ranking; only its archive is read once the season is closed.
+    /// Callable by anyone, and repeated until it returns 0. Returns the number
+    /// of entries still stored.
+    pub fn prune_season(env: Env, game_id: Symbol, season: u32, limit: u32) -> Result<u32, Error> {
+        load_archive(&env, &game_id, season)?;
+        if limit == 0 || limit > MAX_PRUNE_BATCH {
+            return Err(Error::InvalidLimit);
+        }
+        Ok(prune_ranking(
+            &env,
+            &Ranking::game(&env, &game_id, season),
+            limit,
+        ))
     }
 
-    /// Get the rank of a player in a specific game (1-indexed).
-    /// Returns 0 if player is not in the top leaderboard.
-    pub fn player_rank(env: Env, game_id: Symbol, player: Address) -> Result<u32, Error> {
-        let leaderboard: Vec<ScoreEntry> = env
-            .storage()
+    /// Get the season currently accepting scores for a game.
+    pub fn current_season(env: Env, game_id: Symbol) -> SeasonState {
+        season_state(&env, &game_id)
+    }
+
+    /// Get a game's season settings.
+    pub fn get_season_config(env: Env, game_id: Symbol) -> SeasonConfig {
+        season_config(&env, &game_id)
+    }
+
+    /// Get the archived top list of a closed season.
+    pub fn get_season_archive(env: Env, game_id: Symbol, season: u32) -> Option<SeasonArchive> {
+        env.storage()
             .persistent()
-            .get(&DataKey::Leaderboard(game_id))
-            .unwrap_or(Vec::new(&env));
+            .get(&DataKey::SeasonArchive(game_id, season))
+    }
 
-        for i in 0..leaderboard.len() {
-            if leaderboard.get_unchecked(i).player == player {
-                return Ok(i + 1);
-            }
-        }
+    /// Get the top players of a closed season, up to a certain limit.
+    pub fn season_top_players(
+        env: Env,
+        game_id: Symbol,
+        season: u32,
+        limit: u32,
+    ) -> Result<Vec<ScoreEntry>, Error> {
+        let archive = load_archive(&env, &game_id, season)?;
+        take_top(&env, &archive.entries, limit)
+    }
 
-        Ok(0)
+    /// Get a player's rank in a closed season (1-indexed).
+    /// Returns 0 if the player is not in the season's archive.
+    pub fn season_rank(
+        env: Env,
+        game_id: Symbol,
+        season: u32,
+        player: Address,
+    ) -> Result<u32, Error> {
+        let archive = load_archive(&env, &game_id, season)?;
+        Ok(rank_in(&archive.entries, &player))
+    }
+
+    /// Get a game's score aggregation mode (`BestHigh` unless set).
+    pub fn get_game_mode(env: Env, game_id: Symbol) -> ScoreMode {
+        game_mode(&env, &game_id)
     }
 
-    /// Get a player's raw score.
+    /// Get a player's aggregated score in the current season.
     pub fn get_player_score(env: Env, game_id: Symbol, player: Address) -> u64 {
         env.storage()
             .persistent()
-            .get(&DataKey::PlayerScore(game_id, player))
+            .get(&DataKey::PlayerScore(
+                game_id.clone(),
+                season_state(&env, &game_id).season,
+                player,
+            ))
             .unwrap_or(0)
     }
 }
@@ -269,74 +741,692 @@ fn require_admin(env: &Env, caller: &Address) -> Result<(), Error> {
     Ok(())
 }
 
-fn update_leaderboard_internal(
+fn game_mode(env: &Env, game_id: &Symbol) -> ScoreMode {
+    env.storage()
+        .instance()
+        .get(&DataKey::GameMode(game_id.clone()))
+        .unwrap_or(ScoreMode::BestHigh)
+}
+
+/// Whether score `a` ranks strictly ahead of score `b` under `mode`.
+fn ranks_ahead(mode: ScoreMode, a: u64, b: u64) -> bool {
+    match mode {
+        ScoreMode::BestLow => a < b,
+        ScoreMode::BestHigh | ScoreMode::Cumulative | ScoreMode::Latest => a > b,
+    }
+}
+
+/// Whether entry `a` ranks strictly ahead of entry `b`: the better score
+/// first, ties broken by the earliest ledger the score was reached in.
+fn entry_ahead(mode: ScoreMode, a: &ScoreEntry, b: &ScoreEntry) -> bool {
+    if a.score != b.score {
+        return ranks_ahead(mode, a.score, b.score);
+    }
+    a.ledger < b.ledger
+}
+
+fn season_config(env: &Env, game_id: &Symbol) -> SeasonConfig {
+    env.storage()
+        .instance()
+        .get(&DataKey::SeasonConfig(game_id.clone()))
+        .unwrap_or(SeasonConfig {
+            duration: 0,
+            archive_size: MAX_LEADERBOARD_SIZE,
+        })
+}
+
+fn season_state(env: &Env, game_id: &Symbol) -> SeasonState {
+    env.storage()
+        .instance()
+        .get(&DataKey::SeasonState(game_id.clone()))
+        .unwrap_or(SeasonState {
+            season: 1,
+            started_at: 0,
+        })
+}
+
+/// Returns the scheduled end of the current season if it has passed.
+fn season_expired(env: &Env, game_id: &Symbol) -> Option<u64> {
+    let duration = season_config(env, game_id).duration;
+    if duration == 0 {
+        return None;
+    }
+    let end = season_state(env, game_id)
+        .started_at
+        .saturating_add(duration);
+    (env.ledger().timestamp() >= end).then_some(end)
+}
+
+/// Archive the current season's top list and open the next season.
+/// Returns the new season number.
+fn close_current_season(env: &Env, game_id: &Symbol) -> Result<u32, Error> {
+    let config = season_config(env, game_id);
+    let state = season_state(env, game_id);
+    let now = env.ledger().timestamp();
+
+    let ranking = Ranking::game(env, game_id, state.season);
+    let entries = page(env, &ranking, 0, config.archive_size)?;
+    let archived = entries.len();
+    let archive_key = DataKey::SeasonArchive(game_id.clone(), state.season);
+    env.storage().persistent().set(
+        &archive_key,
+        &SeasonArchive {
+            season: state.season,
+            started_at: state.started_at,
+            closed_at: now,
+            entries,
+        },
+    );
+    env.storage().persistent().extend_ttl(
+        &archive_key,
+        PERSISTENT_BUMP_LEDGERS,
+        PERSISTENT_BUMP_LEDGERS,
+    );
+
+    // The closed season no longer earns global points
+    if let Some(weight) = global_weight(env, game_id) {
+        let len = weight.points_per_rank.len();
+        if len > 0 {
+            for entry in page(env, &ranking, 0, len)?.iter() {
+                set_contribution(env, game_id, &entry.player, 0)?;
+            }
+        }
+    }
+
+    // Scheduled seasons stay aligned to the schedule, skipping idle periods
+    let started_at = match season_expired(env, game_id) {
+        Some(end) => now - (now - end) % config.duration,
+        None => now,
+    };
+    let season = state.season.checked_add(1).ok_or(Error::Overflow)?;
+    env.storage().instance().set(
+        &DataKey::SeasonState(game_id.clone()),
+        &SeasonState { season, started_at },
+    );
+
+    SeasonClosed {
+        game_id: game_id.clone(),
+        season: state.season,
+        closed_at: now,
+        archived,
+    }
+    .publish(env);
+
+    Ok(season)
+}
+
+/// Which ranked list a `Ranking` refers to.
+enum Board<'a> {
+    Game(&'a Symbol, u32),
+    Global,
+}
+
+/// A ranked list and the order it is sorted in.
+struct Ranking<'a> {
+    board: Board<'a>,
+    mode: ScoreMode,
+}
+
+impl<'a> Ranking<'a> {
+    /// The ranking of one game season.
+    fn game(env: &Env, game_id: &'a Symbol, season: u32) -> Self {
+        Ranking {
+            board: Board::Game(game_id, season),
+            mode: game_mode(env, game_id),
+        }
+    }
+
+    /// The global arcade points ranking.
+    fn global() -> Self {
+        Ranking {
+            board: Board::Global,
+            mode: ScoreMode::BestHigh,
+        }
+    }
+
+    fn index_key(&self) -> DataKey {
+        match self.board {
+            Board::Game(game_id, season) => DataKey::RankIndex(game_id.clone(), season),
+            Board::Global => DataKey::GlobalIndex,
+        }
+    }
+
+    fn shard_key(&self, id: u32) -> DataKey {
+        match self.board {
+            Board::Game(game_id, season) => DataKey::RankShard(game_id.clone(), season, id),
+            Board::Global => DataKey::GlobalShard(id),
+        }
+    }
+
+    fn segment_key(&self, id: u32) -> DataKey {
+        match self.board {
+            Board::Game(game_id, season) => DataKey::RankSegment(game_id.clone(), season, id),
+            Board::Global => DataKey::GlobalSegment(id),
+        }
+    }
+}
+
+fn load_index(env: &Env, ranking: &Ranking) -> RankIndex {
+    env.storage()
+        .persistent()
+        .get(&ranking.index_key())
+        .unwrap_or(RankIndex {
+            next_id: 0,
+            total: 0,
+            shards: Vec::new(env),
+        })
+}
+
+fn load_shard(env: &Env, ranking: &Ranking, id: u32) -> Vec<SegmentInfo> {
+    env.storage()
+        .persistent()
+        .get(&ranking.shard_key(id))
+        .unwrap_or(Vec::new(env))
+}
+
+fn load_segment(env: &Env, ranking: &Ranking, id: u32) -> Vec<ScoreEntry> {
+    env.storage()
+        .persistent()
+        .get(&ranking.segment_key(id))
+        .unwrap_or(Vec::new(env))
+}
+
+fn save_persistent<V: soroban_sdk::IntoVal<Env, soroban_sdk::Val>>(
     env: &Env,
-    game_id: &Symbol,
-    player: Address,
-    score: u64,
-) -> Result<(), Error> {
-    let leaderboard_key = DataKey::Leaderboard(game_id.clone());
-    let mut leaderboard: Vec<ScoreEntry> = env
-        .storage()
+    key: &DataKey,
+    value: &V,
+) {
+    env.storage().persistent().set(key, value);
+    env.storage()
         .persistent()
-        .get(&leaderboard_key)
-        .unwrap_or(Vec::new(env));
-
-    // Remove existing entry for the player if present
-    let mut existing_index: Option<u32> = None;
-    for i in 0..leaderboard.len() {
-        if leaderboard.get_unchecked(i).player == player {
-            existing_index = Some(i);
-            break;
+        .extend_ttl(key, PERSISTENT_BUMP_LEDGERS, PERSISTENT_BUMP_LEDGERS);
+}
+
+/// Store a segment and return its refreshed index entry.
+fn save_segment(env: &Env, ranking: &Ranking, id: u32, entries: &Vec<ScoreEntry>) -> SegmentInfo {
+    save_persistent(env, &ranking.segment_key(id), entries);
+    SegmentInfo {
+        id,
+        len: entries.len(),
+        worst: entries.last_unchecked().score,
+    }
+}
+
+/// Store a shard and return its refreshed index entry.
+fn save_shard(env: &Env, ranking: &Ranking, id: u32, segments: &Vec<SegmentInfo>) -> ShardInfo {
+    save_persistent(env, &ranking.shard_key(id), segments);
+    let mut len = 0;
+    for info in segments.iter() {
+        len += info.len;
+    }
+    ShardInfo {
+        id,
+        len,
+        segments: segments.len(),
+        worst: segments.last_unchecked().worst,
+    }
+}
+
+/// Take the next free segment or shard id.
+fn next_id(index: &mut RankIndex) -> Result<u32, Error> {
+    let id = index.next_id;
+    index.next_id = id.checked_add(1).ok_or(Error::Overflow)?;
+    Ok(id)
+}
+
+/// Find `player`, currently scored `score`, as (shard position, segment
+/// position within the shard, position within the segment).
+fn locate(
+    env: &Env,
+    ranking: &Ranking,
+    index: &RankIndex,
+    player: &Address,
+    score: u64,
+) -> Option<(u32, u32, u32)> {
+    for i in 0..index.shards.len() {
+        // Every entry in this shard ranks ahead of the player
+        let shard = index.shards.get_unchecked(i);
+        if ranks_ahead(ranking.mode, shard.worst, score) {
+            continue;
+        }
+        let segments = load_shard(env, ranking, shard.id);
+        for j in 0..segments.len() {
+            let info = segments.get_unchecked(j);
+            if ranks_ahead(ranking.mode, info.worst, score) {
+                continue;
+            }
+            let entries = load_segment(env, ranking, info.id);
+            for k in 0..entries.len() {
+                if entries.get_unchecked(k).player == *player {
+                    return Some((i, j, k));
+                }
+            }
+            // Equal scores may continue into the next segment
+            if info.worst != score {
+                return None;
+            }
         }
     }
+    None
+}
+
+/// 1-indexed rank of `player`, currently scored `score`, or 0 if unranked.
+fn rank_of(env: &Env, ranking: &Ranking, player: &Address, score: u64) -> u32 {
+    let index = load_index(env, ranking);
+    let Some((shard, segment, position)) = locate(env, ranking, &index, player, score) else {
+        return 0;
+    };
+    let mut rank = position + 1;
+    for i in 0..shard {
+        rank += index.shards.get_unchecked(i).len;
+    }
+    let segments = load_shard(env, ranking, index.shards.get_unchecked(shard).id);
+    for j in 0..segment {
+        rank += segments.get_unchecked(j).len;
+    }
+    rank
+}
 
-    if let Some(idx) = existing_index {
-        leaderboard.remove(idx);
+/// Read up to `limit` ranked entries starting at the 0-based `offset`.
+fn page(env: &Env, ranking: &Ranking, offset: u32, limit: u32) -> Result<Vec<ScoreEntry>, Error> {
+    if limit == 0 || limit > MAX_LEADERBOARD_SIZE {
+        return Err(Error::InvalidLimit);
     }
 
-    // Binary search/Insertion sort
-    let mut inserted = false;
-    for i in 0..leaderboard.len() {
-        if score > leaderboard.get_unchecked(i).score {
-            leaderboard.insert(
-                i,
-                ScoreEntry {
-                    player: player.clone(),
-                    score,
-                },
-            );
-            inserted = true;
+    let index = load_index(env, ranking);
+    let mut result = Vec::new(env);
+    let mut skip = offset;
+    for shard in index.shards.iter() {
+        if skip >= shard.len {
+            skip -= shard.len;
+            continue;
+        }
+        for info in load_shard(env, ranking, shard.id).iter() {
+            if skip >= info.len {
+                skip -= info.len;
+                continue;
+            }
+            let entries = load_segment(env, ranking, info.id);
+            for j in skip..entries.len() {
+                if result.len() == limit {
+                    return Ok(result);
+                }
+                result.push_back(entries.get_unchecked(j));
+            }
+            skip = 0;
+        }
+    }
+    Ok(result)
+}
+
+/// Delete up to `limit` segments from the end of a ranking, along with the
+/// shards and the index they empty. Returns the number of entries left.
+fn prune_ranking(env: &Env, ranking: &Ranking, limit: u32) -> u32 {
+    let mut index = load_index(env, ranking);
+    let mut removed = 0;
+    while removed < limit {
+        let Some(shard) = index.shards.last() else {
             break;
+        };
+        let mut segments = load_shard(env, ranking, shard.id);
+        while removed < limit {
+            let Some(info) = segments.pop_back() else {
+                break;
+            };
+            env.storage()
+                .persistent()
+                .remove(&ranking.segment_key(info.id));
+            index.total -= info.len;
+            removed += 1;
         }
+        let last = index.shards.len() - 1;
+        if segments.is_empty() {
+            env.storage()
+                .persistent()
+                .remove(&ranking.shard_key(shard.id));
+            index.shards.remove(last);
+        } else {
+            let info = save_shard(env, ranking, shard.id, &segments);
+            index.shards.set(last, info);
+        }
+    }
+    if index.shards.is_empty() {
+        env.storage().persistent().remove(&ranking.index_key());
+    } else {
+        save_persistent(env, &ranking.index_key(), &index);
+    }
+    index.total
+}
+
+fn global_weight(env: &Env, game_id: &Symbol) -> Option<GlobalWeight> {
+    env.storage()
+        .instance()
+        .get(&DataKey::GlobalWeight(game_id.clone()))
+}
+
+fn global_points(env: &Env, player: &Address) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::GlobalPoints(player.clone()))
+}
+
+/// Global points earned by `rank` (1-indexed; 0 = unranked) in a game.
+fn points_for(weight: &GlobalWeight, rank: u32) -> u64 {
+    rank.checked_sub(1)
+        .and_then(|i| weight.points_per_rank.get(i))
+        .map_or(0, |points| points as u64 * weight.weight as u64)
+}
+
+/// Recompute the game points of the players ranked `first..=last`.
+fn refresh_global_points(
+    env: &Env,
+    ranking: &Ranking,
+    weight: &GlobalWeight,
+    first: u32,
+    last: u32,
+) -> Result<(), Error> {
+    let Board::Game(game_id, _) = ranking.board else {
+        return Ok(());
+    };
+    if first == 0 || first > last {
+        return Ok(());
+    }
+    let entries = page(env, ranking, first - 1, last - first + 1)?;
+    for (i, entry) in entries.iter().enumerate() {
+        let points = points_for(weight, first + i as u32);
+        set_contribution(env, game_id, &entry.player, points)?;
+    }
+    Ok(())
+}
+
+/// Set the points `player` earns from `game_id` and re-rank their global
+/// total if it changed.
+fn set_contribution(
+    env: &Env,
+    game_id: &Symbol,
+    player: &Address,
+    points: u64,
+) -> Result<(), Error> {
+    let key = DataKey::GlobalContribution(game_id.clone(), player.clone());
+    let old: u64 = env.storage().persistent().get(&key).unwrap_or(0);
+    if old == points {
+        return Ok(());
+    }
+    if points == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        save_persistent(env, &key, &points);
     }
 
-    if !inserted && leaderboard.len() < MAX_LEADERBOARD_SIZE {
-        leaderboard.push_back(ScoreEntry {
+    let previous = global_points(env, player);
+    let total = previous
+        .unwrap_or(0)
+        .checked_sub(old)
+        .and_then(|t| t.checked_add(points))
+        .ok_or(Error::Overflow)?;
+
+    let ranking = Ranking::global();
+    let points_key = DataKey::GlobalPoints(player.clone());
+    let mut index = load_index(env, &ranking);
+    if let Some(previous) = previous {
+        remove_entry(env, &ranking, &mut index, player, previous);
+    }
+    if total == 0 {
+        env.storage().persistent().remove(&points_key);
+    } else {
+        save_persistent(env, &points_key, &total);
+        let entry = ScoreEntry {
             player: player.clone(),
-            score,
-        });
+            score: total,
+            ledger: env.ledger().sequence(),
+            timestamp: env.ledger().timestamp(),
+        };
+        insert_entry(env, &ranking, &mut index, entry)?;
     }
+    save_persistent(env, &ranking.index_key(), &index);
 
-    // Truncate if exceeded max size
-    while leaderboard.len() > MAX_LEADERBOARD_SIZE {
-        leaderboard.pop_back();
+    GlobalPointsUpdated {
+        player: player.clone(),
+        points: total,
     }
+    .publish(env);
+    Ok(())
+}
 
+fn load_archive(env: &Env, game_id: &Symbol, season: u32) -> Result<SeasonArchive, Error> {
     env.storage()
         .persistent()
-        .set(&leaderboard_key, &leaderboard);
-    env.storage().persistent().extend_ttl(
-        &leaderboard_key,
-        PERSISTENT_BUMP_LEDGERS,
-        PERSISTENT_BUMP_LEDGERS,
-    );
+        .get(&DataKey::SeasonArchive(game_id.clone(), season))
+        .ok_or(Error::SeasonNotFound)
+}
 
-    LeaderboardUpdated {
-        game_id: game_id.clone(),
+/// Copy the first `limit` entries of a ranked list.
+fn take_top(env: &Env, entries: &Vec<ScoreEntry>, limit: u32) -> Result<Vec<ScoreEntry>, Error> {
+    if limit == 0 || limit > MAX_LEADERBOARD_SIZE {
+        return Err(Error::InvalidLimit);
     }
-    .publish(env);
 
+    let mut result = Vec::new(env);
+    for i in 0..limit.min(entries.len()) {
+        result.push_back(entries.get_unchecked(i));
+    }
+    Ok(result)
+}
+
+/// 1-indexed position of `player` in a ranked list, or 0 if absent.
+fn rank_in(entries: &Vec<ScoreEntry>, player: &Address) -> u32 {
+    for i in 0..entries.len() {
+        if entries.get_unchecked(i).player == *player {
+            return i + 1;
+        }
+    }
+    0
+}
+
+/// Move `entry.player` from their `previous` score (if ranked) to `entry`.
+fn update_leaderboard_internal(
+    env: &Env,
+    ranking: &Ranking,
+    entry: ScoreEntry,
+    previous: Option<u64>,
+) -> Result<(), Error> {
+    let mut index = load_index(env, ranking);
+    if let Some(previous) = previous {
+        remove_entry(env, ranking, &mut index, &entry.player, previous);
+    }
+    insert_entry(env, ranking, &mut index, entry)?;
+    save_persistent(env, &ranking.index_key(), &index);
+    Ok(())
+}
+
+/// Remove `player`, currently scored `score`, from a ranking if present.
+fn remove_entry(env: &Env, ranking: &Ranking, index: &mut RankIndex, player: &Address, score: u64) {
+    if let Some((shard, segment, position)) = locate(env, ranking, index, player, score) {
+        remove_at(env, ranking, index, shard, segment, position);
+    }
+}
+
+/// Remove the entry at `position` of the `segment`-th segment of the
+/// `shard`-th shard, then merge the segment and the shard with a neighbour
+/// they now fit together with.
+fn remove_at(
+    env: &Env,
+    ranking: &Ranking,
+    index: &mut RankIndex,
+    shard: u32,
+    segment: u32,
+    position: u32,
+) {
+    let shard_info = index.shards.get_unchecked(shard);
+    let mut segments = load_shard(env, ranking, shard_info.id);
+    let info = segments.get_unchecked(segment);
+    let mut entries = load_segment(env, ranking, info.id);
+    entries.remove(position);
+    if entries.is_empty() {
+        env.storage()
+            .persistent()
+            .remove(&ranking.segment_key(info.id));
+        segments.remove(segment);
+    } else {
+        let info = save_segment(env, ranking, info.id, &entries);
+        segments.set(segment, info);
+        merge_with_previous(env, ranking, &mut segments, segment + 1);
+    }
+    merge_with_previous(env, ranking, &mut segments, segment);
+
+    if segments.is_empty() {
+        env.storage()
+            .persistent()
+            .remove(&ranking.shard_key(shard_info.id));
+        index.shards.remove(shard);
+    } else {
+        let info = save_shard(env, ranking, shard_info.id, &segments);
+        index.shards.set(shard, info);
+        merge_shard_with_previous(env, ranking, index, shard + 1);
+    }
+    merge_shard_with_previous(env, ranking, index, shard);
+    index.total -= 1;
+}
+
+/// Merge the `i`-th segment of a shard into the one before it if both fit in
+/// a single segment.
+fn merge_with_previous(env: &Env, ranking: &Ranking, segments: &mut Vec<SegmentInfo>, i: u32) {
+    if i == 0 || i >= segments.len() {
+        return;
+    }
+    let previous = segments.get_unchecked(i - 1);
+    let info = segments.get_unchecked(i);
+    if previous.len + info.len > SEGMENT_SIZE {
+        return;
+    }
+    let mut entries = load_segment(env, ranking, previous.id);
+    entries.append(&load_segment(env, ranking, info.id));
+    env.storage()
+        .persistent()
+        .remove(&ranking.segment_key(info.id));
+    segments.remove(i);
+    let merged = save_segment(env, ranking, previous.id, &entries);
+    segments.set(i - 1, merged);
+}
+
+/// Merge the `i`-th shard into the one before it if both fit in a single
+/// shard, then merge the segments that meet at the join.
+fn merge_shard_with_previous(env: &Env, ranking: &Ranking, index: &mut RankIndex, i: u32) {
+    if i == 0 || i >= index.shards.len() {
+        return;
+    }
+    let previous = index.shards.get_unchecked(i - 1);
+    let info = index.shards.get_unchecked(i);
+    if previous.segments + info.segments > SHARD_SIZE {
+        return;
+    }
+    let mut segments = load_shard(env, ranking, previous.id);
+    let join = segments.len();
+    segments.append(&load_shard(env, ranking, info.id));
+    merge_with_previous(env, ranking, &mut segments, join);
+    env.storage()
+        .persistent()
+        .remove(&ranking.shard_key(info.id));
+    index.shards.remove(i);
+    let merged = save_shard(env, ranking, previous.id, &segments);
+    index.shards.set(i - 1, merged);
+}
+
+/// Position of the first of `worst` scores that `score` ranks ahead of.
+fn first_behind(mode: ScoreMode, score: u64, worst: impl Iterator<Item = u64>) -> Option<u32> {
+    for (i, worst) in worst.enumerate() {
+        if ranks_ahead(mode, score, worst) {
+            return Some(i as u32);
+        }
+    }
+    None
+}
+
+/// Insert `entry` in order. Entries are ordered by `entry_ahead`; ties within
+/// one ledger keep the order they were submitted in.
+fn insert_entry(
+    env: &Env,
+    ranking: &Ranking,
+    index: &mut RankIndex,
+    entry: ScoreEntry,
+) -> Result<(), Error> {
+    // Insert into the first shard, and the first segment within it, holding
+    // a lower ranked entry, else the last. The new entry is the most recent,
+    // so it only ranks ahead of entries with a worse score.
+    let shard = first_behind(
+        ranking.mode,
+        entry.score,
+        index.shards.iter().map(|info| info.worst),
+    )
+    .unwrap_or(index.shards.len().saturating_sub(1));
+    let (shard_id, mut segments) = match index.shards.get(shard) {
+        Some(info) => (info.id, load_shard(env, ranking, info.id)),
+        None => (next_id(index)?, Vec::new(env)),
+    };
+    let segment = first_behind(
+        ranking.mode,
+        entry.score,
+        segments.iter().map(|info| info.worst),
+    )
+    .unwrap_or(segments.len().saturating_sub(1));
+    let id = match segments.get(segment) {
+        Some(info) => info.id,
+        None => next_id(index)?,
+    };
+    let mut entries = load_segment(env, ranking, id);
+
+    let mut position = entries.len();
+    for j in 0..entries.len() {
+        if entry_ahead(ranking.mode, &entry, &entries.get_unchecked(j)) {
+            position = j;
+            break;
+        }
+    }
+    entries.insert(position, entry);
+    index.total = index.total.checked_add(1).ok_or(Error::Overflow)?;
+
+    // Split a full segment in two
+    let split = entries.len() > SEGMENT_SIZE;
+    if split {
+        let half = entries.len() / 2;
+        let tail = entries.slice(half..);
+        entries = entries.slice(..half);
+        let tail_id = next_id(index)?;
+        let info = save_segment(env, ranking, tail_id, &tail);
+        segments.insert(segment + 1, info);
+    }
+    let info = save_segment(env, ranking, id, &entries);
+    if segments.get(segment).is_some_and(|s| s.id == id) {
+        segments.set(segment, info);
+    } else {
+        segments.insert(segment, info);
+    }
+    // The halves may now fit together with their outer neighbours
+    if split {
+        merge_with_previous(env, ranking, &mut segments, segment + 2);
+        merge_with_previous(env, ranking, &mut segments, segment);
+    }
+
+    // Likewise split a full shard in two
+    let split = segments.len() > SHARD_SIZE;
+    if split {
+        let half = segments.len() / 2;
+        let tail = segments.slice(half..);
+        segments = segments.slice(..half);
+        let tail_id = next_id(index)?;
+        let info = save_shard(env, ranking, tail_id, &tail);
+        index.shards.insert(shard + 1, info);
+    }
+    let info = save_shard(env, ranking, shard_id, &segments);
+    if index.shards.get(shard).is_some_and(|s| s.id == shard_id) {
+        index.shards.set(shard, info);
+    } else {
+        index.shards.insert(shard, info);
+    }
+    if split {
+        merge_shard_with_previous(env, ranking, index, shard + 2);
+        merge_shard_with_previous(env, ranking, index, shard);
+    }
     Ok(())
 }
 
@@ -1,7 +1,13 @@
 #![cfg(test)]
 
+extern crate std;
+
 use super::*;
-use soroban_sdk::{symbol_short, testutils::Address as _, Address, Env};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger as _},
+    Address, Env,
+};
 
 fn setup(env: &Env) -> (LeaderboardContractClient<'_>, Address) {
     let admin = Address::generate(env);
@@ -122,3 +128,632 @@ fn test_inactive_game() {
     let result = client.try_submit_score(&admin, &player, &game_id, &500);
     assert_eq!(result, Err(Ok(Error::GameNotFound)));
 }
+
+#[test]
+fn test_best_low_mode() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let game_id = symbol_short!("race");
+
+    env.mock_all_auths();
+    client.activate_game(&admin, &game_id, &ScoreMode::BestLow);
+    assert_eq!(client.get_game_mode(&game_id), ScoreMode::BestLow);
+
+    let p1 = Address::generate(&env);
+    let p2 = Address::generate(&env);
+    let p3 = Address::generate(&env);
+
+    client.submit_score(&admin, &p1, &game_id, &90);
+    client.submit_score(&admin, &p2, &game_id, &60);
+    client.submit_score(&admin, &p3, &game_id, &75);
+
+    // A slower time does not replace a faster one
+    client.submit_score(&admin, &p2, &game_id, &80);
+    assert_eq!(client.get_player_score(&game_id, &p2), 60);
+
+    // A faster time moves the player up
+    client.submit_score(&admin, &p1, &game_id, &50);
+    assert_eq!(client.get_player_score(&game_id, &p1), 50);
+
+    let top = client.top_players(&game_id, &10);
+    assert_eq!(top.get(0).unwrap().player, p1); // 50
+    assert_eq!(top.get(1).unwrap().player, p2); // 60
+    assert_eq!(top.get(2).unwrap().player, p3); // 75
+}
+
+#[test]
+fn test_cumulative_mode() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let game_id = symbol_short!("grind");
+
+    env.mock_all_auths();
+    client.activate_game(&admin, &game_id, &ScoreMode::Cumulative);
+
+    let p1 = Address::generate(&env);
+    let p2 = Address::generate(&env);
+
+    client.submit_score(&admin, &p1, &game_id, &100);
+    client.submit_score(&admin, &p2, &game_id, &150);
+    client.submit_score(&admin, &p1, &game_id, &80);
+    assert_eq!(client.get_player_score(&game_id, &p1), 180);
+    assert_eq!(client.player_rank(&game_id, &p1), 1);
+    assert_eq!(client.player_rank(&game_id, &p2), 2);
+
+    let result = client.try_submit_score(&admin, &p1, &game_id, &u64::MAX);
+    assert_eq!(result, Err(Ok(Error::Overflow)));
+}
+
+#[test]
+fn test_latest_mode() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let game_id = symbol_short!("daily");
+
+    env.mock_all_auths();
+    client.activate_game(&admin, &game_id, &ScoreMode::Latest);
+
+    let p1 = Address::generate(&env);
+    let p2 = Address::generate(&env);
+
+    client.submit_score(&admin, &p1, &game_id, &300);
+    client.submit_score(&admin, &p2, &game_id, &200);
+    assert_eq!(client.player_rank(&game_id, &p1), 1);
+
+    // A lower latest score replaces the old one and drops the player
+    client.submit_score(&admin, &p1, &game_id, &100);
+    assert_eq!(client.get_player_score(&game_id, &p1), 100);
+    assert_eq!(client.player_rank(&game_id, &p1), 2);
+    assert_eq!(client.player_rank(&game_id, &p2), 1);
+    assert_eq!(client.top_players(&game_id, &10).len(), 2);
+}
+
+#[test]
+fn test_mode_locked_once_ranked() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let game_id = symbol_short!("game1");
+    let player = Address::generate(&env);
+
+    env.mock_all_auths();
+    // Games activated without a mode keep the best-high behaviour
+    client.set_game_active(&admin, &game_id, &true);
+    assert_eq!(client.get_game_mode(&game_id), ScoreMode::BestHigh);
+    client.activate_game(&admin, &game_id, &ScoreMode::Latest);
+
+    client.submit_score(&admin, &player, &game_id, &10);
+
+    let result = client.try_activate_game(&admin, &game_id, &ScoreMode::BestLow);
+    assert_eq!(result, Err(Ok(Error::ModeLocked)));
+
+    // Re-activating with the same mode is allowed
+    client.set_game_active(&admin, &game_id, &false);
+    client.activate_game(&admin, &game_id, &ScoreMode::Latest);
+    assert_eq!(client.get_game_mode(&game_id), ScoreMode::Latest);
+}
+
+#[test]
+fn test_close_season_archives_and_resets() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let game_id = symbol_short!("game1");
+
+    env.mock_all_auths();
+    client.set_game_active(&admin, &game_id, &true);
+    client.set_season_config(&admin, &game_id, &0, &2);
+
+    let p1 = Address::generate(&env);
+    let p2 = Address::generate(&env);
+    let p3 = Address::generate(&env);
+
+    client.submit_score(&admin, &p1, &game_id, &100);
+    client.submit_score(&admin, &p2, &game_id, &300);
+    client.submit_score(&admin, &p3, &game_id, &200);
+    assert_eq!(client.current_season(&game_id).season, 1);
+
+    assert_eq!(client.close_season(&admin, &game_id), 2);
+
+    // Only the configured top 2 are archived
+    let archive = client.get_season_archive(&game_id, &1).unwrap();
+    assert_eq!(archive.entries.len(), 2);
+    assert_eq!(client.season_rank(&game_id, &1, &p2), 1);
+    assert_eq!(client.season_rank(&game_id, &1, &p3), 2);
+    assert_eq!(client.season_rank(&game_id, &1, &p1), 0);
+    let top = client.season_top_players(&game_id, &1, &1);
+    assert_eq!(top.get(0).unwrap().player, p2);
+
+    // The new season starts empty
+    assert_eq!(client.get_player_score(&game_id, &p2), 0);
+    assert_eq!(client.top_players(&game_id, &10).len(), 0);
+    client.submit_score(&admin, &p1, &game_id, &50);
+    assert_eq!(client.player_rank(&game_id, &p1), 1);
+
+    // The archive is not affected by the new season
+    assert_eq!(client.get_season_archive(&game_id, &1).unwrap(), archive);
+
+    let result = client.try_season_rank(&game_id, &2, &p1);
+    assert_eq!(result, Err(Ok(Error::SeasonNotFound)));
+}
+
+#[test]
+fn test_scheduled_seasons() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let game_id = symbol_short!("monthly");
+    let player = Address::generate(&env);
+
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+    client.set_game_active(&admin, &game_id, &true);
+    client.set_season_config(&admin, &game_id, &100, &10);
+    client.submit_score(&admin, &player, &game_id, &40);
+
+    let result = client.try_close_expired_season(&game_id);
+    assert_eq!(result, Err(Ok(Error::SeasonNotEnded)));
+
+    // A submission after the scheduled end opens the next season first
+    env.ledger().with_mut(|l| l.timestamp = 1_150);
+    client.submit_score(&admin, &player, &game_id, &10);
+    let state = client.current_season(&game_id);
+    assert_eq!(state.season, 2);
+    assert_eq!(state.started_at, 1_100);
+    assert_eq!(client.get_player_score(&game_id, &player), 10);
+    let archive = client.get_season_archive(&game_id, &1).unwrap();
+    assert_eq!(archive.entries.get(0).unwrap().score, 40);
+
+    // Anyone can close an expired season; idle periods are skipped
+    env.ledger().with_mut(|l| l.timestamp = 1_420);
+    assert_eq!(client.close_expired_season(&game_id), 3);
+    assert_eq!(client.current_season(&game_id).started_at, 1_400);
+    assert_eq!(client.season_rank(&game_id, &2, &player), 1);
+}
+
+#[test]
+fn test_mode_change_after_season_close() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let game_id = symbol_short!("game1");
+    let player = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.set_game_active(&admin, &game_id, &true);
+    client.submit_score(&admin, &player, &game_id, &10);
+
+    let result = client.try_activate_game(&admin, &game_id, &ScoreMode::BestLow);
+    assert_eq!(result, Err(Ok(Error::ModeLocked)));
+
+    client.close_season(&admin, &game_id);
+    client.activate_game(&admin, &game_id, &ScoreMode::BestLow);
+    assert_eq!(client.get_game_mode(&game_id), ScoreMode::BestLow);
+}
+
+#[test]
+fn test_ranks_beyond_top_page() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let game_id = symbol_short!("game1");
+
+    env.mock_all_auths();
+    client.set_game_active(&admin, &game_id, &true);
+
+    // Scores spread over several ranking segments, submitted out of order
+    let mut players = std::vec::Vec::new();
+    for i in 0..150u64 {
+        let player = Address::generate(&env);
+        client.submit_score(&admin, &player, &game_id, &((i * 37) % 150 + 1));
+        players.push(player);
+    }
+    assert_eq!(client.player_count(&game_id), 150);
+
+    // Scores are a permutation of 1..=150, so score s ranks 151 - s
+    for (i, player) in players.iter().enumerate() {
+        let score = (i as u64 * 37) % 150 + 1;
+        assert_eq!(client.player_rank(&game_id, player), 151 - score as u32);
+    }
+
+    let page = client.players_between(&game_id, &120, &100);
+    assert_eq!(page.len(), 30);
+    assert_eq!(page.get(0).unwrap().score, 30);
+    assert_eq!(page.get(29).unwrap().score, 1);
+    let page = client.players_between(&game_id, &60, &10);
+    for (i, entry) in page.iter().enumerate() {
+        assert_eq!(entry.score, 90 - i as u64);
+    }
+    assert_eq!(client.players_between(&game_id, &150, &10).len(), 0);
+    let result = client.try_players_between(&game_id, &0, &101);
+    assert_eq!(result, Err(Ok(Error::InvalidLimit)));
+
+    // Improving a low score moves the player to the top
+    let last = client
+        .players_between(&game_id, &149, &1)
+        .get(0)
+        .unwrap()
+        .player;
+    client.submit_score(&admin, &last, &game_id, &500);
+    assert_eq!(client.player_rank(&game_id, &last), 1);
+    assert_eq!(client.player_count(&game_id), 150);
+    assert_eq!(
+        client
+            .players_between(&game_id, &149, &1)
+            .get(0)
+            .unwrap()
+            .score,
+        2
+    );
+}
+
+#[test]
+fn test_latest_mode_moves_down_across_segments() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let game_id = symbol_short!("daily");
+
+    env.mock_all_auths();
+    client.activate_game(&admin, &game_id, &ScoreMode::Latest);
+
+    let mut players = std::vec::Vec::new();
+    for i in 0..100u64 {
+        let player = Address::generate(&env);
+        client.submit_score(&admin, &player, &game_id, &(1_000 - i));
+        players.push(player);
+    }
+
+    // The leader drops below everyone else, and back up again
+    client.submit_score(&admin, &players[0], &game_id, &1);
+    assert_eq!(client.player_rank(&game_id, &players[0]), 100);
+    assert_eq!(client.player_rank(&game_id, &players[1]), 1);
+    client.submit_score(&admin, &players[0], &game_id, &950);
+    assert_eq!(client.player_rank(&game_id, &players[0]), 51);
+    assert_eq!(client.player_rank(&game_id, &players[50]), 50);
+
+    // Ties keep arrival order
+    let tied = Address::generate(&env);
+    client.submit_score(&admin, &tied, &game_id, &950);
+    assert_eq!(client.player_rank(&game_id, &tied), 52);
+    assert_eq!(client.player_count(&game_id), 101);
+}
+
+fn rank_index(env: &Env, client: &LeaderboardContractClient, game_id: &Symbol) -> RankIndex {
+    let season = client.current_season(game_id).season;
+    env.as_contract(&client.address, || {
+        load_index(env, &Ranking::game(env, game_id, season))
+    })
+}
+
+/// Check both levels of a game's ranking against the merge invariants.
+fn assert_compact(env: &Env, client: &LeaderboardContractClient, game_id: &Symbol) {
+    let season = client.current_season(game_id).season;
+    env.as_contract(&client.address, || {
+        let ranking = Ranking::game(env, game_id, season);
+        let index = load_index(env, &ranking);
+        let mut total = 0;
+        let mut segment_count = 0;
+        for (i, shard) in index.shards.iter().enumerate() {
+            assert!(shard.segments > 0 && shard.segments <= SHARD_SIZE);
+            if i > 0 {
+                let previous = index.shards.get(i as u32 - 1).unwrap();
+                assert!(previous.segments + shard.segments > SHARD_SIZE);
+            }
+            let segments = load_shard(env, &ranking, shard.id);
+            assert_eq!(segments.len(), shard.segments);
+            assert_eq!(segments.last().unwrap().worst, shard.worst);
+            let mut len = 0;
+            for (j, info) in segments.iter().enumerate() {
+                assert!(info.len > 0 && info.len <= SEGMENT_SIZE);
+                if j > 0 {
+                    let previous = segments.get(j as u32 - 1).unwrap();
+                    assert!(previous.len + info.len > SEGMENT_SIZE);
+                }
+                len += info.len;
+            }
+            assert_eq!(len, shard.len);
+            total += len;
+            segment_count += shard.segments;
+        }
+        assert_eq!(total, index.total);
+        assert!(index.shards.len() <= 2 * segment_count / SHARD_SIZE + 1);
+    });
+}
+
+#[test]
+fn test_drained_segments_are_merged() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let game_id = symbol_short!("daily");
+
+    env.mock_all_auths();
+    client.activate_game(&admin, &game_id, &ScoreMode::Latest);
+
+    let mut players = std::vec::Vec::new();
+    for i in 0..192u64 {
+        let player = Address::generate(&env);
+        client.submit_score(&admin, &player, &game_id, &(1_000 - i));
+        players.push(player);
+    }
+    assert_compact(&env, &client, &game_id);
+
+    // Move the middle of the ranking to the top, draining its segments
+    for (j, player) in players[64..128].iter().enumerate() {
+        client.submit_score(&admin, player, &game_id, &(2_000 + j as u64));
+        assert_compact(&env, &client, &game_id);
+    }
+    assert_eq!(client.player_count(&game_id), 192);
+    assert_eq!(client.player_rank(&game_id, &players[127]), 1);
+    assert_eq!(client.player_rank(&game_id, &players[64]), 64);
+    assert_eq!(client.player_rank(&game_id, &players[0]), 65);
+    assert_eq!(client.player_rank(&game_id, &players[191]), 192);
+}
+
+#[test]
+fn test_large_ranking_keeps_every_player() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let game_id = symbol_short!("game1");
+
+    env.mock_all_auths();
+    client.set_game_active(&admin, &game_id, &true);
+
+    // Every new score ranks first, filling the first shard until it splits
+    let mut players = std::vec::Vec::new();
+    for i in 0..1_200u64 {
+        let player = Address::generate(&env);
+        client.submit_score(&admin, &player, &game_id, &((i + 1) * 10));
+        players.push(player);
+    }
+    assert_eq!(client.player_count(&game_id), 1_200);
+    assert!(rank_index(&env, &client, &game_id).shards.len() > 1);
+    assert_compact(&env, &client, &game_id);
+    assert_eq!(client.player_rank(&game_id, &players[1_199]), 1);
+    assert_eq!(client.player_rank(&game_id, &players[600]), 600);
+    assert_eq!(client.player_rank(&game_id, &players[0]), 1_200);
+
+    // Reads cross shard boundaries
+    let page = client.players_between(&game_id, &1_150, &100);
+    assert_eq!(page.len(), 50);
+    for (i, entry) in page.iter().enumerate() {
+        assert_eq!(entry.score, (50 - i as u64) * 10);
+    }
+
+    // The lowest score is still ranked
+    let late = Address::generate(&env);
+    client.submit_score(&admin, &late, &game_id, &5);
+    assert_eq!(client.player_rank(&game_id, &late), 1_201);
+    assert_eq!(client.player_count(&game_id), 1_201);
+
+    client.submit_score(&admin, &players[0], &game_id, &100_000);
+    assert_eq!(client.player_rank(&game_id, &players[0]), 1);
+    assert_eq!(client.player_rank(&game_id, &late), 1_201);
+    assert_compact(&env, &client, &game_id);
+}
+
+#[test]
+fn test_drained_shards_are_merged() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let game_id = symbol_short!("daily");
+
+    env.mock_all_auths();
+    client.activate_game(&admin, &game_id, &ScoreMode::Latest);
+
+    // Every new score ranks first, so the ranking ends up in two shards
+    let mut players = std::vec::Vec::new();
+    for i in 0..1_200u64 {
+        let player = Address::generate(&env);
+        client.submit_score(&admin, &player, &game_id, &(10_000 + i));
+        players.push(player);
+    }
+    assert_eq!(rank_index(&env, &client, &game_id).shards.len(), 2);
+
+    // Move the players ranked 301 to 700 to the bottom, draining the first
+    // shard until both fit in one
+    for (j, player) in players[500..900].iter().rev().enumerate() {
+        client.submit_score(&admin, player, &game_id, &(1_000 - j as u64));
+    }
+    assert_eq!(rank_index(&env, &client, &game_id).shards.len(), 1);
+    assert_compact(&env, &client, &game_id);
+    assert_eq!(client.player_count(&game_id), 1_200);
+    assert_eq!(client.player_rank(&game_id, &players[1_199]), 1);
+    assert_eq!(client.player_rank(&game_id, &players[900]), 300);
+    assert_eq!(client.player_rank(&game_id, &players[499]), 301);
+    assert_eq!(client.player_rank(&game_id, &players[899]), 801);
+    assert_eq!(client.player_rank(&game_id, &players[500]), 1_200);
+}
+
+#[test]
+fn test_prune_closed_season_ranking() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let game_id = symbol_short!("game1");
+
+    env.mock_all_auths();
+    client.set_game_active(&admin, &game_id, &true);
+    for i in 0..150u64 {
+        client.submit_score(&admin, &Address::generate(&env), &game_id, &(i + 1));
+    }
+    let index = rank_index(&env, &client, &game_id);
+    assert_eq!(index.shards.get(0).unwrap().segments, 4);
+
+    // Only closed seasons can be pruned
+    let result = client.try_prune_season(&game_id, &1, &1);
+    assert_eq!(result, Err(Ok(Error::SeasonNotFound)));
+    client.close_season(&admin, &game_id);
+    let result = client.try_prune_season(&game_id, &1, &(MAX_PRUNE_BATCH + 1));
+    assert_eq!(result, Err(Ok(Error::InvalidLimit)));
+
+    let remaining = client.prune_season(&game_id, &1, &3);
+    assert!(remaining > 0 && remaining < 150);
+    assert_eq!(client.prune_season(&game_id, &1, &3), 0);
+    env.as_contract(&client.address, || {
+        let storage = env.storage().persistent();
+        assert!(!storage.has(&DataKey::RankIndex(game_id.clone(), 1)));
+        for id in 0..index.next_id {
+            assert!(!storage.has(&DataKey::RankShard(game_id.clone(), 1, id)));
+            assert!(!storage.has(&DataKey::RankSegment(game_id.clone(), 1, id)));
+        }
+    });
+    assert_eq!(
+        client
+            .season_top_players(&game_id, &1, &1)
+            .get(0)
+            .unwrap()
+            .score,
+        150
+    );
+}
+
+#[test]
+fn test_ties_broken_by_earliest_achievement() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let game_id = symbol_short!("daily");
+
+    env.mock_all_auths();
+    client.activate_game(&admin, &game_id, &ScoreMode::Latest);
+
+    let p1 = Address::generate(&env);
+    let p2 = Address::generate(&env);
+
+    env.ledger().with_mut(|l| {
+        l.sequence_number = 10;
+        l.timestamp = 1_000;
+    });
+    client.submit_score(&admin, &p1, &game_id, &100);
+    env.ledger().with_mut(|l| {
+        l.sequence_number = 11;
+        l.timestamp = 1_005;
+    });
+    client.submit_score(&admin, &p2, &game_id, &100);
+
+    let top = client.top_players(&game_id, &10);
+    let first = top.get(0).unwrap();
+    assert_eq!(first.player, p1);
+    assert_eq!(first.ledger, 10);
+    assert_eq!(first.timestamp, 1_000);
+    assert_eq!(top.get(1).unwrap().ledger, 11);
+
+    // Re-reaching the same score later gives up the earlier achievement
+    env.ledger().with_mut(|l| {
+        l.sequence_number = 12;
+        l.timestamp = 1_010;
+    });
+    client.submit_score(&admin, &p1, &game_id, &100);
+    assert_eq!(client.player_rank(&game_id, &p2), 1);
+    assert_eq!(client.player_rank(&game_id, &p1), 2);
+    assert_eq!(client.top_players(&game_id, &10).get(1).unwrap().ledger, 12);
+}
+
+#[test]
+fn test_global_points_follow_game_ranks() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let chess = symbol_short!("chess");
+    let race = symbol_short!("race");
+
+    env.mock_all_auths();
+    client.set_game_active(&admin, &chess, &true);
+    client.activate_game(&admin, &race, &ScoreMode::BestLow);
+    client.set_global_weight(&admin, &chess, &1, &soroban_sdk::vec![&env, 10, 5]);
+    client.set_global_weight(&admin, &race, &2, &soroban_sdk::vec![&env, 10, 5, 1]);
+
+    let p1 = Address::generate(&env);
+    let p2 = Address::generate(&env);
+    let p3 = Address::generate(&env);
+
+    client.submit_score(&admin, &p1, &chess, &100);
+    client.submit_score(&admin, &p2, &chess, &200);
+    assert_eq!(client.get_global_points(&p2), 10);
+    assert_eq!(client.get_global_points(&p1), 5);
+
+    // A new leader pushes everyone down a rank, and p1 out of the table
+    client.submit_score(&admin, &p3, &chess, &300);
+    assert_eq!(client.get_global_points(&p3), 10);
+    assert_eq!(client.get_global_points(&p2), 5);
+    assert_eq!(client.get_global_points(&p1), 0);
+    assert_eq!(client.global_rank(&p1), 0);
+    assert_eq!(client.global_player_count(), 2);
+
+    // Fastest race time, weighted double
+    client.submit_score(&admin, &p1, &race, &40);
+    client.submit_score(&admin, &p2, &race, &50);
+    assert_eq!(client.get_global_points(&p1), 20);
+    assert_eq!(client.get_global_points(&p2), 15);
+
+    let top = client.global_top_players(&10);
+    assert_eq!(top.get(0).unwrap().player, p1);
+    assert_eq!(top.get(1).unwrap().player, p2);
+    assert_eq!(top.get(2).unwrap().player, p3);
+    assert_eq!(client.global_rank(&p3), 3);
+    assert_eq!(client.global_players_between(&2, &5).len(), 1);
+}
+
+#[test]
+fn test_global_weight_changes_and_season_close() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let game_id = symbol_short!("game1");
+
+    env.mock_all_auths();
+    client.set_game_active(&admin, &game_id, &true);
+
+    let p1 = Address::generate(&env);
+    let p2 = Address::generate(&env);
+    client.submit_score(&admin, &p1, &game_id, &200);
+    client.submit_score(&admin, &p2, &game_id, &100);
+    assert_eq!(client.get_global_points(&p1), 0);
+
+    let result = client.try_set_global_weight(&admin, &game_id, &1, &soroban_sdk::vec![&env, 1, 2]);
+    assert_eq!(result, Err(Ok(Error::InvalidPointsTable)));
+
+    // Configuring a game scores its existing ranks
+    client.set_global_weight(&admin, &game_id, &3, &soroban_sdk::vec![&env, 10, 5]);
+    assert_eq!(client.get_global_points(&p1), 30);
+    assert_eq!(client.get_global_points(&p2), 15);
+
+    // Shrinking the table drops the ranks past its end
+    client.set_global_weight(&admin, &game_id, &1, &soroban_sdk::vec![&env, 10]);
+    assert_eq!(client.get_global_points(&p1), 10);
+    assert_eq!(client.get_global_points(&p2), 0);
+
+    // A closed season no longer earns points
+    client.close_season(&admin, &game_id);
+    assert_eq!(client.get_global_points(&p1), 0);
+    assert_eq!(client.global_player_count(), 0);
+}
+
+#[test]
+fn test_every_player_with_points_has_a_global_rank() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    env.mock_all_auths();
+
+    // 55 games each give points to their top 20, more than a single
+    // thousand-player ranking would hold
+    let mut points_per_rank = Vec::new(&env);
+    for points in (1..=MAX_POINTS_RANKS).rev() {
+        points_per_rank.push_back(points);
+    }
+    let mut players = std::vec::Vec::new();
+    for g in 0..55u32 {
+        let game_id = Symbol::new(&env, &std::format!("game{g}"));
+        client.set_game_active(&admin, &game_id, &true);
+        client.set_global_weight(&admin, &game_id, &1, &points_per_rank);
+        for r in 0..MAX_POINTS_RANKS {
+            let player = Address::generate(&env);
+            client.submit_score(&admin, &player, &game_id, &(100 - r as u64));
+            players.push((player, g, r));
+        }
+    }
+    assert_eq!(client.global_player_count(), 1_100);
+
+    // Equal points keep the order they were earned in
+    for (player, g, r) in players.iter() {
+        assert_eq!(
+            client.get_global_points(player),
+            (MAX_POINTS_RANKS - r) as u64
+        );
+        assert_eq!(client.global_rank(player), 55 * r + g + 1);
+    }
+    let page = client.global_players_between(&1_050, &100);
+    assert_eq!(page.len(), 50);
+    assert_eq!(page.get(49).unwrap().player, players[1_099].0);
+}
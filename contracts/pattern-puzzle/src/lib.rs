@@ -5,22 +5,43 @@
 //!
 //! ## Game Flow
 //! 1. Admin calls `create_puzzle` with SHA-256(correct_pattern) as commitment.
-//! 2. Players call `submit_solution` with their guesses and pay the entry fee.
+//! 2. Players call `submit_solution` with their guesses; the entry fee is debited
+//!    through the balance contract and escrowed by this contract.
 //! 3. Admin calls `resolve_round` with the plaintext answer (verified against hash).
-//! 4. Winning players call `claim_reward` to receive their proportional share.
+//!    With winners, their payouts are reserved in the prize pool; without, the
+//!    pot is handled by the rollover policy.
+//! 4. Winning players call `claim_reward` to receive their share, paid out
+//!    through the prize pool.
+//!
+//! ## Funds
+//! Every entry fee stays in this contract's escrow in the balance contract until
+//! the round is resolved. A pot rolled over to a later round is part of the
+//! same escrow, so carried-in funds are paid exactly like new entry fees.
+//! What the winners are owed is reserved in the prize pool under the round id,
+//! and the escrow covering it moves to the admin's balance, since the house
+//! funds the pool. Each claim pays out of that reservation, so this contract
+//! must be the prize pool's admin.
+//!
+//! ## Scoring
+//! Rounds opened with `create_puzzle` only reward byte-for-byte matches. Rounds
+//! opened with `create_scored_puzzle` score every guess Mastermind style (see
+//! `score_pattern`) and pay each `ScoreTier` its share of the pot. Their
+//! pattern length is fixed at creation and every guess must match it, so a
+//! guess cannot list every symbol to collect misplaced points.
 //!
 //! ## Storage Strategy
 //! - `instance()` storage: contract-level config only (Admin, PrizePoolContract,
-//!   BalanceContract). Small, fixed size, bounded.
+//!   BalanceContract, RolloverPolicy). Small, fixed size, bounded.
 //! - `persistent()` storage: all per-round and per-player data (Round, Players,
-//!   Submission, IsWinner, Claimed). Each key is an independent ledger entry
-//!   with constant-cost access and an explicit TTL extended on every write.
+//!   Submission, Score, Tier, IsWinner, Claimed) and the carried-over pot. Each key is an
+//!   independent ledger entry with constant-cost access and an explicit TTL
+//!   extended on every write.
 #![no_std]
 #![allow(unexpected_cfgs)]
 
 use soroban_sdk::{
-    contract, contracterror, contractevent, contractimpl, contracttype, Address, Bytes, BytesN,
-    Env, Vec,
+    contract, contractclient, contracterror, contractevent, contractimpl, contracttype,
+    symbol_short, Address, Bytes, BytesN, Env, Symbol, Vec,
 };
 
 // ---------------------------------------------------------------------------
@@ -35,6 +56,35 @@ pub const MAX_PLAYERS_PER_ROUND: u32 = 500;
 /// Extended on every write so active round data never expires mid-game.
 pub const PERSISTENT_BUMP_LEDGERS: u32 = 518_400;
 
+/// Maximum number of payout tiers in a scored round.
+pub const MAX_SCORE_TIERS: u32 = 8;
+
+/// Points for a symbol in the correct position.
+pub const EXACT_POINTS: u32 = 2;
+
+/// Points for a correct symbol in the wrong position.
+pub const MISPLACED_POINTS: u32 = 1;
+
+/// Tier shares are expressed in basis points of the pot.
+pub const BPS_DENOMINATOR: i128 = 10_000;
+
+// ---------------------------------------------------------------------------
+// External contract clients
+// ---------------------------------------------------------------------------
+
+#[contractclient(name = "PrizePoolClient")]
+pub trait PrizePoolContract {
+    fn reserve(env: Env, admin: Address, game_id: u64, amount: i128);
+    fn payout(env: Env, admin: Address, to: Address, game_id: u64, amount: i128);
+}
+
+#[contractclient(name = "BalanceClient")]
+pub trait UserBalanceContract {
+    fn credit(env: Env, game: Address, user: Address, amount: i128, reason: Symbol);
+    fn debit(env: Env, game: Address, user: Address, amount: i128, reason: Symbol);
+    fn balance_of(env: Env, user: Address) -> i128;
+}
+
 // ---------------------------------------------------------------------------
 // Error Types
 // ---------------------------------------------------------------------------
@@ -56,6 +106,50 @@ pub enum Error {
     Overflow           = 11,
     CommitmentMismatch = 12,
     RoundFull          = 13,
+    InvalidTiers       = 14,
+    InvalidPattern     = 15,
+}
+
+/// What happens to the pot of a round that resolves with no winners.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RolloverPolicy {
+    /// Carry the whole pot into the next puzzle created.
+    Carry  = 0,
+    /// Refund each entry fee to its player; any carried-in pot carries on.
+    Refund = 1,
+}
+
+/// A payout tier of a scored round.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScoreTier {
+    /// Minimum points (see `score_points`) a guess needs to land in this tier.
+    pub min_points: u32,
+    /// Share of the pot split evenly among the tier's players.
+    pub share_bps:  u32,
+}
+
+/// How submissions are judged when a round resolves.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum ScoringMode {
+    /// Only byte-for-byte matches win; winners split the pot equally.
+    Exact,
+    /// Guesses are scored with `score_pattern` and paid by tier. Tiers are
+    /// strictly descending by `min_points`; a guess falls in the first tier
+    /// whose `min_points` it reaches.
+    Mastermind(Vec<ScoreTier>),
+}
+
+/// Mastermind-style feedback for a guess.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PatternScore {
+    /// Symbols in the correct position.
+    pub exact:     u32,
+    /// Correct symbols in the wrong position, each answer symbol counted once.
+    pub misplaced: u32,
 }
 
 // ---------------------------------------------------------------------------
@@ -84,10 +178,23 @@ pub struct RoundData {
     pub correct_pattern:    Bytes,
     /// Amount each player must wager to enter (0 = free round).
     pub entry_fee:          i128,
-    /// Accumulated from all submitted entry fees.
+    /// Accumulated from all submitted entry fees plus `carried_in`.
     pub total_pot:          i128,
     /// Current number of submissions; enforces MAX_PLAYERS_PER_ROUND.
     pub player_count:       u32,
+    /// Pot rolled over from earlier rounds without winners.
+    pub carried_in:         i128,
+    /// Set during resolve_round: `total_pot / winner_count` in exact rounds,
+    /// otherwise 0.
+    pub payout_per_winner:  i128,
+    /// How submissions are judged at resolve.
+    pub scoring:            ScoringMode,
+    /// Length every guess and the revealed pattern must have in scored rounds;
+    /// 0 (any length) in exact rounds.
+    pub pattern_length:     u32,
+    /// Set during resolve_round: the payout of each winner in each tier
+    /// (a single entry for exact rounds).
+    pub tier_payouts:       Vec<i128>,
 }
 
 /// A player's committed guess for a round.
@@ -100,10 +207,10 @@ pub struct PlayerSubmission {
 
 /// Storage key discriminants.
 ///
-/// Instance keys (Admin, PrizePoolContract, BalanceContract): contract config,
-/// small fixed set, stored in a single ledger entry.
+/// Instance keys (Admin, PrizePoolContract, BalanceContract, RolloverPolicy):
+/// contract config, small fixed set, stored in a single ledger entry.
 ///
-/// Persistent keys (Round, Players, Submission, IsWinner, Claimed): per-round
+/// Persistent keys (Round, Players, Submission, Score, Tier, IsWinner, Claimed): per-round
 /// and per-player data, each stored as an independent ledger entry with its own
 /// TTL so reads and writes are O(1) and cost does not scale with contract state.
 #[contracttype]
@@ -112,13 +219,22 @@ pub enum DataKey {
     Admin,
     PrizePoolContract,
     BalanceContract,
+    /// RolloverPolicy applied to rounds with no winners (default `Carry`).
+    RolloverPolicy,
     // --- persistent() keys: round and player data ---
+    /// Pot waiting to be added to the next puzzle created.
+    RolloverPot,
     /// RoundData keyed by round_id.
     Round(u32),
     /// Vec<Address> of all submitters for a round, used during resolve_round.
     Players(u32),
     /// PlayerSubmission keyed by (round_id, player).
     Submission(u32, Address),
+    /// PatternScore keyed by (round_id, player), set during resolve_round of
+    /// scored rounds.
+    Score(u32, Address),
+    /// Index of the tier a winner of a scored round landed in.
+    Tier(u32, Address),
     /// Set to `true` during resolve_round for each correct submitter.
     IsWinner(u32, Address),
     /// Set to `true` during claim_reward to prevent double-claims.
@@ -134,6 +250,7 @@ pub struct RoundCreated {
     #[topic]
     pub round_id: u32,
     pub pattern_commitment: BytesN<32>,
+    pub carried_in: i128,
 }
 
 #[contractevent]
@@ -151,6 +268,13 @@ pub struct RoundResolved {
     pub round_id: u32,
     pub correct_pattern: Bytes,
     pub winner_count: u32,
+    pub payout_per_winner: i128,
+    pub tier_payouts: Vec<i128>,
+    /// Added to the rollover pot: the whole pot when carried, or the part not
+    /// owed to winners.
+    pub rolled_over: i128,
+    /// Entry fees returned to players under `RolloverPolicy::Refund`.
+    pub refunded: i128,
 }
 
 #[contractevent]
@@ -162,6 +286,11 @@ pub struct RewardClaimed {
     pub amount: i128,
 }
 
+#[contractevent]
+pub struct RolloverPolicySet {
+    pub policy: RolloverPolicy,
+}
+
 // ---------------------------------------------------------------------------
 // Contract
 // ---------------------------------------------------------------------------
@@ -179,7 +308,8 @@ impl PatternPuzzle {
     ///
     /// Stores the admin, prize pool contract address, and balance contract
     /// address in instance storage. Subsequent calls are rejected with
-    /// `NotAuthorized`.
+    /// `NotAuthorized`. This contract must be the admin of
+    /// `prize_pool_contract`, which pays out every reward.
     pub fn init(
         env:                 Env,
         admin:               Address,
@@ -199,6 +329,23 @@ impl PatternPuzzle {
         Ok(())
     }
 
+    // -----------------------------------------------------------------------
+    // set_rollover_policy
+    // -----------------------------------------------------------------------
+
+    /// Choose what happens to the pot of rounds resolved without winners.
+    /// Admin only. Applies to rounds resolved afterwards.
+    pub fn set_rollover_policy(
+        env:    Env,
+        admin:  Address,
+        policy: RolloverPolicy,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        env.storage().instance().set(&DataKey::RolloverPolicy, &policy);
+        RolloverPolicySet { policy }.publish(&env);
+        Ok(())
+    }
+
     // -----------------------------------------------------------------------
     // create_puzzle
     // -----------------------------------------------------------------------
@@ -207,6 +354,7 @@ impl PatternPuzzle {
     ///
     /// `pattern_commitment` is `SHA-256(correct_pattern_bytes)` computed off-chain.
     /// `entry_fee` is the token amount each player must wager (0 for free rounds).
+    /// Any pot rolled over from rounds without winners is added to this round.
     /// Round data is stored in persistent storage with a 30-day TTL.
     pub fn create_puzzle(
         env:                Env,
@@ -215,52 +363,46 @@ impl PatternPuzzle {
         pattern_commitment: BytesN<32>,
         entry_fee:          i128,
     ) -> Result<(), Error> {
-        let stored_admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(Error::NotInitialized)?;
-
-        admin.require_auth();
-        if admin != stored_admin {
-            return Err(Error::NotAuthorized);
-        }
+        open_round(&env, &admin, round_id, pattern_commitment, 0, entry_fee, ScoringMode::Exact)
+    }
 
-        if env.storage().persistent().has(&DataKey::Round(round_id)) {
-            return Err(Error::RoundAlreadyExists);
-        }
+    // -----------------------------------------------------------------------
+    // create_scored_puzzle
+    // -----------------------------------------------------------------------
 
-        if entry_fee < 0 {
-            return Err(Error::InvalidAmount);
+    /// Open a puzzle round scored Mastermind style. Admin only.
+    ///
+    /// Behaves like `create_puzzle`, but at resolve every guess is scored with
+    /// `score_pattern` and each of `tiers` pays `share_bps` of the pot, split
+    /// evenly among the players in it. Tiers must be strictly descending by a
+    /// non-zero `min_points`, at most `MAX_SCORE_TIERS` long, with shares
+    /// totalling at most 10000 bps.
+    ///
+    /// `pattern_length` is the non-zero length of the committed pattern. Guesses
+    /// of any other length are rejected, as a long guess holding every symbol
+    /// would otherwise score misplaced points without any knowledge.
+    pub fn create_scored_puzzle(
+        env:                Env,
+        admin:              Address,
+        round_id:           u32,
+        pattern_commitment: BytesN<32>,
+        pattern_length:     u32,
+        entry_fee:          i128,
+        tiers:              Vec<ScoreTier>,
+    ) -> Result<(), Error> {
+        validate_tiers(&tiers)?;
+        if pattern_length == 0 {
+            return Err(Error::InvalidPattern);
         }
-
-        let round = RoundData {
-            pattern_commitment: pattern_commitment.clone(),
-            status:             RoundStatus::Open,
-            winner_count:       0,
-            correct_pattern:    Bytes::new(&env),
+        open_round(
+            &env,
+            &admin,
+            round_id,
+            pattern_commitment,
+            pattern_length,
             entry_fee,
-            total_pot:          0,
-            player_count:       0,
-        };
-
-        env.storage().persistent().set(&DataKey::Round(round_id), &round);
-        env.storage().persistent().extend_ttl(
-            &DataKey::Round(round_id),
-            PERSISTENT_BUMP_LEDGERS,
-            PERSISTENT_BUMP_LEDGERS,
-        );
-
-        env.storage().persistent().set(&DataKey::Players(round_id), &Vec::<Address>::new(&env));
-        env.storage().persistent().extend_ttl(
-            &DataKey::Players(round_id),
-            PERSISTENT_BUMP_LEDGERS,
-            PERSISTENT_BUMP_LEDGERS,
-        );
-
-        RoundCreated { round_id, pattern_commitment }.publish(&env);
-
-        Ok(())
+            ScoringMode::Mastermind(tiers),
+        )
     }
 
     // -----------------------------------------------------------------------
@@ -271,8 +413,10 @@ impl PatternPuzzle {
     ///
     /// Each player may submit exactly once per round, up to `MAX_PLAYERS_PER_ROUND`
     /// total. The `solution` bytes are stored and compared byte-for-byte against the
-    /// revealed pattern during `resolve_round`. Entry fee accounting is updated here;
-    /// actual token transfer should invoke the balance contract (see TODO below).
+    /// revealed pattern during `resolve_round`, or scored against it in scored
+    /// rounds, where it must be exactly `pattern_length` bytes long. The entry
+    /// fee is debited from the player through the balance contract and held by
+    /// this contract until the round resolves.
     pub fn submit_solution(
         env:      Env,
         player:   Address,
@@ -307,6 +451,10 @@ impl PatternPuzzle {
             return Err(Error::InvalidAmount);
         }
 
+        if round.pattern_length > 0 && solution.len() != round.pattern_length {
+            return Err(Error::InvalidPattern);
+        }
+
         let submission = PlayerSubmission {
             solution: solution.clone(),
             wager:    round.entry_fee,
@@ -320,8 +468,17 @@ impl PatternPuzzle {
             PERSISTENT_BUMP_LEDGERS,
         );
 
-        // TODO: Invoke balance_contract to transfer entry_fee from player to this contract.
-        // balance_contract_client::new(&env, &balance_contract).transfer(&player, &env.current_contract_address(), &round.entry_fee);
+        if round.entry_fee > 0 {
+            let balance = balance_client(&env)?;
+            let contract_addr = env.current_contract_address();
+            balance.debit(&contract_addr, &player, &round.entry_fee, &symbol_short!("entry"));
+            balance.credit(
+                &contract_addr,
+                &contract_addr,
+                &round.entry_fee,
+                &symbol_short!("entry"),
+            );
+        }
 
         let mut players: Vec<Address> = env
             .storage()
@@ -367,23 +524,21 @@ impl PatternPuzzle {
     /// Admin only. `correct_pattern` must satisfy `SHA-256(correct_pattern) ==
     /// stored pattern_commitment`. Iterates all submissions (bounded by
     /// `MAX_PLAYERS_PER_ROUND`) to mark winners and compute `winner_count`.
-    /// Transitions the round to `Resolved`.
+    /// In scored rounds every guess is scored and winners are the players that
+    /// reach a tier. Transitions the round to `Resolved`.
+    ///
+    /// With winners, the sum of their payouts is reserved in the prize pool and
+    /// the escrow covering it is paid to the admin's balance; the rest of the
+    /// pot (division remainders and shares of empty tiers) is rolled over.
+    /// Without winners, the pot is carried into the next puzzle or refunded,
+    /// per the `RolloverPolicy`. Rolled-over funds remain in escrow.
     pub fn resolve_round(
         env:             Env,
         admin:           Address,
         round_id:        u32,
         correct_pattern: Bytes,
     ) -> Result<(), Error> {
-        let stored_admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(Error::NotInitialized)?;
-
-        admin.require_auth();
-        if admin != stored_admin {
-            return Err(Error::NotAuthorized);
-        }
+        require_admin(&env, &admin)?;
 
         let mut round: RoundData = env
             .storage()
@@ -401,6 +556,9 @@ impl PatternPuzzle {
         if revealed_hash != round.pattern_commitment {
             return Err(Error::CommitmentMismatch);
         }
+        if round.pattern_length > 0 && correct_pattern.len() != round.pattern_length {
+            return Err(Error::InvalidPattern);
+        }
 
         let players: Vec<Address> = env
             .storage()
@@ -408,32 +566,116 @@ impl PatternPuzzle {
             .get(&DataKey::Players(round_id))
             .unwrap_or_else(|| Vec::new(&env));
 
+        // Exact rounds behave as a single tier owning the whole pot.
+        let tier_shares: Vec<u32> = match &round.scoring {
+            ScoringMode::Exact => Vec::from_array(&env, [BPS_DENOMINATOR as u32]),
+            ScoringMode::Mastermind(tiers) => {
+                let mut shares = Vec::new(&env);
+                for tier in tiers.iter() {
+                    shares.push_back(tier.share_bps);
+                }
+                shares
+            }
+        };
+        let mut tier_counts: Vec<u32> = Vec::new(&env);
+        for _ in 0..tier_shares.len() {
+            tier_counts.push_back(0);
+        }
         let mut winner_count: u32 = 0;
 
         // Bounded by MAX_PLAYERS_PER_ROUND enforced in submit_solution.
         for player in players.iter() {
-            if let Some(submission) = env
+            let Some(submission) = env
                 .storage()
                 .persistent()
                 .get::<DataKey, PlayerSubmission>(&DataKey::Submission(round_id, player.clone()))
-            {
-                if submission.solution == correct_pattern {
-                    env.storage()
-                        .persistent()
-                        .set(&DataKey::IsWinner(round_id, player.clone()), &true);
-                    env.storage().persistent().extend_ttl(
-                        &DataKey::IsWinner(round_id, player.clone()),
-                        PERSISTENT_BUMP_LEDGERS,
-                        PERSISTENT_BUMP_LEDGERS,
-                    );
-                    winner_count = winner_count.checked_add(1).ok_or(Error::Overflow)?;
+            else {
+                continue;
+            };
+
+            let tier = match &round.scoring {
+                ScoringMode::Exact => (submission.solution == correct_pattern).then_some(0),
+                ScoringMode::Mastermind(tiers) => {
+                    let score = score_pattern(&submission.solution, &correct_pattern);
+                    set_player_entry(&env, DataKey::Score(round_id, player.clone()), &score);
+                    let tier = tier_for(tiers, score_points(&score));
+                    if let Some(tier) = tier {
+                        set_player_entry(&env, DataKey::Tier(round_id, player.clone()), &tier);
+                    }
+                    tier
                 }
+            };
+            let Some(tier) = tier else {
+                continue;
+            };
+
+            set_player_entry(&env, DataKey::IsWinner(round_id, player.clone()), &true);
+            let count = tier_counts.get(tier).unwrap_or(0);
+            tier_counts.set(tier, count.checked_add(1).ok_or(Error::Overflow)?);
+            winner_count = winner_count.checked_add(1).ok_or(Error::Overflow)?;
+        }
+
+        let mut tier_payouts: Vec<i128> = Vec::new(&env);
+        let (rolled_over, refunded) = if winner_count > 0 {
+            let mut owed: i128 = 0;
+            for (share_bps, count) in tier_shares.iter().zip(tier_counts.iter()) {
+                let payout = if count > 0 {
+                    round
+                        .total_pot
+                        .checked_mul(share_bps as i128)
+                        .and_then(|share| share.checked_div(BPS_DENOMINATOR))
+                        .and_then(|share| share.checked_div(count as i128))
+                        .ok_or(Error::Overflow)?
+                } else {
+                    0
+                };
+                let tier_total = payout.checked_mul(count as i128).ok_or(Error::Overflow)?;
+                owed = owed.checked_add(tier_total).ok_or(Error::Overflow)?;
+                tier_payouts.push_back(payout);
+            }
+            if owed > 0 {
+                let contract_addr = env.current_contract_address();
+                prize_pool_client(&env)?.reserve(&contract_addr, &u64::from(round_id), &owed);
+                let balance = balance_client(&env)?;
+                balance.debit(&contract_addr, &contract_addr, &owed, &symbol_short!("pool"));
+                balance.credit(&contract_addr, &admin, &owed, &symbol_short!("pool"));
             }
+            let remainder = round.total_pot.checked_sub(owed).ok_or(Error::Overflow)?;
+            (remainder, 0)
+        } else {
+            let policy: RolloverPolicy = env
+                .storage()
+                .instance()
+                .get(&DataKey::RolloverPolicy)
+                .unwrap_or(RolloverPolicy::Carry);
+            match policy {
+                RolloverPolicy::Carry => (round.total_pot, 0),
+                RolloverPolicy::Refund => {
+                    let refunded = refund_entries(&env, round_id, &players)?;
+                    (round.carried_in, refunded)
+                }
+            }
+        };
+
+        if rolled_over > 0 {
+            let pot: i128 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::RolloverPot)
+                .unwrap_or(0);
+            set_rollover_pot(&env, pot.checked_add(rolled_over).ok_or(Error::Overflow)?);
         }
 
-        round.status          = RoundStatus::Resolved;
-        round.correct_pattern = correct_pattern.clone();
-        round.winner_count    = winner_count;
+        let payout_per_winner = match round.scoring {
+            ScoringMode::Exact => tier_payouts.get(0).unwrap_or(0),
+            ScoringMode::Mastermind(_) => 0,
+        };
+
+        round.status            = RoundStatus::Resolved;
+        round.correct_pattern   = correct_pattern.clone();
+        round.winner_count      = winner_count;
+        round.payout_per_winner = payout_per_winner;
+        round.tier_payouts      = tier_payouts.clone();
         env.storage().persistent().set(&DataKey::Round(round_id), &round);
         env.storage().persistent().extend_ttl(
             &DataKey::Round(round_id),
@@ -441,7 +683,16 @@ impl PatternPuzzle {
             PERSISTENT_BUMP_LEDGERS,
         );
 
-        RoundResolved { round_id, correct_pattern, winner_count }.publish(&env);
+        RoundResolved {
+            round_id,
+            correct_pattern,
+            winner_count,
+            payout_per_winner,
+            tier_payouts,
+            rolled_over,
+            refunded,
+        }
+        .publish(&env);
 
         Ok(())
     }
@@ -450,11 +701,12 @@ impl PatternPuzzle {
     // claim_reward
     // -----------------------------------------------------------------------
 
-    /// Claim the proportional reward share for a winning submission.
+    /// Claim the reward share for a winning submission.
     ///
-    /// Returns the reward amount (`total_pot / winner_count`). The `Claimed` flag
-    /// is set before any external call to preserve reentrancy safety. Actual token
-    /// transfer should invoke the prize pool contract (see TODO below).
+    /// Returns the reward amount (`total_pot / winner_count`, or the payout of
+    /// the player's tier in scored rounds), paid by the prize pool out of the
+    /// round's reservation. The `Claimed` flag is set before any external call
+    /// to preserve reentrancy safety.
     pub fn claim_reward(
         env:      Env,
         player:   Address,
@@ -494,10 +746,15 @@ impl PatternPuzzle {
             return Err(Error::NoRewardAvailable);
         }
 
-        let reward: i128 = round
-            .total_pot
-            .checked_div(round.winner_count as i128)
-            .ok_or(Error::Overflow)?;
+        let tier: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Tier(round_id, player.clone()))
+            .unwrap_or(0);
+        let reward: i128 = round.tier_payouts.get(tier).unwrap_or(0);
+        if reward <= 0 {
+            return Err(Error::NoRewardAvailable);
+        }
 
         // Mark claimed before any external call (reentrancy safety).
         env.storage()
@@ -509,8 +766,8 @@ impl PatternPuzzle {
             PERSISTENT_BUMP_LEDGERS,
         );
 
-        // TODO: Invoke prize_pool_contract to transfer `reward` tokens to `player`.
-        // prize_pool_contract_client::new(&env, &prize_pool_contract).payout(&player, &reward);
+        let contract_addr = env.current_contract_address();
+        prize_pool_client(&env)?.payout(&contract_addr, &player, &u64::from(round_id), &reward);
 
         RewardClaimed { player, round_id, amount: reward }.publish(&env);
 
@@ -533,6 +790,13 @@ impl PatternPuzzle {
             .get(&DataKey::Submission(round_id, player))
     }
 
+    /// Returns a player's score in a resolved scored round, or `None`.
+    pub fn get_score(env: Env, round_id: u32, player: Address) -> Option<PatternScore> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Score(round_id, player))
+    }
+
     /// Returns `true` if the player has already claimed their reward for a round.
     pub fn has_claimed(env: Env, round_id: u32, player: Address) -> bool {
         env.storage()
@@ -540,6 +804,243 @@ impl PatternPuzzle {
             .get(&DataKey::Claimed(round_id, player))
             .unwrap_or(false)
     }
+
+    /// Returns the pot waiting to be added to the next puzzle created.
+    pub fn get_rollover_pot(env: Env) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RolloverPot)
+            .unwrap_or(0)
+    }
+
+    /// Returns the policy applied to rounds resolved without winners.
+    pub fn get_rollover_policy(env: Env) -> RolloverPolicy {
+        env.storage()
+            .instance()
+            .get(&DataKey::RolloverPolicy)
+            .unwrap_or(RolloverPolicy::Carry)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Scoring
+// ---------------------------------------------------------------------------
+
+/// Score `guess` against `answer` Mastermind style.
+///
+/// Pass 1 counts positions where both patterns hold the same symbol (`exact`).
+/// Pass 2 counts, over the remaining positions of both patterns, symbols the
+/// guess shares with the answer (`misplaced`); each answer symbol is consumed
+/// at most once, so duplicates in the guess are not over-credited. Patterns of
+/// different lengths are compared over their common prefix in pass 1.
+pub fn score_pattern(guess: &Bytes, answer: &Bytes) -> PatternScore {
+    let mut guess_left = [0u32; 256];
+    let mut answer_left = [0u32; 256];
+    let mut exact: u32 = 0;
+
+    // Pass 1: exact matches; tally every other symbol per side.
+    for i in 0..guess.len().max(answer.len()) {
+        match (guess.get(i), answer.get(i)) {
+            (Some(g), Some(a)) if g == a => exact += 1,
+            (g, a) => {
+                if let Some(g) = g {
+                    guess_left[g as usize] += 1;
+                }
+                if let Some(a) = a {
+                    answer_left[a as usize] += 1;
+                }
+            }
+        }
+    }
+
+    // Pass 2: right symbol, wrong position.
+    let misplaced = guess_left
+        .iter()
+        .zip(answer_left.iter())
+        .map(|(g, a)| *g.min(a))
+        .sum();
+
+    PatternScore { exact, misplaced }
+}
+
+/// Points used to place a score in a `ScoreTier`.
+pub fn score_points(score: &PatternScore) -> u32 {
+    score
+        .exact
+        .saturating_mul(EXACT_POINTS)
+        .saturating_add(score.misplaced.saturating_mul(MISPLACED_POINTS))
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// Store and open a new round. Shared by `create_puzzle` and `create_scored_puzzle`.
+fn open_round(
+    env:                &Env,
+    admin:              &Address,
+    round_id:           u32,
+    pattern_commitment: BytesN<32>,
+    pattern_length:     u32,
+    entry_fee:          i128,
+    scoring:            ScoringMode,
+) -> Result<(), Error> {
+    require_admin(env, admin)?;
+
+    if env.storage().persistent().has(&DataKey::Round(round_id)) {
+        return Err(Error::RoundAlreadyExists);
+    }
+
+    if entry_fee < 0 {
+        return Err(Error::InvalidAmount);
+    }
+
+    let carried_in: i128 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::RolloverPot)
+        .unwrap_or(0);
+    if carried_in > 0 {
+        set_rollover_pot(env, 0);
+    }
+
+    let round = RoundData {
+        pattern_commitment: pattern_commitment.clone(),
+        status:             RoundStatus::Open,
+        winner_count:       0,
+        correct_pattern:    Bytes::new(env),
+        entry_fee,
+        total_pot:          carried_in,
+        player_count:       0,
+        carried_in,
+        payout_per_winner:  0,
+        scoring,
+        pattern_length,
+        tier_payouts:       Vec::new(env),
+    };
+
+    env.storage().persistent().set(&DataKey::Round(round_id), &round);
+    env.storage().persistent().extend_ttl(
+        &DataKey::Round(round_id),
+        PERSISTENT_BUMP_LEDGERS,
+        PERSISTENT_BUMP_LEDGERS,
+    );
+
+    env.storage().persistent().set(&DataKey::Players(round_id), &Vec::<Address>::new(env));
+    env.storage().persistent().extend_ttl(
+        &DataKey::Players(round_id),
+        PERSISTENT_BUMP_LEDGERS,
+        PERSISTENT_BUMP_LEDGERS,
+    );
+
+    RoundCreated { round_id, pattern_commitment, carried_in }.publish(env);
+
+    Ok(())
+}
+
+fn validate_tiers(tiers: &Vec<ScoreTier>) -> Result<(), Error> {
+    if tiers.is_empty() || tiers.len() > MAX_SCORE_TIERS {
+        return Err(Error::InvalidTiers);
+    }
+    let mut total_bps: u32 = 0;
+    let mut previous_min: Option<u32> = None;
+    for tier in tiers.iter() {
+        if tier.min_points == 0 || tier.share_bps == 0 {
+            return Err(Error::InvalidTiers);
+        }
+        if previous_min.is_some_and(|min| tier.min_points >= min) {
+            return Err(Error::InvalidTiers);
+        }
+        previous_min = Some(tier.min_points);
+        total_bps = total_bps.checked_add(tier.share_bps).ok_or(Error::InvalidTiers)?;
+    }
+    if total_bps as i128 > BPS_DENOMINATOR {
+        return Err(Error::InvalidTiers);
+    }
+    Ok(())
+}
+
+/// Index of the first (highest) tier `points` reaches.
+fn tier_for(tiers: &Vec<ScoreTier>, points: u32) -> Option<u32> {
+    tiers
+        .iter()
+        .position(|tier| points >= tier.min_points)
+        .map(|index| index as u32)
+}
+
+/// Write a per-player round entry and extend its TTL.
+fn set_player_entry<V: soroban_sdk::IntoVal<Env, soroban_sdk::Val>>(
+    env:   &Env,
+    key:   DataKey,
+    value: &V,
+) {
+    env.storage().persistent().set(&key, value);
+    env.storage().persistent().extend_ttl(&key, PERSISTENT_BUMP_LEDGERS, PERSISTENT_BUMP_LEDGERS);
+}
+
+/// Require `admin` to sign and match the stored admin.
+fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+    let stored_admin: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(Error::NotInitialized)?;
+
+    admin.require_auth();
+    if *admin != stored_admin {
+        return Err(Error::NotAuthorized);
+    }
+    Ok(())
+}
+
+fn prize_pool_client(env: &Env) -> Result<PrizePoolClient<'_>, Error> {
+    let address: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::PrizePoolContract)
+        .ok_or(Error::NotInitialized)?;
+    Ok(PrizePoolClient::new(env, &address))
+}
+
+fn balance_client(env: &Env) -> Result<BalanceClient<'_>, Error> {
+    let address: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::BalanceContract)
+        .ok_or(Error::NotInitialized)?;
+    Ok(BalanceClient::new(env, &address))
+}
+
+fn set_rollover_pot(env: &Env, amount: i128) {
+    env.storage().persistent().set(&DataKey::RolloverPot, &amount);
+    env.storage().persistent().extend_ttl(
+        &DataKey::RolloverPot,
+        PERSISTENT_BUMP_LEDGERS,
+        PERSISTENT_BUMP_LEDGERS,
+    );
+}
+
+/// Return every player's escrowed wager. Bounded by MAX_PLAYERS_PER_ROUND.
+fn refund_entries(env: &Env, round_id: u32, players: &Vec<Address>) -> Result<i128, Error> {
+    let balance = balance_client(env)?;
+    let contract_addr = env.current_contract_address();
+    let mut refunded: i128 = 0;
+    for player in players.iter() {
+        let Some(submission) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, PlayerSubmission>(&DataKey::Submission(round_id, player.clone()))
+        else {
+            continue;
+        };
+        if submission.wager <= 0 {
+            continue;
+        }
+        balance.debit(&contract_addr, &contract_addr, &submission.wager, &symbol_short!("refund"));
+        balance.credit(&contract_addr, &player, &submission.wager, &symbol_short!("refund"));
+        refunded = refunded.checked_add(submission.wager).ok_or(Error::Overflow)?;
+    }
+    Ok(refunded)
 }
 
 // ---------------------------------------------------------------------------
@@ -549,7 +1050,90 @@ impl PatternPuzzle {
 #[cfg(test)]
 mod test {
     use super::*;
-    use soroban_sdk::{testutils::Address as _, Bytes, BytesN, Env};
+    use soroban_sdk::{contract, contractimpl, testutils::Address as _, Bytes, BytesN, Env};
+
+    /// Balance stand-in; balances may go negative so tests need not fund players.
+    #[contract]
+    pub struct MockBalance;
+
+    #[contracttype]
+    pub enum BalanceKey {
+        Balance(Address),
+    }
+
+    #[contractimpl]
+    impl MockBalance {
+        pub fn credit(env: Env, _game: Address, user: Address, amount: i128, _reason: Symbol) {
+            let bal = Self::balance_of(env.clone(), user.clone());
+            env.storage()
+                .persistent()
+                .set(&BalanceKey::Balance(user), &(bal + amount));
+        }
+
+        pub fn debit(env: Env, _game: Address, user: Address, amount: i128, _reason: Symbol) {
+            let bal = Self::balance_of(env.clone(), user.clone());
+            env.storage()
+                .persistent()
+                .set(&BalanceKey::Balance(user), &(bal - amount));
+        }
+
+        pub fn balance_of(env: Env, user: Address) -> i128 {
+            env.storage()
+                .persistent()
+                .get(&BalanceKey::Balance(user))
+                .unwrap_or(0)
+        }
+    }
+
+    /// Prize pool stand-in that tracks each game's reservation and what it
+    /// paid to each player. Like the real pool, both calls need the admin's
+    /// signature.
+    #[contract]
+    pub struct MockPrizePool;
+
+    #[contracttype]
+    pub enum PoolKey {
+        Reserved(u64),
+        Paid(Address),
+    }
+
+    #[contractimpl]
+    impl MockPrizePool {
+        pub fn reserve(env: Env, admin: Address, game_id: u64, amount: i128) {
+            admin.require_auth();
+            if Self::reserved(env.clone(), game_id) > 0 {
+                panic!("game already reserved");
+            }
+            env.storage().persistent().set(&PoolKey::Reserved(game_id), &amount);
+        }
+
+        pub fn payout(env: Env, admin: Address, to: Address, game_id: u64, amount: i128) {
+            admin.require_auth();
+            let reserved = Self::reserved(env.clone(), game_id);
+            if amount > reserved {
+                panic!("payout exceeds reservation");
+            }
+            env.storage()
+                .persistent()
+                .set(&PoolKey::Reserved(game_id), &(reserved - amount));
+            let paid = Self::paid(env.clone(), to.clone()) + amount;
+            env.storage().persistent().set(&PoolKey::Paid(to), &paid);
+        }
+
+        pub fn reserved(env: Env, game_id: u64) -> i128 {
+            env.storage()
+                .persistent()
+                .get(&PoolKey::Reserved(game_id))
+                .unwrap_or(0)
+        }
+
+        pub fn paid(env: Env, to: Address) -> i128 {
+            env.storage()
+                .persistent()
+                .get(&PoolKey::Paid(to))
+                .unwrap_or(0)
+        }
+    }
 
     /// Compute SHA-256 of a byte slice using the test environment's crypto host.
     fn sha256_of(env: &Env, data: &[u8]) -> BytesN<32> {
@@ -562,8 +1146,8 @@ mod test {
         let contract_id = env.register(PatternPuzzle, ());
         let client = PatternPuzzleClient::new(env, &contract_id);
         let admin = Address::generate(env);
-        let prize_pool = Address::generate(env);
-        let balance = Address::generate(env);
+        let prize_pool = env.register(MockPrizePool, ());
+        let balance = env.register(MockBalance, ());
 
         env.mock_all_auths();
         client.init(&admin, &prize_pool, &balance);
@@ -845,4 +1429,263 @@ mod test {
         let result = client.try_submit_solution(&overflow_player, &1u32, &solution);
         assert!(result.is_err());
     }
+
+    // ------------------------------------------------------------------
+    // 13. Entry fees are escrowed; winners are paid through the prize pool
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_entry_fees_escrowed_and_paid_through_prize_pool() {
+        let env = Env::default();
+        let (client, admin, prize_pool, balance) = setup(&env);
+        env.mock_all_auths();
+        let pool = MockPrizePoolClient::new(&env, &prize_pool);
+        let ledger = MockBalanceClient::new(&env, &balance);
+
+        let correct = b"TRIANGLE";
+        client.create_puzzle(&admin, &1u32, &sha256_of(&env, correct), &25i128);
+
+        let winner1 = Address::generate(&env);
+        let winner2 = Address::generate(&env);
+        let loser = Address::generate(&env);
+        let correct_bytes = Bytes::from_slice(&env, correct);
+        client.submit_solution(&winner1, &1u32, &correct_bytes);
+        client.submit_solution(&winner2, &1u32, &correct_bytes);
+        client.submit_solution(&loser, &1u32, &Bytes::from_slice(&env, b"SQUARE"));
+
+        assert_eq!(ledger.balance_of(&loser), -25);
+        assert_eq!(ledger.balance_of(&client.address), 75);
+
+        // 75 / 2 = 37 each, reserved in the pool and funded by the house;
+        // the remainder of 1 rolls over and stays in escrow.
+        client.resolve_round(&admin, &1u32, &correct_bytes);
+        assert_eq!(client.get_rollover_pot(), 1);
+        assert_eq!(pool.reserved(&1u64), 74);
+        assert_eq!(ledger.balance_of(&admin), 74);
+        assert_eq!(ledger.balance_of(&client.address), 1);
+
+        assert_eq!(client.claim_reward(&winner1, &1u32), 37);
+        assert_eq!(client.claim_reward(&winner2, &1u32), 37);
+        assert_eq!(pool.paid(&winner1), 37);
+        assert_eq!(pool.paid(&winner2), 37);
+        assert_eq!(pool.reserved(&1u64), 0);
+        assert_eq!(ledger.balance_of(&client.address), 1);
+    }
+
+    // ------------------------------------------------------------------
+    // 14. A round without winners carries its pot into the next puzzle
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_no_winner_pot_carries_into_next_round() {
+        let env = Env::default();
+        let (client, admin, prize_pool, balance) = setup(&env);
+        env.mock_all_auths();
+        let pool = MockPrizePoolClient::new(&env, &prize_pool);
+        let ledger = MockBalanceClient::new(&env, &balance);
+        assert_eq!(client.get_rollover_policy(), RolloverPolicy::Carry);
+
+        client.create_puzzle(&admin, &1u32, &sha256_of(&env, b"HARD"), &40i128);
+        client.submit_solution(&Address::generate(&env), &1u32, &Bytes::from_slice(&env, b"X"));
+        client.submit_solution(&Address::generate(&env), &1u32, &Bytes::from_slice(&env, b"Y"));
+        client.resolve_round(&admin, &1u32, &Bytes::from_slice(&env, b"HARD"));
+        assert_eq!(client.get_round(&1u32).unwrap().payout_per_winner, 0);
+        assert_eq!(client.get_rollover_pot(), 80);
+        assert_eq!(ledger.balance_of(&client.address), 80);
+
+        let correct = b"EASY";
+        client.create_puzzle(&admin, &2u32, &sha256_of(&env, correct), &10i128);
+        assert_eq!(client.get_rollover_pot(), 0);
+        let round = client.get_round(&2u32).unwrap();
+        assert_eq!(round.carried_in, 80);
+        assert_eq!(round.total_pot, 80);
+
+        let winner = Address::generate(&env);
+        let correct_bytes = Bytes::from_slice(&env, correct);
+        client.submit_solution(&winner, &2u32, &correct_bytes);
+        client.resolve_round(&admin, &2u32, &correct_bytes);
+        // The carried pot leaves escrow along with the new entry.
+        assert_eq!(pool.reserved(&2u64), 90);
+        assert_eq!(ledger.balance_of(&client.address), 0);
+        assert_eq!(client.claim_reward(&winner, &2u32), 90);
+        assert_eq!(pool.paid(&winner), 90);
+    }
+
+    // ------------------------------------------------------------------
+    // 15. Refund policy returns entry fees when nobody wins
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_refund_policy_returns_entry_fees() {
+        let env = Env::default();
+        let (client, admin, _, balance) = setup(&env);
+        env.mock_all_auths();
+        let ledger = MockBalanceClient::new(&env, &balance);
+
+        let imposter = Address::generate(&env);
+        let result = client.try_set_rollover_policy(&imposter, &RolloverPolicy::Refund);
+        assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+        client.set_rollover_policy(&admin, &RolloverPolicy::Refund);
+
+        client.create_puzzle(&admin, &1u32, &sha256_of(&env, b"NOPE"), &30i128);
+        let player = Address::generate(&env);
+        client.submit_solution(&player, &1u32, &Bytes::from_slice(&env, b"GUESS"));
+        assert_eq!(ledger.balance_of(&player), -30);
+
+        client.resolve_round(&admin, &1u32, &Bytes::from_slice(&env, b"NOPE"));
+        assert_eq!(ledger.balance_of(&player), 0);
+        assert_eq!(ledger.balance_of(&client.address), 0);
+        assert_eq!(client.get_rollover_pot(), 0);
+    }
+
+    // ------------------------------------------------------------------
+    // 16. score_pattern counts exact and misplaced symbols
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_score_pattern() {
+        let env = Env::default();
+        let score = |guess: &[u8], answer: &[u8]| {
+            score_pattern(&Bytes::from_slice(&env, guess), &Bytes::from_slice(&env, answer))
+        };
+
+        assert_eq!(score(b"RGBY", b"RGBY"), PatternScore { exact: 4, misplaced: 0 });
+        assert_eq!(score(b"YBGR", b"RGBY"), PatternScore { exact: 0, misplaced: 4 });
+        assert_eq!(score(b"RGYB", b"RGBY"), PatternScore { exact: 2, misplaced: 2 });
+        assert_eq!(score(b"XXXX", b"RGBY"), PatternScore { exact: 0, misplaced: 0 });
+
+        // Each answer symbol is consumed once: exact first, then misplaced.
+        assert_eq!(score(b"RRRR", b"RGBR"), PatternScore { exact: 2, misplaced: 0 });
+        assert_eq!(score(b"GGRB", b"RGBR"), PatternScore { exact: 1, misplaced: 2 });
+
+        // Length mismatches still credit shared symbols.
+        assert_eq!(score(b"RG", b"RGBY"), PatternScore { exact: 2, misplaced: 0 });
+        assert_eq!(score(b"BYRG", b"RGB"), PatternScore { exact: 0, misplaced: 3 });
+
+        assert_eq!(score_points(&PatternScore { exact: 2, misplaced: 2 }), 6);
+    }
+
+    // ------------------------------------------------------------------
+    // 17. Scored rounds pay partial matches by tier
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_scored_round_pays_tiers() {
+        let env = Env::default();
+        let (client, admin, prize_pool, balance) = setup(&env);
+        env.mock_all_auths();
+        let pool = MockPrizePoolClient::new(&env, &prize_pool);
+        let ledger = MockBalanceClient::new(&env, &balance);
+
+        let correct = b"RGBY";
+        let tiers = Vec::from_array(
+            &env,
+            [
+                ScoreTier { min_points: 8, share_bps: 6_000 },
+                ScoreTier { min_points: 4, share_bps: 3_000 },
+            ],
+        );
+        let commitment = sha256_of(&env, correct);
+        client.create_scored_puzzle(&admin, &1u32, &commitment, &4u32, &100i128, &tiers);
+
+        let solver = Address::generate(&env);
+        let close = Address::generate(&env);
+        let scrambled = Address::generate(&env);
+        let miss = Address::generate(&env);
+        client.submit_solution(&solver, &1u32, &Bytes::from_slice(&env, correct));
+        client.submit_solution(&close, &1u32, &Bytes::from_slice(&env, b"RGYB"));
+        client.submit_solution(&scrambled, &1u32, &Bytes::from_slice(&env, b"YBGR"));
+        client.submit_solution(&miss, &1u32, &Bytes::from_slice(&env, b"XXXX"));
+
+        // Pot 400: top tier 240 / 1, second tier 120 / 2; the unallocated
+        // 10% rolls over.
+        client.resolve_round(&admin, &1u32, &Bytes::from_slice(&env, correct));
+        let round = client.get_round(&1u32).unwrap();
+        assert_eq!(round.winner_count, 3);
+        assert_eq!(round.tier_payouts, Vec::from_array(&env, [240i128, 60]));
+        assert_eq!(client.get_rollover_pot(), 40);
+        assert_eq!(pool.reserved(&1u64), 360);
+        assert_eq!(
+            client.get_score(&1u32, &close),
+            Some(PatternScore { exact: 2, misplaced: 2 })
+        );
+        assert_eq!(
+            client.get_score(&1u32, &miss),
+            Some(PatternScore { exact: 0, misplaced: 0 })
+        );
+
+        assert_eq!(client.claim_reward(&solver, &1u32), 240);
+        assert_eq!(client.claim_reward(&close, &1u32), 60);
+        assert_eq!(client.claim_reward(&scrambled, &1u32), 60);
+        let result = client.try_claim_reward(&miss, &1u32);
+        assert_eq!(result, Err(Ok(Error::NoRewardAvailable)));
+        assert_eq!(pool.reserved(&1u64), 0);
+        assert_eq!(ledger.balance_of(&client.address), 40);
+    }
+
+    // ------------------------------------------------------------------
+    // 18. Invalid score tiers are rejected
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_invalid_score_tiers_rejected() {
+        let env = Env::default();
+        let (client, admin, _, _) = setup(&env);
+        env.mock_all_auths();
+
+        let commitment = sha256_of(&env, b"RGBY");
+        let tier = |min_points: u32, share_bps: u32| ScoreTier { min_points, share_bps };
+        let invalid = [
+            Vec::new(&env),
+            Vec::from_array(&env, [tier(0, 5_000)]),
+            Vec::from_array(&env, [tier(4, 0)]),
+            Vec::from_array(&env, [tier(4, 5_000), tier(6, 2_000)]),
+            Vec::from_array(&env, [tier(6, 5_000), tier(6, 2_000)]),
+            Vec::from_array(&env, [tier(8, 8_000), tier(4, 3_000)]),
+        ];
+        for tiers in invalid.iter() {
+            let result =
+                client.try_create_scored_puzzle(&admin, &1u32, &commitment, &4u32, &0i128, tiers);
+            assert_eq!(result, Err(Ok(Error::InvalidTiers)));
+        }
+        assert!(client.get_round(&1u32).is_none());
+    }
+
+    // ------------------------------------------------------------------
+    // 19. Scored rounds reject guesses of the wrong length
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_scored_round_rejects_shotgun_guess() {
+        let env = Env::default();
+        let (client, admin, _, _) = setup(&env);
+        env.mock_all_auths();
+
+        let correct = b"RGBY";
+        let commitment = sha256_of(&env, correct);
+        let tiers = Vec::from_array(&env, [ScoreTier { min_points: 2, share_bps: 10_000 }]);
+        let result =
+            client.try_create_scored_puzzle(&admin, &1u32, &commitment, &0u32, &0i128, &tiers);
+        assert_eq!(result, Err(Ok(Error::InvalidPattern)));
+        client.create_scored_puzzle(&admin, &1u32, &commitment, &4u32, &10i128, &tiers);
+
+        // Every byte value once: scored, it would credit each answer symbol as
+        // misplaced and reach the tier without knowing anything.
+        let mut shotgun = Bytes::new(&env);
+        for symbol in 0..=u8::MAX {
+            shotgun.push_back(symbol);
+        }
+        assert_eq!(score_points(&score_pattern(&shotgun, &Bytes::from_slice(&env, correct))), 4);
+
+        let player = Address::generate(&env);
+        let result = client.try_submit_solution(&player, &1u32, &shotgun);
+        assert_eq!(result, Err(Ok(Error::InvalidPattern)));
+        let result = client.try_submit_solution(&player, &1u32, &Bytes::from_slice(&env, b"RGB"));
+        assert_eq!(result, Err(Ok(Error::InvalidPattern)));
+        assert!(client.get_submission(&1u32, &player).is_none());
+
+        client.submit_solution(&player, &1u32, &Bytes::from_slice(&env, b"YBGR"));
+        client.resolve_round(&admin, &1u32, &Bytes::from_slice(&env, correct));
+        assert_eq!(client.claim_reward(&player, &1u32), 10);
+    }
 }
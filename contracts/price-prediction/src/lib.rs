@@ -6,27 +6,71 @@
 //! ## Game Flow
 //! 1. Admin calls `open_market` → oracle provides opening price, round stored.
 //! 2. Players call `place_prediction` before `close_time` → tokens escrowed.
-//! 3. After `close_time`, anyone calls `settle_round` → oracle provides
+//! 3. After `close_time`, anyone calls `settle_round` → oracles provide
 //!    closing price, outcome determined, net pool calculated.
 //! 4. Winners call `claim` → proportional share of net pool transferred.
 //!
+//! ## Position Management
+//! Before a round locks, a player may `increase_position` on their side or,
+//! when the admin has enabled it, `exit_position` to get their stake back
+//! minus `exit_penalty_bps`. The penalty stays in the round as `forfeited`
+//! and is shared by the winners. If the round ends as a push or is voided
+//! there are no winners, and the admin collects it with `sweep_forfeited`.
+//!
+//! ## Cancellation
+//! A round that cannot be settled (oracle down, stale or invalid prices) is
+//! not stuck forever: once `close_time + settlement_grace` has passed anyone
+//! may call `cancel_round`, and the admin may `void_round` at any time with
+//! a reason code. Cancelled and voided rounds refund every wager in full.
+//!
+//! ## Chained Rounds
+//! After `start_rounds`, rounds run back to back without admin input. Each
+//! call to the permissionless `execute_round`, once the betting round's
+//! `lock_time` has passed:
+//! 1. settles the live round N with the current price as its close price,
+//! 2. locks round N+1, freezing the same price as its open price,
+//! 3. opens round N+2 for bets until `now + interval`,
+//!
+//! and pays the caller `keeper_fee_bps` of the house fee taken from round N.
+//! A round reached more than `MAX_EXECUTION_DELAY` after its close time
+//! (live round) or lock time (betting round) is voided instead, since the
+//! current price no longer belongs to it.
+//!
+//! ## Bucket Markets
+//! `open_bucket_market` takes strictly increasing bounds in basis points of
+//! price change relative to the open price. `n` bounds define `n + 1`
+//! buckets; bucket `i` covers changes in `[bounds[i - 1], bounds[i])`, with
+//! the first and last buckets open-ended. Players stake on a bucket index
+//! and the outcome is the index of the bucket the close price lands in.
+//!
 //! ## Pari-Mutuel Settlement
-//! - Total pool = sum of all wagers from both sides.
+//! - Total pool = sum of all wagers across every outcome, plus penalties
+//!   forfeited by early exits.
 //! - House fee = total_pool × house_edge_bps / 10000.
 //! - Net pool = total_pool − fee.
 //! - Each winner receives: net_pool × (their_wager / total_winning_side).
 //!
+//! ## Oracle Aggregation
+//! Every registered oracle is a SEP-40 price feed queried with
+//! `lastprice(Asset::Other(asset))` and `decimals()`.
+//! Prices older than `max_price_age` or `<= 0` are discarded, the rest are
+//! normalised to `PRICE_DECIMALS` and the median is used. If any accepted
+//! source deviates from the median by more than `max_deviation_bps`, opening
+//! is rejected and settlement voids the round instead.
+//!
 //! ## Push Rules
 //! A round is a push (all bets refunded) when:
 //! - Close price equals open price (flat).
 //! - No bets were placed.
 //! - Only one side has bets (no opposing risk).
+//! - The winning bucket has no stake, or holds every stake.
+//! - Oracle sources diverged at settlement (outcome `OUTCOME_VOID`).
 #![no_std]
 #![allow(unexpected_cfgs)]
 
 use soroban_sdk::{
     contract, contractclient, contracterror, contractevent, contractimpl, contracttype,
-    token::TokenClient, Address, Env, Symbol,
+    token::TokenClient, vec, Address, Env, Symbol, Vec,
 };
 
 // ---------------------------------------------------------------------------
@@ -42,14 +86,63 @@ pub const DIRECTION_DOWN: u32 = 1;
 pub const OUTCOME_UP: u32 = 0;
 pub const OUTCOME_DOWN: u32 = 1;
 pub const OUTCOME_FLAT: u32 = 2;
+/// Outcome of a voided round; outside the range of any bucket index.
+pub const OUTCOME_VOID: u32 = u32::MAX;
+
+/// Maximum number of outcomes in a bucket market.
+pub const MAX_BUCKETS: u32 = 8;
+
+pub const DEFAULT_SETTLEMENT_GRACE: u64 = 86_400;
+/// Shortest settlement grace the admin may set, so a round can never be
+/// cancelled before the keeper and oracles have had a chance to settle it.
+pub const MIN_SETTLEMENT_GRACE: u64 = 600;
+
+/// `RoundData::void_reason` values set by the contract. Admins may use any
+/// other non-zero code with `void_round`.
+pub const VOID_REASON_NONE: u32 = 0;
+pub const VOID_REASON_ORACLE_DIVERGED: u32 = 1;
+pub const VOID_REASON_SETTLEMENT_TIMEOUT: u32 = 2;
+pub const VOID_REASON_EXECUTION_LATE: u32 = 3;
+
+/// Minimum betting / live phase length of a chained round, in seconds.
+pub const MIN_ROUND_INTERVAL: u64 = 60;
+/// Longest `execute_round` may run after a chained round's lock or close
+/// time and still use the current price for it; later rounds are voided.
+pub const MAX_EXECUTION_DELAY: u64 = 300;
+
+/// Decimals every oracle price is normalised to before aggregation.
+pub const PRICE_DECIMALS: u32 = 14;
+pub const MAX_ORACLES: u32 = 5;
+pub const DEFAULT_MAX_PRICE_AGE: u64 = 300;
+pub const DEFAULT_MAX_DEVIATION_BPS: u32 = 100;
 
 // ---------------------------------------------------------------------------
 // External contract clients
 // ---------------------------------------------------------------------------
 
+/// SEP-40 asset identifier. Markets are keyed by `Symbol` tickers, which
+/// are always queried as `Asset::Other`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Asset {
+    Stellar(Address),
+    Other(Symbol),
+}
+
+/// SEP-40 price record.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+/// Subset of the SEP-40 price feed interface used for settlement, as
+/// implemented by Reflector and other Stellar oracles.
 #[contractclient(name = "OracleClient")]
 pub trait OracleContract {
-    fn get_price(env: Env, asset: Symbol) -> i128;
+    fn lastprice(env: Env, asset: Asset) -> Option<PriceData>;
+    fn decimals(env: Env) -> u32;
 }
 
 // ---------------------------------------------------------------------------
@@ -60,26 +153,43 @@ pub trait OracleContract {
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
 pub enum Error {
-    AlreadyInitialized  = 1,
-    NotInitialized      = 2,
-    NotAuthorized       = 3,
-    InvalidAmount       = 4,
-    InvalidDirection    = 5,
-    RoundAlreadyExists  = 6,
-    RoundNotFound       = 7,
-    AlreadySettled      = 8,
-    NotSettled          = 9,
-    RoundNotClosed      = 10,
-    RoundClosed         = 11,
-    BetAlreadyPlaced    = 12,
-    BetNotFound         = 13,
-    AlreadyClaimed      = 14,
-    NoPayout            = 15,
-    WagerTooLow         = 16,
-    WagerTooHigh        = 17,
-    Overflow            = 18,
-    InvalidCloseTime    = 19,
-    InvalidPrice        = 20,
+    AlreadyInitialized      = 1,
+    NotInitialized          = 2,
+    NotAuthorized           = 3,
+    InvalidAmount           = 4,
+    InvalidDirection        = 5,
+    RoundAlreadyExists      = 6,
+    RoundNotFound           = 7,
+    AlreadySettled          = 8,
+    NotSettled              = 9,
+    RoundNotClosed          = 10,
+    RoundClosed             = 11,
+    BetAlreadyPlaced        = 12,
+    BetNotFound             = 13,
+    AlreadyClaimed          = 14,
+    NoPayout                = 15,
+    WagerTooLow             = 16,
+    WagerTooHigh            = 17,
+    Overflow                = 18,
+    InvalidCloseTime        = 19,
+    InvalidPrice            = 20,
+    StalePrice              = 21,
+    PriceDeviationTooHigh   = 22,
+    OracleAlreadyRegistered = 23,
+    OracleNotRegistered     = 24,
+    TooManyOracles          = 25,
+    InsufficientSources     = 26,
+    InvalidOracleConfig     = 27,
+    InvalidBuckets          = 28,
+    InvalidInterval         = 29,
+    InvalidKeeperFee        = 30,
+    ChainNotStarted         = 31,
+    ChainAlreadyStarted     = 32,
+    RoundNotLocked          = 33,
+    GracePeriodActive       = 34,
+    InvalidReason           = 35,
+    EarlyExitDisabled       = 36,
+    InvalidGrace            = 37,
 }
 
 // ---------------------------------------------------------------------------
@@ -98,28 +208,78 @@ pub struct BetKey {
 pub enum DataKey {
     Admin,
     Token,
-    OracleContract,
+    Oracles,
+    OracleConfig,
     MinWager,
     MaxWager,
     HouseEdgeBps,
+    SettlementGrace,
+    ExitPenaltyBps,
+    Chain,
     Round(u64),
     Bet(BetKey),
 }
 
+/// Freshness and agreement requirements applied to oracle reads.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OracleConfig {
+    /// Maximum age in seconds of an accepted price.
+    pub max_price_age: u64,
+    /// Maximum distance of any accepted price from the median.
+    pub max_deviation_bps: u32,
+    /// Minimum number of fresh, valid prices needed to aggregate.
+    pub min_sources: u32,
+}
+
+/// State of the continuous round cycle started by `start_rounds`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChainConfig {
+    pub asset: Symbol,
+    pub interval: u64,
+    /// Share of each settled round's house fee paid to the `execute_round`
+    /// caller.
+    pub keeper_fee_bps: i128,
+    /// Round currently accepting bets.
+    pub current_round: u64,
+    /// Locked round awaiting settlement, if any.
+    pub live_round: Option<u64>,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct RoundData {
     pub asset: Symbol,
+    /// Price frozen when betting locks; `0` for a chained round still
+    /// accepting bets.
     pub open_price: i128,
     pub close_price: i128,
+    /// Bets are accepted until this time. Equals `close_time` for rounds
+    /// opened with `open_market` / `open_bucket_market`.
+    pub lock_time: u64,
     pub close_time: u64,
+    /// Binary markets only: mirrors `pools[DIRECTION_UP]`.
     pub total_up: i128,
+    /// Binary markets only: mirrors `pools[DIRECTION_DOWN]`.
     pub total_down: i128,
+    /// Bucket bounds in bps of price change; empty for UP/DOWN markets.
+    pub bounds: Vec<i128>,
+    /// Stake per outcome: `[up, down]` for binary markets, one entry per
+    /// bucket otherwise.
+    pub pools: Vec<i128>,
+    /// Early-exit penalties kept in the round; added to the total pool at
+    /// settlement but not part of any outcome's stake. Reset to zero once
+    /// `sweep_forfeited` collects it from a push.
+    pub forfeited: i128,
     pub settled: bool,
     pub outcome: u32,
     pub is_push: bool,
     pub net_pool: i128,
     pub winning_total: i128,
+    /// Why the round was voided; `VOID_REASON_NONE` unless `outcome` is
+    /// `OUTCOME_VOID`.
+    pub void_reason: u32,
 }
 
 #[contracttype]
@@ -143,6 +303,49 @@ pub struct MarketOpened {
     pub close_time: u64,
 }
 
+#[contractevent]
+pub struct BucketMarketOpened {
+    #[topic]
+    pub round_id: u64,
+    pub asset: Symbol,
+    pub open_price: i128,
+    pub close_time: u64,
+    pub bounds: Vec<i128>,
+}
+
+#[contractevent]
+pub struct RoundsStarted {
+    pub first_round_id: u64,
+    pub asset: Symbol,
+    pub interval: u64,
+    pub keeper_fee_bps: i128,
+}
+
+#[contractevent]
+pub struct ChainRoundOpened {
+    #[topic]
+    pub round_id: u64,
+    pub asset: Symbol,
+    pub lock_time: u64,
+}
+
+#[contractevent]
+pub struct RoundLocked {
+    #[topic]
+    pub round_id: u64,
+    pub lock_price: i128,
+    pub close_time: u64,
+}
+
+#[contractevent]
+pub struct KeeperRewarded {
+    #[topic]
+    pub caller: Address,
+    #[topic]
+    pub round_id: u64,
+    pub reward: i128,
+}
+
 #[contractevent]
 pub struct PredictionPlaced {
     #[topic]
@@ -153,6 +356,31 @@ pub struct PredictionPlaced {
     pub wager: i128,
 }
 
+#[contractevent]
+pub struct PositionIncreased {
+    #[topic]
+    pub round_id: u64,
+    #[topic]
+    pub player: Address,
+    pub amount: i128,
+    pub wager: i128,
+}
+
+#[contractevent]
+pub struct PositionExited {
+    #[topic]
+    pub round_id: u64,
+    #[topic]
+    pub player: Address,
+    pub refund: i128,
+    pub penalty: i128,
+}
+
+#[contractevent]
+pub struct ExitPenaltyUpdated {
+    pub penalty_bps: Option<i128>,
+}
+
 #[contractevent]
 pub struct RoundSettled {
     #[topic]
@@ -163,6 +391,53 @@ pub struct RoundSettled {
     pub net_pool: i128,
 }
 
+#[contractevent]
+pub struct PriceSourcesDiverged {
+    #[topic]
+    pub round_id: u64,
+    pub median_price: i128,
+    pub deviation_bps: i128,
+}
+
+#[contractevent]
+pub struct OracleAdded {
+    #[topic]
+    pub oracle: Address,
+}
+
+#[contractevent]
+pub struct OracleRemoved {
+    #[topic]
+    pub oracle: Address,
+}
+
+#[contractevent]
+pub struct OracleConfigUpdated {
+    pub max_price_age: u64,
+    pub max_deviation_bps: u32,
+    pub min_sources: u32,
+}
+
+#[contractevent]
+pub struct RoundVoided {
+    #[topic]
+    pub round_id: u64,
+    pub reason: u32,
+}
+
+#[contractevent]
+pub struct ForfeitedSwept {
+    #[topic]
+    pub round_id: u64,
+    pub to: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+pub struct SettlementGraceUpdated {
+    pub grace: u64,
+}
+
 #[contractevent]
 pub struct Claimed {
     #[topic]
@@ -184,6 +459,9 @@ impl PricePrediction {
     /// Initialize the price prediction game.
     ///
     /// `house_edge_bps`: house edge in basis points (e.g., 500 = 5%).
+    /// `oracle_contract` becomes the first registered price source and the
+    /// oracle config starts at `DEFAULT_MAX_PRICE_AGE` /
+    /// `DEFAULT_MAX_DEVIATION_BPS` with a single required source.
     pub fn init(
         env: Env,
         admin: Address,
@@ -199,18 +477,97 @@ impl PricePrediction {
         admin.require_auth();
 
         env.storage().instance().set(&DataKey::Admin, &admin);
-        env.storage().instance().set(&DataKey::OracleContract, &oracle_contract);
+        env.storage().instance().set(&DataKey::Oracles, &vec![&env, oracle_contract]);
+        env.storage().instance().set(
+            &DataKey::OracleConfig,
+            &OracleConfig {
+                max_price_age: DEFAULT_MAX_PRICE_AGE,
+                max_deviation_bps: DEFAULT_MAX_DEVIATION_BPS,
+                min_sources: 1,
+            },
+        );
         env.storage().instance().set(&DataKey::Token, &token);
         env.storage().instance().set(&DataKey::MinWager, &min_wager);
         env.storage().instance().set(&DataKey::MaxWager, &max_wager);
         env.storage().instance().set(&DataKey::HouseEdgeBps, &house_edge_bps);
+        env.storage().instance().set(&DataKey::SettlementGrace, &DEFAULT_SETTLEMENT_GRACE);
+        Ok(())
+    }
+
+    /// Register an additional oracle price source. Admin only.
+    pub fn add_oracle(env: Env, oracle: Address) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_admin(&env)?;
+
+        let mut oracles = get_oracles(&env);
+        if oracles.contains(&oracle) {
+            return Err(Error::OracleAlreadyRegistered);
+        }
+        if oracles.len() >= MAX_ORACLES {
+            return Err(Error::TooManyOracles);
+        }
+        oracles.push_back(oracle.clone());
+        env.storage().instance().set(&DataKey::Oracles, &oracles);
+
+        OracleAdded { oracle }.publish(&env);
+        Ok(())
+    }
+
+    /// Remove a registered oracle price source. Admin only.
+    ///
+    /// Fails with `InsufficientSources` if fewer than `min_sources` would
+    /// remain registered.
+    pub fn remove_oracle(env: Env, oracle: Address) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_admin(&env)?;
+
+        let mut oracles = get_oracles(&env);
+        let index = oracles.first_index_of(&oracle).ok_or(Error::OracleNotRegistered)?;
+        if oracles.len() - 1 < get_oracle_config(&env).min_sources {
+            return Err(Error::InsufficientSources);
+        }
+        oracles.remove(index);
+        env.storage().instance().set(&DataKey::Oracles, &oracles);
+
+        OracleRemoved { oracle }.publish(&env);
+        Ok(())
+    }
+
+    /// Update the staleness and deviation guards. Admin only.
+    ///
+    /// `max_price_age` must be non-zero, `max_deviation_bps` at most 10000
+    /// and `min_sources` between 1 and the number of registered oracles.
+    pub fn set_oracle_config(
+        env: Env,
+        max_price_age: u64,
+        max_deviation_bps: u32,
+        min_sources: u32,
+    ) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_admin(&env)?;
+
+        if max_price_age == 0
+            || max_deviation_bps as i128 > BASIS_POINTS_DIVISOR
+            || min_sources == 0
+            || min_sources > get_oracles(&env).len()
+        {
+            return Err(Error::InvalidOracleConfig);
+        }
+
+        env.storage().instance().set(
+            &DataKey::OracleConfig,
+            &OracleConfig { max_price_age, max_deviation_bps, min_sources },
+        );
+
+        OracleConfigUpdated { max_price_age, max_deviation_bps, min_sources }.publish(&env);
         Ok(())
     }
 
     /// Open a new prediction market round. Admin only.
     ///
-    /// Queries the oracle for the current price of `asset` to set the
-    /// opening price. `close_time` must be in the future.
+    /// Queries every registered oracle for the current price of `asset` and
+    /// uses the median as the opening price. Fails if the sources disagree
+    /// by more than `max_deviation_bps`. `close_time` must be in the future.
     pub fn open_market(
         env: Env,
         round_id: u64,
@@ -220,49 +577,77 @@ impl PricePrediction {
         require_initialized(&env)?;
         require_admin(&env)?;
 
-        if close_time <= env.ledger().timestamp() {
-            return Err(Error::InvalidCloseTime);
-        }
+        let open_price = opening_price(&env, &asset)?;
+        let round = open_round(
+            &env,
+            round_id,
+            asset.clone(),
+            open_price,
+            close_time,
+            close_time,
+            Vec::new(&env),
+        )?;
 
-        let round_key = DataKey::Round(round_id);
-        if env.storage().persistent().has(&round_key) {
-            return Err(Error::RoundAlreadyExists);
-        }
+        MarketOpened { round_id, asset, open_price: round.open_price, close_time }.publish(&env);
+        Ok(())
+    }
 
-        // Get opening price from oracle
-        let oracle_addr = get_oracle(&env);
-        let open_price = OracleClient::new(&env, &oracle_addr).get_price(&asset);
-        if open_price <= 0 {
-            return Err(Error::InvalidPrice);
+    /// Open a multi-outcome bucket market. Admin only.
+    ///
+    /// `bounds` are strictly increasing price changes in basis points
+    /// relative to the open price, e.g. `[-200, 0, 200]` for the buckets
+    /// `< -2%`, `-2%..0`, `0..+2%` and `>= +2%`. Between 1 and
+    /// `MAX_BUCKETS - 1` bounds are allowed and all must be above -10000.
+    pub fn open_bucket_market(
+        env: Env,
+        round_id: u64,
+        asset: Symbol,
+        close_time: u64,
+        bounds: Vec<i128>,
+    ) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_admin(&env)?;
+
+        if bounds.is_empty() || bounds.len() >= MAX_BUCKETS {
+            return Err(Error::InvalidBuckets);
+        }
+        let mut prev = -BASIS_POINTS_DIVISOR;
+        for bound in bounds.iter() {
+            if bound <= prev {
+                return Err(Error::InvalidBuckets);
+            }
+            prev = bound;
         }
 
-        let round = RoundData {
-            asset: asset.clone(),
+        let open_price = opening_price(&env, &asset)?;
+        let round = open_round(
+            &env,
+            round_id,
+            asset.clone(),
             open_price,
-            close_price: 0,
             close_time,
-            total_up: 0,
-            total_down: 0,
-            settled: false,
-            outcome: 0,
-            is_push: false,
-            net_pool: 0,
-            winning_total: 0,
-        };
-        env.storage().persistent().set(&round_key, &round);
-        env.storage()
-            .persistent()
-            .extend_ttl(&round_key, PERSISTENT_BUMP_LEDGERS, PERSISTENT_BUMP_LEDGERS);
+            close_time,
+            bounds.clone(),
+        )?;
 
-        MarketOpened { round_id, asset, open_price, close_time }.publish(&env);
+        BucketMarketOpened {
+            round_id,
+            asset,
+            open_price: round.open_price,
+            close_time,
+            bounds,
+        }
+        .publish(&env);
         Ok(())
     }
 
     /// Player places a prediction on an open round.
     ///
-    /// `direction`: 0 = Up, 1 = Down.
+    /// `direction`: 0 = Up, 1 = Down for binary markets, or the bucket
+    /// index for bucket markets.
     /// Tokens are transferred from the player to the contract as escrow.
-    /// Each player may only bet once per round.
+    /// Each player holds a single bet per round; use `increase_position` to
+    /// add to it.
     pub fn place_prediction(
         env: Env,
         player: Address,
@@ -273,9 +658,6 @@ impl PricePrediction {
         require_initialized(&env)?;
         player.require_auth();
 
-        if direction != DIRECTION_UP && direction != DIRECTION_DOWN {
-            return Err(Error::InvalidDirection);
-        }
         if wager <= 0 {
             return Err(Error::InvalidAmount);
         }
@@ -296,10 +678,13 @@ impl PricePrediction {
             .get(&round_key)
             .ok_or(Error::RoundNotFound)?;
 
+        if direction >= round.pools.len() {
+            return Err(Error::InvalidDirection);
+        }
         if round.settled {
             return Err(Error::AlreadySettled);
         }
-        if env.ledger().timestamp() >= round.close_time {
+        if env.ledger().timestamp() >= round.lock_time {
             return Err(Error::RoundClosed);
         }
 
@@ -320,11 +705,7 @@ impl PricePrediction {
         );
 
         // Update round totals
-        if direction == DIRECTION_UP {
-            round.total_up = round.total_up.checked_add(wager).ok_or(Error::Overflow)?;
-        } else {
-            round.total_down = round.total_down.checked_add(wager).ok_or(Error::Overflow)?;
-        }
+        adjust_stake(&mut round, direction, wager)?;
         env.storage().persistent().set(&round_key, &round);
         env.storage()
             .persistent()
@@ -345,6 +726,179 @@ impl PricePrediction {
         Ok(())
     }
 
+    /// Add `amount` to the player's existing position in a round, on the
+    /// same side, before the round locks.
+    ///
+    /// The combined wager must stay within `max_wager`.
+    pub fn increase_position(
+        env: Env,
+        player: Address,
+        round_id: u64,
+        amount: i128,
+    ) -> Result<(), Error> {
+        require_initialized(&env)?;
+        player.require_auth();
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let round_key = DataKey::Round(round_id);
+        let mut round = load_open_round(&env, round_id)?;
+        let bet_key = DataKey::Bet(BetKey {
+            round_id,
+            player: player.clone(),
+        });
+        let mut bet: BetData = env
+            .storage()
+            .persistent()
+            .get(&bet_key)
+            .ok_or(Error::BetNotFound)?;
+
+        let wager = bet.wager.checked_add(amount).ok_or(Error::Overflow)?;
+        let max_wager: i128 = env.storage().instance().get(&DataKey::MaxWager).unwrap();
+        if wager > max_wager {
+            return Err(Error::WagerTooHigh);
+        }
+
+        let token = get_token(&env);
+        TokenClient::new(&env, &token).transfer(
+            &player,
+            env.current_contract_address(),
+            &amount,
+        );
+
+        adjust_stake(&mut round, bet.direction, amount)?;
+        env.storage().persistent().set(&round_key, &round);
+        env.storage()
+            .persistent()
+            .extend_ttl(&round_key, PERSISTENT_BUMP_LEDGERS, PERSISTENT_BUMP_LEDGERS);
+
+        bet.wager = wager;
+        env.storage().persistent().set(&bet_key, &bet);
+        env.storage()
+            .persistent()
+            .extend_ttl(&bet_key, PERSISTENT_BUMP_LEDGERS, PERSISTENT_BUMP_LEDGERS);
+
+        PositionIncreased { round_id, player, amount, wager }.publish(&env);
+        Ok(())
+    }
+
+    /// Close the player's position before the round locks. Requires early
+    /// exit to be enabled with `set_exit_penalty`.
+    ///
+    /// The player gets back `wager - penalty`, where
+    /// `penalty = wager × exit_penalty_bps / 10000`. The penalty stays in
+    /// the round as `forfeited`. The bet is removed, so the player may place
+    /// a new one.
+    pub fn exit_position(env: Env, player: Address, round_id: u64) -> Result<i128, Error> {
+        require_initialized(&env)?;
+        player.require_auth();
+
+        let penalty_bps: i128 = env
+            .storage()
+            .instance()
+            .get::<_, Option<i128>>(&DataKey::ExitPenaltyBps)
+            .flatten()
+            .ok_or(Error::EarlyExitDisabled)?;
+
+        let round_key = DataKey::Round(round_id);
+        let mut round = load_open_round(&env, round_id)?;
+        let bet_key = DataKey::Bet(BetKey {
+            round_id,
+            player: player.clone(),
+        });
+        let bet: BetData = env
+            .storage()
+            .persistent()
+            .get(&bet_key)
+            .ok_or(Error::BetNotFound)?;
+
+        let penalty = bet
+            .wager
+            .checked_mul(penalty_bps)
+            .and_then(|v| v.checked_div(BASIS_POINTS_DIVISOR))
+            .ok_or(Error::Overflow)?;
+        let refund = bet.wager.checked_sub(penalty).ok_or(Error::Overflow)?;
+
+        // State update before transfer (reentrancy-safe)
+        adjust_stake(&mut round, bet.direction, -bet.wager)?;
+        round.forfeited = round.forfeited.checked_add(penalty).ok_or(Error::Overflow)?;
+        env.storage().persistent().set(&round_key, &round);
+        env.storage()
+            .persistent()
+            .extend_ttl(&round_key, PERSISTENT_BUMP_LEDGERS, PERSISTENT_BUMP_LEDGERS);
+        env.storage().persistent().remove(&bet_key);
+
+        if refund > 0 {
+            let token = get_token(&env);
+            TokenClient::new(&env, &token).transfer(
+                &env.current_contract_address(),
+                &player,
+                &refund,
+            );
+        }
+
+        PositionExited { round_id, player, refund, penalty }.publish(&env);
+        Ok(refund)
+    }
+
+    /// Enable early exit with the given penalty in basis points, or disable
+    /// it with `None`. Admin only.
+    pub fn set_exit_penalty(env: Env, penalty_bps: Option<i128>) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_admin(&env)?;
+
+        if let Some(bps) = penalty_bps {
+            if !(0..=BASIS_POINTS_DIVISOR).contains(&bps) {
+                return Err(Error::InvalidAmount);
+            }
+        }
+        env.storage().instance().set(&DataKey::ExitPenaltyBps, &penalty_bps);
+
+        ExitPenaltyUpdated { penalty_bps }.publish(&env);
+        Ok(())
+    }
+
+    /// Transfer the early-exit penalties of a push or voided round to `to`.
+    /// Admin only.
+    ///
+    /// Such a round has no winners to share `forfeited`, so without this the
+    /// penalties would stay in the contract forever. Returns the amount
+    /// swept; fails with `NotSettled` before settlement and `NoPayout` when
+    /// the round paid winners or has nothing left to sweep.
+    pub fn sweep_forfeited(env: Env, round_id: u64, to: Address) -> Result<i128, Error> {
+        require_initialized(&env)?;
+        require_admin(&env)?;
+
+        let round_key = DataKey::Round(round_id);
+        let mut round: RoundData = env
+            .storage()
+            .persistent()
+            .get(&round_key)
+            .ok_or(Error::RoundNotFound)?;
+        if !round.settled {
+            return Err(Error::NotSettled);
+        }
+        if !round.is_push || round.forfeited == 0 {
+            return Err(Error::NoPayout);
+        }
+
+        // State update before transfer (reentrancy-safe)
+        let amount = round.forfeited;
+        round.forfeited = 0;
+        env.storage().persistent().set(&round_key, &round);
+        env.storage()
+            .persistent()
+            .extend_ttl(&round_key, PERSISTENT_BUMP_LEDGERS, PERSISTENT_BUMP_LEDGERS);
+
+        let token = get_token(&env);
+        TokenClient::new(&env, &token).transfer(&env.current_contract_address(), &to, &amount);
+
+        ForfeitedSwept { round_id, to, amount }.publish(&env);
+        Ok(amount)
+    }
+
     /// Settle a round after `close_time` has passed.
     /// Anyone can call this — the outcome is deterministic from the oracle.
     ///
@@ -352,6 +906,11 @@ impl PricePrediction {
     /// - Close price equals open price (flat market).
     /// - No bets were placed.
     /// - Only one side has bets (no opposing risk).
+    /// - The winning bucket has no stake, or holds every stake.
+    /// - The oracle sources diverged beyond `max_deviation_bps`; the round
+    ///   is voided (`OUTCOME_VOID`) so every bettor can reclaim their wager.
+    ///
+    /// Fails without settling if too few sources return a fresh price.
     pub fn settle_round(env: Env, round_id: u64) -> Result<(), Error> {
         require_initialized(&env)?;
 
@@ -369,61 +928,177 @@ impl PricePrediction {
             return Err(Error::RoundNotClosed);
         }
 
-        // Get closing price from oracle
-        let oracle_addr = get_oracle(&env);
-        let close_price = OracleClient::new(&env, &oracle_addr).get_price(&round.asset);
+        if round.open_price == 0 {
+            return Err(Error::RoundNotLocked);
+        }
 
-        let total_pool = round
-            .total_up
-            .checked_add(round.total_down)
-            .ok_or(Error::Overflow)?;
+        // Get closing price from the oracle sources
+        let quote = aggregate_price(&env, &round.asset)?;
+        settle(&env, round_id, &mut round, &quote)?;
+        Ok(())
+    }
 
-        // Determine outcome
-        let outcome = if close_price > round.open_price {
-            OUTCOME_UP
-        } else if close_price < round.open_price {
-            OUTCOME_DOWN
-        } else {
-            OUTCOME_FLAT
+    /// Start the continuous round cycle for `asset`. Admin only.
+    ///
+    /// Opens `first_round_id` for bets until `now + interval`. From then on
+    /// `execute_round` drives the cycle. `keeper_fee_bps` is the share of
+    /// each settled round's house fee paid to the `execute_round` caller.
+    pub fn start_rounds(
+        env: Env,
+        first_round_id: u64,
+        asset: Symbol,
+        interval: u64,
+        keeper_fee_bps: i128,
+    ) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_admin(&env)?;
+
+        if env.storage().instance().has(&DataKey::Chain) {
+            return Err(Error::ChainAlreadyStarted);
+        }
+        if interval < MIN_ROUND_INTERVAL {
+            return Err(Error::InvalidInterval);
+        }
+        if !(0..=BASIS_POINTS_DIVISOR).contains(&keeper_fee_bps) {
+            return Err(Error::InvalidKeeperFee);
+        }
+
+        open_chain_round(&env, first_round_id, &asset, interval)?;
+
+        let chain = ChainConfig {
+            asset: asset.clone(),
+            interval,
+            keeper_fee_bps,
+            current_round: first_round_id,
+            live_round: None,
         };
+        env.storage().instance().set(&DataKey::Chain, &chain);
+
+        RoundsStarted { first_round_id, asset, interval, keeper_fee_bps }.publish(&env);
+        Ok(())
+    }
+
+    /// Advance the continuous round cycle. Anyone can call this once the
+    /// betting round's `lock_time` has passed; `caller` receives the keeper
+    /// reward.
+    ///
+    /// A single oracle read settles the live round, locks the betting round
+    /// at that price and opens the next round. If the oracle sources
+    /// diverge, both the live and the betting round are voided so all their
+    /// bets are refundable. So is a round this call reaches more than
+    /// `MAX_EXECUTION_DELAY` after its close time (live round) or lock time
+    /// (betting round); the oracle is not read when every round is late.
+    /// Returns the id of the newly opened round.
+    pub fn execute_round(env: Env, caller: Address) -> Result<u64, Error> {
+        require_initialized(&env)?;
+        caller.require_auth();
+
+        let mut chain: ChainConfig = env
+            .storage()
+            .instance()
+            .get(&DataKey::Chain)
+            .ok_or(Error::ChainNotStarted)?;
+        let now = env.ledger().timestamp();
+
+        let current_id = chain.current_round;
+        let current_key = DataKey::Round(current_id);
+        let mut current: RoundData = env
+            .storage()
+            .persistent()
+            .get(&current_key)
+            .ok_or(Error::RoundNotFound)?;
+        if now < current.lock_time {
+            return Err(Error::RoundNotClosed);
+        }
 
-        // Push if: flat, no bets, or only one side has bets
-        let is_push = outcome == OUTCOME_FLAT
-            || total_pool == 0
-            || round.total_up == 0
-            || round.total_down == 0;
+        let live = match chain.live_round {
+            Some(live_id) => {
+                let round: RoundData = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::Round(live_id))
+                    .ok_or(Error::RoundNotFound)?;
+                Some((live_id, round)).filter(|(_, round)| !round.settled)
+            }
+            None => None,
+        };
+        let live_late = match &live {
+            Some((_, round)) => now > round.close_time.saturating_add(MAX_EXECUTION_DELAY),
+            None => false,
+        };
+        let current_late = now > current.lock_time.saturating_add(MAX_EXECUTION_DELAY);
 
-        let (net_pool, winning_total) = if is_push {
-            (0i128, 0i128)
+        let needs_price = (live.is_some() && !live_late) || (!current.settled && !current_late);
+        let quote = if needs_price {
+            Some(aggregate_price(&env, &chain.asset)?)
         } else {
-            let house_edge_bps: i128 =
-                env.storage().instance().get(&DataKey::HouseEdgeBps).unwrap();
-            let fee = total_pool
-                .checked_mul(house_edge_bps)
-                .and_then(|v| v.checked_div(BASIS_POINTS_DIVISOR))
-                .ok_or(Error::Overflow)?;
-            let net = total_pool.checked_sub(fee).ok_or(Error::Overflow)?;
-            let wt = if outcome == OUTCOME_UP {
-                round.total_up
-            } else {
-                round.total_down
-            };
-            (net, wt)
+            None
         };
 
-        round.close_price = close_price;
-        round.settled = true;
-        round.outcome = outcome;
-        round.is_push = is_push;
-        round.net_pool = net_pool;
-        round.winning_total = winning_total;
-        env.storage().persistent().set(&round_key, &round);
-        env.storage()
-            .persistent()
-            .extend_ttl(&round_key, PERSISTENT_BUMP_LEDGERS, PERSISTENT_BUMP_LEDGERS);
+        // 1. Settle the live round, or void it if it was left too long
+        let mut reward = 0i128;
+        let mut rewarded_round = current_id;
+        if let Some((live_id, mut round)) = live {
+            match quote.as_ref().filter(|_| !live_late) {
+                Some(quote) => {
+                    let fee = settle(&env, live_id, &mut round, quote)?;
+                    reward = fee
+                        .checked_mul(chain.keeper_fee_bps)
+                        .and_then(|v| v.checked_div(BASIS_POINTS_DIVISOR))
+                        .ok_or(Error::Overflow)?;
+                    rewarded_round = live_id;
+                }
+                None => void(&env, live_id, &mut round, VOID_REASON_EXECUTION_LATE),
+            }
+        }
 
-        RoundSettled { round_id, close_price, outcome, is_push, net_pool }.publish(&env);
-        Ok(())
+        // 2. Lock the betting round, or void it if it was left too long or
+        //    the price is unreliable
+        if !current.settled && current_late {
+            void(&env, current_id, &mut current, VOID_REASON_EXECUTION_LATE);
+        }
+        match quote.filter(|_| !current.settled) {
+            None => chain.live_round = None,
+            Some(quote)
+                if quote.deviation_bps > get_oracle_config(&env).max_deviation_bps as i128 =>
+            {
+                settle(&env, current_id, &mut current, &quote)?;
+                chain.live_round = None;
+            }
+            Some(quote) => {
+                current.open_price = quote.price;
+                current.close_time = now.checked_add(chain.interval).ok_or(Error::Overflow)?;
+                env.storage().persistent().set(&current_key, &current);
+                env.storage()
+                    .persistent()
+                    .extend_ttl(&current_key, PERSISTENT_BUMP_LEDGERS, PERSISTENT_BUMP_LEDGERS);
+                chain.live_round = Some(current_id);
+
+                RoundLocked {
+                    round_id: current_id,
+                    lock_price: quote.price,
+                    close_time: current.close_time,
+                }
+                .publish(&env);
+            }
+        }
+
+        // 3. Open the next round
+        let next_id = current_id.checked_add(1).ok_or(Error::Overflow)?;
+        open_chain_round(&env, next_id, &chain.asset, chain.interval)?;
+        chain.current_round = next_id;
+        env.storage().instance().set(&DataKey::Chain, &chain);
+
+        if reward > 0 {
+            let token = get_token(&env);
+            TokenClient::new(&env, &token).transfer(
+                &env.current_contract_address(),
+                &caller,
+                &reward,
+            );
+            KeeperRewarded { caller, round_id: rewarded_round, reward }.publish(&env);
+        }
+        Ok(next_id)
     }
 
     /// Claim winnings for a settled round. Winners receive their
@@ -496,6 +1171,59 @@ impl PricePrediction {
         Ok(())
     }
 
+    /// Set how long after `close_time` a round may remain unsettled before
+    /// anyone can cancel it. Admin only. `grace` must be at least
+    /// `MIN_SETTLEMENT_GRACE`.
+    pub fn set_settlement_grace(env: Env, grace: u64) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_admin(&env)?;
+
+        if grace < MIN_SETTLEMENT_GRACE {
+            return Err(Error::InvalidGrace);
+        }
+
+        env.storage().instance().set(&DataKey::SettlementGrace, &grace);
+        SettlementGraceUpdated { grace }.publish(&env);
+        Ok(())
+    }
+
+    /// Cancel a round that is still unsettled `settlement_grace` seconds
+    /// after its `close_time`. Anyone can call this. Every bettor can then
+    /// reclaim their full wager through `claim`, with no house fee.
+    pub fn cancel_round(env: Env, round_id: u64) -> Result<(), Error> {
+        require_initialized(&env)?;
+
+        let mut round = load_unsettled_round(&env, round_id)?;
+        let grace: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::SettlementGrace)
+            .unwrap_or(DEFAULT_SETTLEMENT_GRACE);
+        let deadline = round.close_time.checked_add(grace).ok_or(Error::Overflow)?;
+        if env.ledger().timestamp() < deadline {
+            return Err(Error::GracePeriodActive);
+        }
+
+        void(&env, round_id, &mut round, VOID_REASON_SETTLEMENT_TIMEOUT);
+        Ok(())
+    }
+
+    /// Void an unsettled round during an incident. Admin only.
+    ///
+    /// `reason` is a non-zero code recorded in `RoundData::void_reason`.
+    /// Every bettor can reclaim their full wager through `claim`.
+    pub fn void_round(env: Env, round_id: u64, reason: u32) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_admin(&env)?;
+
+        if reason == VOID_REASON_NONE {
+            return Err(Error::InvalidReason);
+        }
+        let mut round = load_unsettled_round(&env, round_id)?;
+        void(&env, round_id, &mut round, reason);
+        Ok(())
+    }
+
     /// View a round's state.
     pub fn get_round(env: Env, round_id: u64) -> Result<RoundData, Error> {
         env.storage()
@@ -504,6 +1232,44 @@ impl PricePrediction {
             .ok_or(Error::RoundNotFound)
     }
 
+    /// View the early-exit penalty in basis points; `None` when early exit
+    /// is disabled.
+    pub fn get_exit_penalty(env: Env) -> Option<i128> {
+        env.storage()
+            .instance()
+            .get::<_, Option<i128>>(&DataKey::ExitPenaltyBps)
+            .flatten()
+    }
+
+    /// View the settlement grace period in seconds.
+    pub fn get_settlement_grace(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::SettlementGrace)
+            .unwrap_or(DEFAULT_SETTLEMENT_GRACE)
+    }
+
+    /// View the continuous round cycle, if started.
+    pub fn get_chain(env: Env) -> Option<ChainConfig> {
+        env.storage().instance().get(&DataKey::Chain)
+    }
+
+    /// View the registered oracle price sources.
+    pub fn get_oracles(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Oracles)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// View the oracle staleness and deviation guards.
+    pub fn get_oracle_config(env: Env) -> Result<OracleConfig, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::OracleConfig)
+            .ok_or(Error::NotInitialized)
+    }
+
     /// View a player's bet in a round.
     pub fn get_bet(env: Env, round_id: u64, player: Address) -> Result<BetData, Error> {
         env.storage()
@@ -541,11 +1307,340 @@ fn get_token(env: &Env) -> Address {
         .expect("PricePrediction: token not set")
 }
 
-fn get_oracle(env: &Env) -> Address {
+/// Settle `round` at the aggregated `quote`, voiding it if the sources
+/// diverged. Returns the house fee retained from the pool.
+fn settle(
+    env: &Env,
+    round_id: u64,
+    round: &mut RoundData,
+    quote: &AggregatePrice,
+) -> Result<i128, Error> {
+    let close_price = quote.price;
+    let diverged = quote.deviation_bps > get_oracle_config(env).max_deviation_bps as i128;
+
+    let mut total_staked = 0i128;
+    for stake in round.pools.iter() {
+        total_staked = total_staked.checked_add(stake).ok_or(Error::Overflow)?;
+    }
+    let total_pool = total_staked.checked_add(round.forfeited).ok_or(Error::Overflow)?;
+
+    // Determine outcome
+    let outcome = if diverged {
+        OUTCOME_VOID
+    } else if !round.bounds.is_empty() {
+        bucket_index(round, close_price)?
+    } else if close_price > round.open_price {
+        OUTCOME_UP
+    } else if close_price < round.open_price {
+        OUTCOME_DOWN
+    } else {
+        OUTCOME_FLAT
+    };
+
+    // Flat and voided outcomes have no pool. Push if the winning pool is
+    // empty (includes no bets) or holds every stake (no opposing risk).
+    // Forfeited penalties stay in the round for `sweep_forfeited` on a push.
+    let winning_pool = round.pools.get(outcome).unwrap_or(0);
+    let is_push = winning_pool == 0 || winning_pool == total_staked;
+
+    let (net_pool, winning_total, fee) = if is_push {
+        (0i128, 0i128, 0i128)
+    } else {
+        let house_edge_bps: i128 =
+            env.storage().instance().get(&DataKey::HouseEdgeBps).unwrap();
+        let fee = total_pool
+            .checked_mul(house_edge_bps)
+            .and_then(|v| v.checked_div(BASIS_POINTS_DIVISOR))
+            .ok_or(Error::Overflow)?;
+        let net = total_pool.checked_sub(fee).ok_or(Error::Overflow)?;
+        (net, winning_pool, fee)
+    };
+
+    round.close_price = close_price;
+    round.settled = true;
+    round.outcome = outcome;
+    round.is_push = is_push;
+    round.net_pool = net_pool;
+    round.winning_total = winning_total;
+    if diverged {
+        round.void_reason = VOID_REASON_ORACLE_DIVERGED;
+    }
+    let round_key = DataKey::Round(round_id);
+    env.storage().persistent().set(&round_key, round);
+    env.storage()
+        .persistent()
+        .extend_ttl(&round_key, PERSISTENT_BUMP_LEDGERS, PERSISTENT_BUMP_LEDGERS);
+
+    if diverged {
+        PriceSourcesDiverged {
+            round_id,
+            median_price: close_price,
+            deviation_bps: quote.deviation_bps,
+        }
+        .publish(env);
+    }
+    RoundSettled { round_id, close_price, outcome, is_push, net_pool }.publish(env);
+    Ok(fee)
+}
+
+/// Load a round that still accepts position changes.
+fn load_open_round(env: &Env, round_id: u64) -> Result<RoundData, Error> {
+    let round = load_unsettled_round(env, round_id)?;
+    if env.ledger().timestamp() >= round.lock_time {
+        return Err(Error::RoundClosed);
+    }
+    Ok(round)
+}
+
+fn load_unsettled_round(env: &Env, round_id: u64) -> Result<RoundData, Error> {
+    let round: RoundData = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Round(round_id))
+        .ok_or(Error::RoundNotFound)?;
+    if round.settled {
+        return Err(Error::AlreadySettled);
+    }
+    Ok(round)
+}
+
+/// Close `round` as a full refund without consulting the oracle.
+fn void(env: &Env, round_id: u64, round: &mut RoundData, reason: u32) {
+    round.settled = true;
+    round.outcome = OUTCOME_VOID;
+    round.is_push = true;
+    round.net_pool = 0;
+    round.winning_total = 0;
+    round.void_reason = reason;
+    let round_key = DataKey::Round(round_id);
+    env.storage().persistent().set(&round_key, round);
+    env.storage()
+        .persistent()
+        .extend_ttl(&round_key, PERSISTENT_BUMP_LEDGERS, PERSISTENT_BUMP_LEDGERS);
+
+    RoundVoided { round_id, reason }.publish(env);
+}
+
+/// Aggregate the opening price of a manually opened round, rejecting
+/// diverging sources.
+fn opening_price(env: &Env, asset: &Symbol) -> Result<i128, Error> {
+    let quote = aggregate_price(env, asset)?;
+    if quote.deviation_bps > get_oracle_config(env).max_deviation_bps as i128 {
+        return Err(Error::PriceDeviationTooHigh);
+    }
+    Ok(quote.price)
+}
+
+/// Open the next chained round for bets until `now + interval`. Its open
+/// price is set when `execute_round` locks it.
+fn open_chain_round(
+    env: &Env,
+    round_id: u64,
+    asset: &Symbol,
+    interval: u64,
+) -> Result<(), Error> {
+    let now = env.ledger().timestamp();
+    let lock_time = now.checked_add(interval).ok_or(Error::Overflow)?;
+    let close_time = lock_time.checked_add(interval).ok_or(Error::Overflow)?;
+    open_round(env, round_id, asset.clone(), 0, lock_time, close_time, Vec::new(env))?;
+
+    ChainRoundOpened { round_id, asset: asset.clone(), lock_time }.publish(env);
+    Ok(())
+}
+
+/// Validate timing and store a new round. Binary markets pass empty
+/// `bounds`.
+fn open_round(
+    env: &Env,
+    round_id: u64,
+    asset: Symbol,
+    open_price: i128,
+    lock_time: u64,
+    close_time: u64,
+    bounds: Vec<i128>,
+) -> Result<RoundData, Error> {
+    if lock_time <= env.ledger().timestamp() {
+        return Err(Error::InvalidCloseTime);
+    }
+
+    let round_key = DataKey::Round(round_id);
+    if env.storage().persistent().has(&round_key) {
+        return Err(Error::RoundAlreadyExists);
+    }
+
+    let outcomes = if bounds.is_empty() { 2 } else { bounds.len() + 1 };
+    let mut pools = Vec::new(env);
+    for _ in 0..outcomes {
+        pools.push_back(0i128);
+    }
+
+    let round = RoundData {
+        asset,
+        open_price,
+        close_price: 0,
+        lock_time,
+        close_time,
+        total_up: 0,
+        total_down: 0,
+        bounds,
+        pools,
+        forfeited: 0,
+        settled: false,
+        outcome: 0,
+        is_push: false,
+        net_pool: 0,
+        winning_total: 0,
+        void_reason: VOID_REASON_NONE,
+    };
+    env.storage().persistent().set(&round_key, &round);
+    env.storage()
+        .persistent()
+        .extend_ttl(&round_key, PERSISTENT_BUMP_LEDGERS, PERSISTENT_BUMP_LEDGERS);
+    Ok(round)
+}
+
+/// Add `delta` (negative to withdraw) to the pool for `outcome`, keeping
+/// the binary mirrors in sync.
+fn adjust_stake(round: &mut RoundData, outcome: u32, delta: i128) -> Result<(), Error> {
+    let pool = round.pools.get(outcome).ok_or(Error::InvalidDirection)?;
+    let pool = pool.checked_add(delta).ok_or(Error::Overflow)?;
+    if pool < 0 {
+        return Err(Error::Overflow);
+    }
+    round.pools.set(outcome, pool);
+    if round.bounds.is_empty() {
+        round.total_up = round.pools.get(DIRECTION_UP).unwrap_or(0);
+        round.total_down = round.pools.get(DIRECTION_DOWN).unwrap_or(0);
+    }
+    Ok(())
+}
+
+/// Index of the bucket `close_price` falls in. Compares
+/// `(close - open) * 10000` against `bound * open` so no precision is lost
+/// to rounding the percentage change.
+fn bucket_index(round: &RoundData, close_price: i128) -> Result<u32, Error> {
+    let change = close_price
+        .checked_sub(round.open_price)
+        .and_then(|v| v.checked_mul(BASIS_POINTS_DIVISOR))
+        .ok_or(Error::Overflow)?;
+    let mut index = 0u32;
+    for bound in round.bounds.iter() {
+        let threshold = bound.checked_mul(round.open_price).ok_or(Error::Overflow)?;
+        if change < threshold {
+            break;
+        }
+        index += 1;
+    }
+    Ok(index)
+}
+
+fn get_oracles(env: &Env) -> Vec<Address> {
     env.storage()
         .instance()
-        .get(&DataKey::OracleContract)
-        .expect("PricePrediction: oracle not set")
+        .get(&DataKey::Oracles)
+        .expect("PricePrediction: oracles not set")
+}
+
+fn get_oracle_config(env: &Env) -> OracleConfig {
+    env.storage()
+        .instance()
+        .get(&DataKey::OracleConfig)
+        .expect("PricePrediction: oracle config not set")
+}
+
+/// Median of the accepted oracle prices and the largest distance of any
+/// accepted price from it, in basis points of the median.
+struct AggregatePrice {
+    price: i128,
+    deviation_bps: i128,
+}
+
+/// Query every registered oracle for `asset` and aggregate the answers.
+///
+/// Sources that fail, have no price for the asset, report a non-positive
+/// price, use unsupported decimals, are older than `max_price_age` or are
+/// dated in the future are skipped. At least `min_sources`
+/// prices must survive; when none do the error reflects why (`StalePrice`
+/// if any source was stale, otherwise `InvalidPrice`).
+fn aggregate_price(env: &Env, asset: &Symbol) -> Result<AggregatePrice, Error> {
+    let config = get_oracle_config(env);
+    let now = env.ledger().timestamp();
+
+    let mut prices = [0i128; MAX_ORACLES as usize];
+    let mut count = 0usize;
+    let mut saw_stale = false;
+    let sep40_asset = Asset::Other(asset.clone());
+    for oracle in get_oracles(env).iter() {
+        let client = OracleClient::new(env, &oracle);
+        let data = match client.try_lastprice(&sep40_asset) {
+            Ok(Ok(Some(data))) => data,
+            _ => continue,
+        };
+        if data.timestamp > now {
+            continue;
+        }
+        if now - data.timestamp > config.max_price_age {
+            saw_stale = true;
+            continue;
+        }
+        if data.price <= 0 {
+            continue;
+        }
+        let decimals = match client.try_decimals() {
+            Ok(Ok(decimals)) => decimals,
+            _ => continue,
+        };
+        if let Some(price) = normalize_price(data.price, decimals) {
+            prices[count] = price;
+            count += 1;
+        }
+    }
+
+    if count == 0 {
+        return Err(if saw_stale { Error::StalePrice } else { Error::InvalidPrice });
+    }
+    if (count as u32) < config.min_sources {
+        return Err(Error::InsufficientSources);
+    }
+
+    let accepted = &mut prices[..count];
+    accepted.sort_unstable();
+    let mid = count / 2;
+    let median = if count.is_multiple_of(2) {
+        accepted[mid - 1]
+            .checked_add(accepted[mid])
+            .map(|v| v / 2)
+            .ok_or(Error::Overflow)?
+    } else {
+        accepted[mid]
+    };
+    if median <= 0 {
+        return Err(Error::InvalidPrice);
+    }
+
+    let mut deviation_bps = 0i128;
+    for price in accepted.iter() {
+        let bps = (price - median)
+            .abs()
+            .checked_mul(BASIS_POINTS_DIVISOR)
+            .map(|v| v / median)
+            .ok_or(Error::Overflow)?;
+        if bps > deviation_bps {
+            deviation_bps = bps;
+        }
+    }
+
+    Ok(AggregatePrice { price: median, deviation_bps })
+}
+
+/// Rescale `price` from `decimals` to `PRICE_DECIMALS`. Returns `None` when
+/// the scale factor or the result does not fit in an `i128`.
+fn normalize_price(price: i128, decimals: u32) -> Option<i128> {
+    if decimals <= PRICE_DECIMALS {
+        price.checked_mul(10i128.checked_pow(PRICE_DECIMALS - decimals)?)
+    } else {
+        price.checked_div(10i128.checked_pow(decimals - PRICE_DECIMALS)?)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -9,7 +9,7 @@ use soroban_sdk::{
 };
 
 // -------------------------------------------------------------------
-// Mock Oracle Contract
+// Mock Oracle Contract (SEP-40)
 // -------------------------------------------------------------------
 
 #[contract]
@@ -18,21 +18,58 @@ pub struct MockOracle;
 #[contracttype]
 pub enum OracleKey {
     Price(Symbol),
+    Timestamp(Symbol),
+    Decimals,
 }
 
 #[contractimpl]
 impl MockOracle {
+    /// Live price: reported with the current ledger timestamp.
     pub fn set_price(env: Env, asset: Symbol, price: i128) {
+        env.storage()
+            .persistent()
+            .remove(&OracleKey::Timestamp(asset.clone()));
         env.storage()
             .persistent()
             .set(&OracleKey::Price(asset), &price);
     }
 
-    pub fn get_price(env: Env, asset: Symbol) -> i128 {
+    /// Price reported with an explicit timestamp.
+    pub fn set_price_at(env: Env, asset: Symbol, price: i128, timestamp: u64) {
+        env.storage()
+            .persistent()
+            .set(&OracleKey::Price(asset.clone()), &price);
         env.storage()
             .persistent()
-            .get(&OracleKey::Price(asset))
-            .unwrap_or(0)
+            .set(&OracleKey::Timestamp(asset), &timestamp);
+    }
+
+    pub fn set_decimals(env: Env, decimals: u32) {
+        env.storage().instance().set(&OracleKey::Decimals, &decimals);
+    }
+
+    pub fn lastprice(env: Env, asset: Asset) -> Option<PriceData> {
+        let symbol = match asset {
+            Asset::Other(symbol) => symbol,
+            Asset::Stellar(_) => return None,
+        };
+        let price: i128 = env
+            .storage()
+            .persistent()
+            .get(&OracleKey::Price(symbol.clone()))?;
+        let timestamp = env
+            .storage()
+            .persistent()
+            .get(&OracleKey::Timestamp(symbol))
+            .unwrap_or(env.ledger().timestamp());
+        Some(PriceData { price, timestamp })
+    }
+
+    pub fn decimals(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&OracleKey::Decimals)
+            .unwrap_or(PRICE_DECIMALS)
     }
 }
 
@@ -894,3 +931,1080 @@ fn test_place_prediction_on_settled_round_rejected() {
     let result = s.client.try_place_prediction(&player, &1u64, &DIRECTION_UP, &100);
     assert!(result.is_err());
 }
+
+// -------------------------------------------------------------------
+// 31. Oracle hardening - staleness, median and deviation guards
+// -------------------------------------------------------------------
+
+fn add_oracle<'a>(env: &'a Env, s: &Setup, price: i128) -> MockOracleClient<'a> {
+    let oracle_id = env.register(MockOracle, ());
+    let oracle = MockOracleClient::new(env, &oracle_id);
+    oracle.set_price(&btc(env), &price);
+    s.client.add_oracle(&oracle_id);
+    oracle
+}
+
+#[test]
+fn test_open_market_stale_price_rejected() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    // Reported at t=600, now is 1000, max age is 300
+    s.oracle_client.set_price_at(&btc(&env), &50_000, &600u64);
+
+    let result = s.client.try_open_market(&1u64, &btc(&env), &2000u64);
+    assert_eq!(result, Err(Ok(Error::StalePrice)));
+
+    s.client.set_oracle_config(&500u64, &DEFAULT_MAX_DEVIATION_BPS, &1u32);
+    s.client.open_market(&1u64, &btc(&env), &2000u64);
+    assert_eq!(s.client.get_round(&1u64).open_price, 50_000);
+}
+
+#[test]
+fn test_open_market_normalises_decimals() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    // 5.0000000 at 7 decimals == 5 * 10^14 at PRICE_DECIMALS
+    s.oracle_client.set_decimals(&7u32);
+    s.oracle_client.set_price(&btc(&env), &50_000_000);
+    s.client.open_market(&1u64, &btc(&env), &2000u64);
+    assert_eq!(s.client.get_round(&1u64).open_price, 500_000_000_000_000);
+}
+
+#[test]
+fn test_open_market_uses_median_of_sources() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    add_oracle(&env, &s, 50_100);
+    add_oracle(&env, &s, 49_950);
+    assert_eq!(s.client.get_oracles().len(), 3);
+
+    s.client.open_market(&1u64, &btc(&env), &2000u64);
+    assert_eq!(s.client.get_round(&1u64).open_price, 50_000);
+}
+
+#[test]
+fn test_open_market_skips_stale_source() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let lagging = add_oracle(&env, &s, 10_000);
+    lagging.set_price_at(&btc(&env), &10_000, &100u64);
+
+    s.client.open_market(&1u64, &btc(&env), &2000u64);
+    assert_eq!(s.client.get_round(&1u64).open_price, 50_000);
+
+    // Requiring both sources fails while one is stale
+    s.client.set_oracle_config(&DEFAULT_MAX_PRICE_AGE, &DEFAULT_MAX_DEVIATION_BPS, &2u32);
+    let result = s.client.try_open_market(&2u64, &btc(&env), &2000u64);
+    assert_eq!(result, Err(Ok(Error::InsufficientSources)));
+}
+
+#[test]
+fn test_open_market_future_dated_price_rejected() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    // Reported at t=1200 while now is 1000: not fresh, just wrong
+    s.oracle_client.set_price_at(&btc(&env), &50_000, &1200u64);
+    let result = s.client.try_open_market(&1u64, &btc(&env), &2000u64);
+    assert_eq!(result, Err(Ok(Error::InvalidPrice)));
+
+    // A second, honest source is used on its own
+    add_oracle(&env, &s, 51_000);
+    s.client.open_market(&1u64, &btc(&env), &2000u64);
+    assert_eq!(s.client.get_round(&1u64).open_price, 51_000);
+}
+
+#[test]
+fn test_open_market_divergent_sources_rejected() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    // 52_000 is 4% away from 50_000, above the 1% default threshold
+    add_oracle(&env, &s, 52_000);
+
+    let result = s.client.try_open_market(&1u64, &btc(&env), &2000u64);
+    assert_eq!(result, Err(Ok(Error::PriceDeviationTooHigh)));
+}
+
+#[test]
+fn test_settle_divergent_sources_voids_round() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let second = add_oracle(&env, &s, 50_000);
+    let third = add_oracle(&env, &s, 50_000);
+
+    let player_a = Address::generate(&env);
+    let player_b = Address::generate(&env);
+    s.token_sac.mint(&player_a, &5000);
+    s.token_sac.mint(&player_b, &5000);
+
+    s.client.open_market(&1u64, &btc(&env), &2000u64);
+    s.client.place_prediction(&player_a, &1u64, &DIRECTION_UP, &300);
+    s.client.place_prediction(&player_b, &1u64, &DIRECTION_DOWN, &500);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 3000;
+    });
+    s.oracle_client.set_price(&btc(&env), &55_000);
+    second.set_price(&btc(&env), &40_000);
+    third.set_price(&btc(&env), &50_000);
+
+    s.client.settle_round(&1u64);
+
+    let round = s.client.get_round(&1u64);
+    assert!(round.settled);
+    assert!(round.is_push);
+    assert_eq!(round.outcome, OUTCOME_VOID);
+    assert_eq!(round.close_price, 50_000);
+
+    s.client.claim(&player_a, &1u64);
+    s.client.claim(&player_b, &1u64);
+    assert_eq!(tc(&env, &s.token_addr).balance(&player_a), 5000);
+    assert_eq!(tc(&env, &s.token_addr).balance(&player_b), 5000);
+}
+
+#[test]
+fn test_settle_one_outlier_does_not_decide_outcome() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let second = add_oracle(&env, &s, 50_000);
+    let third = add_oracle(&env, &s, 50_000);
+    s.client.set_oracle_config(&DEFAULT_MAX_PRICE_AGE, &DEFAULT_MAX_DEVIATION_BPS, &2u32);
+
+    let player_a = Address::generate(&env);
+    let player_b = Address::generate(&env);
+    s.token_sac.mint(&player_a, &5000);
+    s.token_sac.mint(&player_b, &5000);
+
+    s.client.open_market(&1u64, &btc(&env), &2000u64);
+    s.client.place_prediction(&player_a, &1u64, &DIRECTION_UP, &300);
+    s.client.place_prediction(&player_b, &1u64, &DIRECTION_DOWN, &500);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 3000;
+    });
+    // The primary feed reports garbage; the other two agree on a drop
+    s.oracle_client.set_price(&btc(&env), &0);
+    second.set_price(&btc(&env), &49_900);
+    third.set_price(&btc(&env), &49_900);
+
+    s.client.settle_round(&1u64);
+
+    let round = s.client.get_round(&1u64);
+    assert_eq!(round.outcome, OUTCOME_DOWN);
+    assert_eq!(round.close_price, 49_900);
+}
+
+#[test]
+fn test_settle_stale_price_rejected() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    s.client.open_market(&1u64, &btc(&env), &2000u64);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 3000;
+    });
+    s.oracle_client.set_price_at(&btc(&env), &55_000, &2000u64);
+
+    let result = s.client.try_settle_round(&1u64);
+    assert_eq!(result, Err(Ok(Error::StalePrice)));
+    assert!(!s.client.get_round(&1u64).settled);
+}
+
+#[test]
+fn test_oracle_registry_management() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let primary = s.client.get_oracles().get(0).unwrap();
+    let result = s.client.try_add_oracle(&primary);
+    assert_eq!(result, Err(Ok(Error::OracleAlreadyRegistered)));
+
+    // Last source cannot be removed
+    let result = s.client.try_remove_oracle(&primary);
+    assert_eq!(result, Err(Ok(Error::InsufficientSources)));
+
+    for _ in 1..MAX_ORACLES {
+        add_oracle(&env, &s, 50_000);
+    }
+    let result = s.client.try_add_oracle(&Address::generate(&env));
+    assert_eq!(result, Err(Ok(Error::TooManyOracles)));
+
+    let result = s.client.try_remove_oracle(&Address::generate(&env));
+    assert_eq!(result, Err(Ok(Error::OracleNotRegistered)));
+
+    s.client.remove_oracle(&primary);
+    assert_eq!(s.client.get_oracles().len(), MAX_ORACLES - 1);
+    assert!(!s.client.get_oracles().contains(&primary));
+}
+
+#[test]
+fn test_set_oracle_config_validation() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let config = s.client.get_oracle_config();
+    assert_eq!(config.max_price_age, DEFAULT_MAX_PRICE_AGE);
+    assert_eq!(config.max_deviation_bps, DEFAULT_MAX_DEVIATION_BPS);
+    assert_eq!(config.min_sources, 1);
+
+    assert_eq!(
+        s.client.try_set_oracle_config(&0u64, &100u32, &1u32),
+        Err(Ok(Error::InvalidOracleConfig))
+    );
+    assert_eq!(
+        s.client.try_set_oracle_config(&60u64, &10_001u32, &1u32),
+        Err(Ok(Error::InvalidOracleConfig))
+    );
+    // Only one oracle registered
+    assert_eq!(
+        s.client.try_set_oracle_config(&60u64, &100u32, &2u32),
+        Err(Ok(Error::InvalidOracleConfig))
+    );
+
+    s.client.set_oracle_config(&60u64, &250u32, &1u32);
+    let config = s.client.get_oracle_config();
+    assert_eq!(config.max_price_age, 60);
+    assert_eq!(config.max_deviation_bps, 250);
+}
+
+// -------------------------------------------------------------------
+// 32. SEP-40 adapter
+// -------------------------------------------------------------------
+
+#[test]
+fn test_sep40_mixed_decimals_aggregate() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    // Same 50_000 quote from a 7-decimal feed and an 18-decimal feed
+    let seven = add_oracle(&env, &s, 0);
+    seven.set_decimals(&7u32);
+    seven.set_price(&btc(&env), &500_000_000_000);
+    let eighteen = add_oracle(&env, &s, 0);
+    eighteen.set_decimals(&18u32);
+    eighteen.set_price(&btc(&env), &50_000_000_000_000_000_000_000);
+    s.oracle_client.set_price(&btc(&env), &5_000_000_000_000_000_000);
+
+    s.client.open_market(&1u64, &btc(&env), &2000u64);
+    assert_eq!(s.client.get_round(&1u64).open_price, 5_000_000_000_000_000_000);
+}
+
+#[test]
+fn test_sep40_missing_asset_rejected() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    // lastprice returns None for an asset the feed does not track
+    let sol = Symbol::new(&env, "SOL");
+    assert_eq!(s.oracle_client.lastprice(&Asset::Other(sol.clone())), None);
+
+    let result = s.client.try_open_market(&1u64, &sol, &2000u64);
+    assert_eq!(result, Err(Ok(Error::InvalidPrice)));
+}
+
+// -------------------------------------------------------------------
+// 33. Bucket markets
+// -------------------------------------------------------------------
+
+/// Buckets: < -2%, -2%..0, 0..+2%, >= +2%
+fn four_buckets(env: &Env) -> Vec<i128> {
+    vec![env, -200i128, 0, 200]
+}
+
+#[test]
+fn test_open_bucket_market_happy_path() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    s.client.open_bucket_market(&1u64, &btc(&env), &2000u64, &four_buckets(&env));
+
+    let round = s.client.get_round(&1u64);
+    assert_eq!(round.open_price, 50_000);
+    assert_eq!(round.bounds, four_buckets(&env));
+    assert_eq!(round.pools, vec![&env, 0i128, 0, 0, 0]);
+}
+
+#[test]
+fn test_open_bucket_market_invalid_bounds_rejected() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let cases = [
+        vec![&env],
+        vec![&env, 0i128, 0],
+        vec![&env, 100i128, -100],
+        vec![&env, -10_000i128, 0],
+        vec![&env, -300i128, -200, -100, 0, 100, 200, 300, 400],
+    ];
+    for bounds in cases.iter() {
+        let result = s.client.try_open_bucket_market(&1u64, &btc(&env), &2000u64, bounds);
+        assert_eq!(result, Err(Ok(Error::InvalidBuckets)));
+    }
+}
+
+#[test]
+fn test_bucket_prediction_invalid_index_rejected() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let player = Address::generate(&env);
+    s.token_sac.mint(&player, &5000);
+
+    s.client.open_bucket_market(&1u64, &btc(&env), &2000u64, &four_buckets(&env));
+    s.client.place_prediction(&player, &1u64, &3u32, &100);
+
+    let other = Address::generate(&env);
+    s.token_sac.mint(&other, &5000);
+    let result = s.client.try_place_prediction(&other, &1u64, &4u32, &100);
+    assert_eq!(result, Err(Ok(Error::InvalidDirection)));
+}
+
+#[test]
+fn test_bucket_market_pari_mutuel_settlement() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let player_a = Address::generate(&env);
+    let player_b = Address::generate(&env);
+    let player_c = Address::generate(&env);
+    let player_d = Address::generate(&env);
+    for p in [&player_a, &player_b, &player_c, &player_d] {
+        s.token_sac.mint(p, &5000);
+    }
+
+    s.client.open_bucket_market(&1u64, &btc(&env), &2000u64, &four_buckets(&env));
+    s.client.place_prediction(&player_a, &1u64, &0u32, &100);
+    s.client.place_prediction(&player_b, &1u64, &1u32, &200);
+    s.client.place_prediction(&player_c, &1u64, &2u32, &300);
+    s.client.place_prediction(&player_d, &1u64, &2u32, &400);
+
+    let round = s.client.get_round(&1u64);
+    assert_eq!(round.pools, vec![&env, 100i128, 200, 700, 0]);
+    assert_eq!(round.total_up, 0);
+    assert_eq!(round.total_down, 0);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 3000;
+    });
+    s.oracle_client.set_price(&btc(&env), &50_500); // +1%
+
+    s.client.settle_round(&1u64);
+
+    let round = s.client.get_round(&1u64);
+    assert!(!round.is_push);
+    assert_eq!(round.outcome, 2);
+    // Total pool = 1000, fee = 50, net = 950
+    assert_eq!(round.net_pool, 950);
+    assert_eq!(round.winning_total, 700);
+
+    s.client.claim(&player_c, &1u64);
+    s.client.claim(&player_d, &1u64);
+    // 950 * 300 / 700 = 407, 950 * 400 / 700 = 542
+    assert_eq!(tc(&env, &s.token_addr).balance(&player_c), 5000 - 300 + 407);
+    assert_eq!(tc(&env, &s.token_addr).balance(&player_d), 5000 - 400 + 542);
+
+    let result = s.client.try_claim(&player_a, &1u64);
+    assert_eq!(result, Err(Ok(Error::NoPayout)));
+}
+
+#[test]
+fn test_bucket_boundary_belongs_to_upper_bucket() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let player_a = Address::generate(&env);
+    let player_b = Address::generate(&env);
+    s.token_sac.mint(&player_a, &5000);
+    s.token_sac.mint(&player_b, &5000);
+
+    s.client.open_bucket_market(&1u64, &btc(&env), &2000u64, &four_buckets(&env));
+    s.client.place_prediction(&player_a, &1u64, &2u32, &100);
+    s.client.place_prediction(&player_b, &1u64, &3u32, &100);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 3000;
+    });
+    s.oracle_client.set_price(&btc(&env), &51_000); // exactly +2%
+
+    s.client.settle_round(&1u64);
+    assert_eq!(s.client.get_round(&1u64).outcome, 3);
+}
+
+#[test]
+fn test_bucket_market_empty_winning_bucket_is_push() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let player_a = Address::generate(&env);
+    let player_b = Address::generate(&env);
+    s.token_sac.mint(&player_a, &5000);
+    s.token_sac.mint(&player_b, &5000);
+
+    s.client.open_bucket_market(&1u64, &btc(&env), &2000u64, &four_buckets(&env));
+    s.client.place_prediction(&player_a, &1u64, &1u32, &300);
+    s.client.place_prediction(&player_b, &1u64, &2u32, &200);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 3000;
+    });
+    s.oracle_client.set_price(&btc(&env), &45_000); // -10%, nobody staked bucket 0
+
+    s.client.settle_round(&1u64);
+
+    let round = s.client.get_round(&1u64);
+    assert_eq!(round.outcome, 0);
+    assert!(round.is_push);
+
+    s.client.claim(&player_a, &1u64);
+    s.client.claim(&player_b, &1u64);
+    assert_eq!(tc(&env, &s.token_addr).balance(&player_a), 5000);
+    assert_eq!(tc(&env, &s.token_addr).balance(&player_b), 5000);
+}
+
+// -------------------------------------------------------------------
+// 34. Chained rounds
+// -------------------------------------------------------------------
+
+fn set_time(env: &Env, timestamp: u64) {
+    env.ledger().with_mut(|li| {
+        li.timestamp = timestamp;
+    });
+}
+
+#[test]
+fn test_start_rounds_validation() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let keeper = Address::generate(&env);
+    assert_eq!(s.client.try_execute_round(&keeper), Err(Ok(Error::ChainNotStarted)));
+
+    assert_eq!(
+        s.client.try_start_rounds(&1u64, &btc(&env), &(MIN_ROUND_INTERVAL - 1), &0i128),
+        Err(Ok(Error::InvalidInterval))
+    );
+    assert_eq!(
+        s.client.try_start_rounds(&1u64, &btc(&env), &300u64, &10_001i128),
+        Err(Ok(Error::InvalidKeeperFee))
+    );
+
+    s.client.start_rounds(&1u64, &btc(&env), &300u64, &1000i128);
+    assert_eq!(
+        s.client.try_start_rounds(&10u64, &btc(&env), &300u64, &1000i128),
+        Err(Ok(Error::ChainAlreadyStarted))
+    );
+
+    let round = s.client.get_round(&1u64);
+    assert_eq!(round.open_price, 0);
+    assert_eq!(round.lock_time, 1300);
+    assert_eq!(round.close_time, 1600);
+}
+
+#[test]
+fn test_chained_rounds_full_cycle() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let player_a = Address::generate(&env);
+    let player_b = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    s.token_sac.mint(&player_a, &5000);
+    s.token_sac.mint(&player_b, &5000);
+
+    // Keeper takes 10% of the house fee
+    s.client.start_rounds(&1u64, &btc(&env), &300u64, &1000i128);
+    s.client.place_prediction(&player_a, &1u64, &DIRECTION_UP, &300);
+    s.client.place_prediction(&player_b, &1u64, &DIRECTION_DOWN, &500);
+
+    // Too early to lock round 1
+    assert_eq!(s.client.try_execute_round(&keeper), Err(Ok(Error::RoundNotClosed)));
+
+    // t=1300: lock round 1 at 50_000, open round 2
+    set_time(&env, 1300);
+    assert_eq!(s.client.execute_round(&keeper), 2);
+
+    let r1 = s.client.get_round(&1u64);
+    assert_eq!(r1.open_price, 50_000);
+    assert_eq!(r1.close_time, 1600);
+    assert!(!r1.settled);
+    assert_eq!(s.client.get_chain().unwrap().live_round, Some(1));
+
+    // Round 1 is locked, round 2 takes bets
+    let late = Address::generate(&env);
+    s.token_sac.mint(&late, &5000);
+    assert_eq!(
+        s.client.try_place_prediction(&late, &1u64, &DIRECTION_UP, &100),
+        Err(Ok(Error::RoundClosed))
+    );
+    s.client.place_prediction(&late, &2u64, &DIRECTION_UP, &100);
+    assert_eq!(s.client.try_settle_round(&1u64), Err(Ok(Error::RoundNotClosed)));
+
+    // t=1600: settle round 1 at 55_000, lock round 2, open round 3
+    set_time(&env, 1600);
+    s.oracle_client.set_price(&btc(&env), &55_000);
+    assert_eq!(s.client.execute_round(&keeper), 3);
+
+    let r1 = s.client.get_round(&1u64);
+    assert!(r1.settled);
+    assert_eq!(r1.outcome, OUTCOME_UP);
+    assert_eq!(r1.close_price, 55_000);
+    // Total pool = 800, fee = 40, net = 760, keeper gets 10% of fee
+    assert_eq!(r1.net_pool, 760);
+    assert_eq!(tc(&env, &s.token_addr).balance(&keeper), 4);
+
+    let r2 = s.client.get_round(&2u64);
+    assert_eq!(r2.open_price, 55_000);
+    assert_eq!(r2.close_time, 1900);
+
+    let chain = s.client.get_chain().unwrap();
+    assert_eq!(chain.current_round, 3);
+    assert_eq!(chain.live_round, Some(2));
+    assert_eq!(s.client.get_round(&3u64).lock_time, 1900);
+
+    s.client.claim(&player_a, &1u64);
+    assert_eq!(tc(&env, &s.token_addr).balance(&player_a), 5000 - 300 + 760);
+}
+
+#[test]
+fn test_late_execution_voids_rounds() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let player_a = Address::generate(&env);
+    let player_b = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    s.token_sac.mint(&player_a, &5000);
+    s.token_sac.mint(&player_b, &5000);
+
+    s.client.start_rounds(&1u64, &btc(&env), &300u64, &1000i128);
+    s.client.place_prediction(&player_a, &1u64, &DIRECTION_UP, &300);
+    s.client.place_prediction(&player_b, &1u64, &DIRECTION_DOWN, &500);
+
+    // Locked on time at t=1300, so round 1 closes at 1600
+    set_time(&env, 1300);
+    s.client.execute_round(&keeper);
+
+    // The keeper only comes back long after close: round 1 would otherwise
+    // settle at a price from a different window
+    set_time(&env, 1600 + MAX_EXECUTION_DELAY + 1);
+    s.oracle_client.set_price(&btc(&env), &55_000);
+    assert_eq!(s.client.execute_round(&keeper), 3);
+
+    let r1 = s.client.get_round(&1u64);
+    assert!(r1.is_push);
+    assert_eq!(r1.outcome, OUTCOME_VOID);
+    assert_eq!(r1.void_reason, VOID_REASON_EXECUTION_LATE);
+    assert_eq!(tc(&env, &s.token_addr).balance(&keeper), 0);
+
+    // Round 2 was also left far past its lock time
+    let r2 = s.client.get_round(&2u64);
+    assert_eq!(r2.void_reason, VOID_REASON_EXECUTION_LATE);
+    assert_eq!(s.client.get_chain().unwrap().live_round, None);
+
+    s.client.claim(&player_a, &1u64);
+    s.client.claim(&player_b, &1u64);
+    assert_eq!(tc(&env, &s.token_addr).balance(&player_a), 5000);
+    assert_eq!(tc(&env, &s.token_addr).balance(&player_b), 5000);
+}
+
+#[test]
+fn test_late_execution_voids_without_oracle() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let keeper = Address::generate(&env);
+    s.client.start_rounds(&1u64, &btc(&env), &300u64, &0i128);
+
+    // Within the delay a stale feed blocks execution
+    set_time(&env, 1300 + MAX_EXECUTION_DELAY);
+    s.oracle_client.set_price_at(&btc(&env), &50_000, &100u64);
+    assert_eq!(s.client.try_execute_round(&keeper), Err(Ok(Error::StalePrice)));
+
+    // Past it, the betting round is voided and the cycle moves on
+    set_time(&env, 1301 + MAX_EXECUTION_DELAY);
+    assert_eq!(s.client.execute_round(&keeper), 2);
+    assert_eq!(s.client.get_round(&1u64).void_reason, VOID_REASON_EXECUTION_LATE);
+    assert_eq!(s.client.get_round(&2u64).lock_time, 1601 + MAX_EXECUTION_DELAY);
+}
+
+#[test]
+fn test_settle_round_rejects_unlocked_chained_round() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    s.client.start_rounds(&1u64, &btc(&env), &300u64, &0i128);
+
+    // Past the estimated close time, but round 1 was never locked
+    set_time(&env, 1600);
+    assert_eq!(s.client.try_settle_round(&1u64), Err(Ok(Error::RoundNotLocked)));
+}
+
+#[test]
+fn test_chained_push_pays_no_keeper_reward() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let player = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    s.token_sac.mint(&player, &5000);
+
+    s.client.start_rounds(&1u64, &btc(&env), &300u64, &1000i128);
+    s.client.place_prediction(&player, &1u64, &DIRECTION_UP, &300);
+
+    set_time(&env, 1300);
+    s.client.execute_round(&keeper);
+    set_time(&env, 1600);
+    s.oracle_client.set_price(&btc(&env), &55_000);
+    s.client.execute_round(&keeper);
+
+    // One-sided round is a push: no fee, no reward, full refund
+    assert!(s.client.get_round(&1u64).is_push);
+    assert_eq!(tc(&env, &s.token_addr).balance(&keeper), 0);
+    s.client.claim(&player, &1u64);
+    assert_eq!(tc(&env, &s.token_addr).balance(&player), 5000);
+}
+
+#[test]
+fn test_chained_divergence_voids_live_and_betting_rounds() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let second = add_oracle(&env, &s, 50_000);
+    let keeper = Address::generate(&env);
+    let player_a = Address::generate(&env);
+    let player_b = Address::generate(&env);
+    s.token_sac.mint(&player_a, &5000);
+    s.token_sac.mint(&player_b, &5000);
+
+    s.client.start_rounds(&1u64, &btc(&env), &300u64, &1000i128);
+    s.client.place_prediction(&player_a, &1u64, &DIRECTION_UP, &300);
+    s.client.place_prediction(&player_b, &1u64, &DIRECTION_DOWN, &500);
+
+    set_time(&env, 1300);
+    s.client.execute_round(&keeper);
+    s.client.place_prediction(&player_a, &2u64, &DIRECTION_DOWN, &100);
+
+    set_time(&env, 1600);
+    s.oracle_client.set_price(&btc(&env), &55_000);
+    second.set_price(&btc(&env), &45_000);
+    assert_eq!(s.client.execute_round(&keeper), 3);
+
+    let r1 = s.client.get_round(&1u64);
+    let r2 = s.client.get_round(&2u64);
+    assert_eq!(r1.outcome, OUTCOME_VOID);
+    assert_eq!(r2.outcome, OUTCOME_VOID);
+    assert!(r1.is_push && r2.is_push);
+    assert_eq!(s.client.get_chain().unwrap().live_round, None);
+
+    s.client.claim(&player_a, &1u64);
+    s.client.claim(&player_a, &2u64);
+    assert_eq!(tc(&env, &s.token_addr).balance(&player_a), 5000);
+}
+
+// -------------------------------------------------------------------
+// 35. Cancellation and voiding
+// -------------------------------------------------------------------
+
+#[test]
+fn test_cancel_round_after_grace_refunds_all() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let player_a = Address::generate(&env);
+    let player_b = Address::generate(&env);
+    s.token_sac.mint(&player_a, &5000);
+    s.token_sac.mint(&player_b, &5000);
+
+    // Zero would let anyone cancel right at close_time
+    assert_eq!(s.client.try_set_settlement_grace(&0u64), Err(Ok(Error::InvalidGrace)));
+    assert_eq!(
+        s.client.try_set_settlement_grace(&(MIN_SETTLEMENT_GRACE - 1)),
+        Err(Ok(Error::InvalidGrace))
+    );
+    s.client.set_settlement_grace(&600u64);
+    assert_eq!(s.client.get_settlement_grace(), 600);
+
+    s.client.open_market(&1u64, &btc(&env), &2000u64);
+    s.client.place_prediction(&player_a, &1u64, &DIRECTION_UP, &300);
+    s.client.place_prediction(&player_b, &1u64, &DIRECTION_DOWN, &500);
+
+    // Oracle goes down: settlement impossible
+    set_time(&env, 2000);
+    s.oracle_client.set_price(&btc(&env), &0);
+    assert_eq!(s.client.try_settle_round(&1u64), Err(Ok(Error::InvalidPrice)));
+    assert_eq!(s.client.try_cancel_round(&1u64), Err(Ok(Error::GracePeriodActive)));
+
+    set_time(&env, 2599);
+    assert_eq!(s.client.try_cancel_round(&1u64), Err(Ok(Error::GracePeriodActive)));
+
+    set_time(&env, 2600);
+    s.client.cancel_round(&1u64);
+
+    let round = s.client.get_round(&1u64);
+    assert!(round.settled);
+    assert!(round.is_push);
+    assert_eq!(round.outcome, OUTCOME_VOID);
+    assert_eq!(round.void_reason, VOID_REASON_SETTLEMENT_TIMEOUT);
+
+    // Oracle recovers, but the round is final
+    s.oracle_client.set_price(&btc(&env), &55_000);
+    assert_eq!(s.client.try_settle_round(&1u64), Err(Ok(Error::AlreadySettled)));
+    assert_eq!(s.client.try_cancel_round(&1u64), Err(Ok(Error::AlreadySettled)));
+
+    s.client.claim(&player_a, &1u64);
+    s.client.claim(&player_b, &1u64);
+    assert_eq!(tc(&env, &s.token_addr).balance(&player_a), 5000);
+    assert_eq!(tc(&env, &s.token_addr).balance(&player_b), 5000);
+}
+
+#[test]
+fn test_default_settlement_grace() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    assert_eq!(s.client.get_settlement_grace(), DEFAULT_SETTLEMENT_GRACE);
+    s.client.open_market(&1u64, &btc(&env), &2000u64);
+
+    set_time(&env, 2000 + DEFAULT_SETTLEMENT_GRACE - 1);
+    assert_eq!(s.client.try_cancel_round(&1u64), Err(Ok(Error::GracePeriodActive)));
+    set_time(&env, 2000 + DEFAULT_SETTLEMENT_GRACE);
+    s.client.cancel_round(&1u64);
+}
+
+#[test]
+fn test_admin_void_round_with_reason() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let player = Address::generate(&env);
+    s.token_sac.mint(&player, &5000);
+
+    s.client.open_market(&1u64, &btc(&env), &2000u64);
+    s.client.place_prediction(&player, &1u64, &DIRECTION_UP, &300);
+
+    assert_eq!(
+        s.client.try_void_round(&1u64, &VOID_REASON_NONE),
+        Err(Ok(Error::InvalidReason))
+    );
+    assert_eq!(s.client.try_void_round(&99u64, &42u32), Err(Ok(Error::RoundNotFound)));
+
+    // Admin can void before close_time
+    s.client.void_round(&1u64, &42u32);
+
+    let round = s.client.get_round(&1u64);
+    assert_eq!(round.outcome, OUTCOME_VOID);
+    assert_eq!(round.void_reason, 42);
+    assert_eq!(s.client.try_void_round(&1u64, &42u32), Err(Ok(Error::AlreadySettled)));
+
+    // Betting is closed on a voided round
+    let other = Address::generate(&env);
+    s.token_sac.mint(&other, &5000);
+    assert_eq!(
+        s.client.try_place_prediction(&other, &1u64, &DIRECTION_DOWN, &100),
+        Err(Ok(Error::AlreadySettled))
+    );
+
+    s.client.claim(&player, &1u64);
+    assert_eq!(tc(&env, &s.token_addr).balance(&player), 5000);
+}
+
+#[test]
+fn test_divergence_records_void_reason() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let second = add_oracle(&env, &s, 50_000);
+    s.client.open_market(&1u64, &btc(&env), &2000u64);
+
+    set_time(&env, 3000);
+    s.oracle_client.set_price(&btc(&env), &55_000);
+    second.set_price(&btc(&env), &45_000);
+    s.client.settle_round(&1u64);
+
+    assert_eq!(s.client.get_round(&1u64).void_reason, VOID_REASON_ORACLE_DIVERGED);
+}
+
+#[test]
+fn test_voided_chained_round_does_not_stall_cycle() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let keeper = Address::generate(&env);
+    s.client.start_rounds(&1u64, &btc(&env), &300u64, &0i128);
+
+    set_time(&env, 1300);
+    s.client.execute_round(&keeper);
+
+    // Void both the live round and the betting round
+    s.client.void_round(&1u64, &7u32);
+    s.client.void_round(&2u64, &7u32);
+
+    set_time(&env, 1600);
+    assert_eq!(s.client.execute_round(&keeper), 3);
+    assert_eq!(s.client.get_round(&2u64).void_reason, 7);
+    assert_eq!(s.client.get_chain().unwrap().live_round, None);
+
+    set_time(&env, 1900);
+    assert_eq!(s.client.execute_round(&keeper), 4);
+    assert_eq!(s.client.get_chain().unwrap().live_round, Some(3));
+}
+
+// -------------------------------------------------------------------
+// 36. Position management
+// -------------------------------------------------------------------
+
+#[test]
+fn test_increase_position() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let player = Address::generate(&env);
+    s.token_sac.mint(&player, &20_000);
+
+    s.client.open_market(&1u64, &btc(&env), &2000u64);
+    assert_eq!(
+        s.client.try_increase_position(&player, &1u64, &100),
+        Err(Ok(Error::BetNotFound))
+    );
+
+    s.client.place_prediction(&player, &1u64, &DIRECTION_DOWN, &100);
+    s.client.increase_position(&player, &1u64, &250);
+
+    let bet = s.client.get_bet(&1u64, &player);
+    assert_eq!(bet.direction, DIRECTION_DOWN);
+    assert_eq!(bet.wager, 350);
+    let round = s.client.get_round(&1u64);
+    assert_eq!(round.total_down, 350);
+    assert_eq!(round.pools, vec![&env, 0i128, 350]);
+    assert_eq!(tc(&env, &s.token_addr).balance(&player), 20_000 - 350);
+
+    assert_eq!(
+        s.client.try_increase_position(&player, &1u64, &0),
+        Err(Ok(Error::InvalidAmount))
+    );
+    // max_wager = 10_000 applies to the combined position
+    assert_eq!(
+        s.client.try_increase_position(&player, &1u64, &9_651),
+        Err(Ok(Error::WagerTooHigh))
+    );
+
+    set_time(&env, 2000);
+    assert_eq!(
+        s.client.try_increase_position(&player, &1u64, &100),
+        Err(Ok(Error::RoundClosed))
+    );
+}
+
+#[test]
+fn test_exit_position_disabled_by_default() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let player = Address::generate(&env);
+    s.token_sac.mint(&player, &5000);
+
+    s.client.open_market(&1u64, &btc(&env), &2000u64);
+    s.client.place_prediction(&player, &1u64, &DIRECTION_UP, &100);
+
+    assert_eq!(s.client.get_exit_penalty(), None);
+    assert_eq!(
+        s.client.try_exit_position(&player, &1u64),
+        Err(Ok(Error::EarlyExitDisabled))
+    );
+
+    assert_eq!(
+        s.client.try_set_exit_penalty(&Some(10_001i128)),
+        Err(Ok(Error::InvalidAmount))
+    );
+    s.client.set_exit_penalty(&Some(1000i128));
+    assert_eq!(s.client.get_exit_penalty(), Some(1000));
+    s.client.set_exit_penalty(&None);
+    assert_eq!(s.client.get_exit_penalty(), None);
+}
+
+#[test]
+fn test_exit_position_penalty_feeds_winners() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let player_a = Address::generate(&env);
+    let player_b = Address::generate(&env);
+    let player_c = Address::generate(&env);
+    for p in [&player_a, &player_b, &player_c] {
+        s.token_sac.mint(p, &5000);
+    }
+
+    // 10% exit penalty
+    s.client.set_exit_penalty(&Some(1000i128));
+
+    s.client.open_market(&1u64, &btc(&env), &2000u64);
+    s.client.place_prediction(&player_a, &1u64, &DIRECTION_UP, &500);
+    s.client.place_prediction(&player_b, &1u64, &DIRECTION_DOWN, &500);
+    s.client.place_prediction(&player_c, &1u64, &DIRECTION_UP, &200);
+
+    assert_eq!(s.client.exit_position(&player_a, &1u64), 450);
+    assert_eq!(tc(&env, &s.token_addr).balance(&player_a), 4950);
+    assert_eq!(s.client.try_get_bet(&1u64, &player_a), Err(Ok(Error::BetNotFound)));
+
+    let round = s.client.get_round(&1u64);
+    assert_eq!(round.total_up, 200);
+    assert_eq!(round.total_down, 500);
+    assert_eq!(round.pools, vec![&env, 200i128, 500]);
+    assert_eq!(round.forfeited, 50);
+
+    set_time(&env, 3000);
+    s.oracle_client.set_price(&btc(&env), &55_000);
+    s.client.settle_round(&1u64);
+
+    // Total pool = 700 + 50 forfeited = 750, fee = 37, net = 713
+    let round = s.client.get_round(&1u64);
+    assert_eq!(round.net_pool, 713);
+    assert_eq!(round.winning_total, 200);
+
+    s.client.claim(&player_c, &1u64);
+    assert_eq!(tc(&env, &s.token_addr).balance(&player_c), 5000 - 200 + 713);
+}
+
+#[test]
+fn test_exit_position_then_rebet_other_side() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let player = Address::generate(&env);
+    s.token_sac.mint(&player, &5000);
+    s.client.set_exit_penalty(&Some(0i128));
+
+    s.client.open_market(&1u64, &btc(&env), &2000u64);
+    s.client.place_prediction(&player, &1u64, &DIRECTION_UP, &300);
+    assert_eq!(s.client.exit_position(&player, &1u64), 300);
+    s.client.place_prediction(&player, &1u64, &DIRECTION_DOWN, &200);
+
+    let round = s.client.get_round(&1u64);
+    assert_eq!(round.total_up, 0);
+    assert_eq!(round.total_down, 200);
+    assert_eq!(round.forfeited, 0);
+    assert_eq!(tc(&env, &s.token_addr).balance(&player), 4800);
+
+    set_time(&env, 2000);
+    assert_eq!(s.client.try_exit_position(&player, &1u64), Err(Ok(Error::RoundClosed)));
+}
+
+#[test]
+fn test_push_after_exit_sweeps_forfeited() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let player_a = Address::generate(&env);
+    let player_b = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    s.token_sac.mint(&player_a, &5000);
+    s.token_sac.mint(&player_b, &5000);
+
+    s.client.set_exit_penalty(&Some(1000i128));
+    s.client.open_market(&1u64, &btc(&env), &2000u64);
+    s.client.place_prediction(&player_a, &1u64, &DIRECTION_UP, &500);
+    s.client.place_prediction(&player_b, &1u64, &DIRECTION_DOWN, &500);
+    assert_eq!(s.client.exit_position(&player_a, &1u64), 450);
+
+    // Nothing to sweep before settlement
+    assert_eq!(
+        s.client.try_sweep_forfeited(&1u64, &treasury),
+        Err(Ok(Error::NotSettled))
+    );
+
+    // Only DOWN has stake left, so the round is a push
+    set_time(&env, 3000);
+    s.oracle_client.set_price(&btc(&env), &45_000);
+    s.client.settle_round(&1u64);
+    let round = s.client.get_round(&1u64);
+    assert!(round.is_push);
+    assert_eq!(round.forfeited, 50);
+
+    s.client.claim(&player_b, &1u64);
+    assert_eq!(tc(&env, &s.token_addr).balance(&player_b), 5000);
+
+    assert_eq!(s.client.sweep_forfeited(&1u64, &treasury), 50);
+    assert_eq!(tc(&env, &s.token_addr).balance(&treasury), 50);
+    assert_eq!(s.client.get_round(&1u64).forfeited, 0);
+    assert_eq!(
+        s.client.try_sweep_forfeited(&1u64, &treasury),
+        Err(Ok(Error::NoPayout))
+    );
+}
+
+#[test]
+fn test_sweep_forfeited_rejected_when_winners_share_it() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let player_a = Address::generate(&env);
+    let player_b = Address::generate(&env);
+    let player_c = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    for p in [&player_a, &player_b, &player_c] {
+        s.token_sac.mint(p, &5000);
+    }
+
+    s.client.set_exit_penalty(&Some(1000i128));
+    s.client.open_market(&1u64, &btc(&env), &2000u64);
+    s.client.place_prediction(&player_a, &1u64, &DIRECTION_UP, &500);
+    s.client.place_prediction(&player_b, &1u64, &DIRECTION_DOWN, &500);
+    s.client.place_prediction(&player_c, &1u64, &DIRECTION_UP, &200);
+    s.client.exit_position(&player_a, &1u64);
+
+    set_time(&env, 3000);
+    s.oracle_client.set_price(&btc(&env), &55_000);
+    s.client.settle_round(&1u64);
+    assert!(!s.client.get_round(&1u64).is_push);
+
+    // The penalty is already part of the winners' net pool
+    assert_eq!(
+        s.client.try_sweep_forfeited(&1u64, &treasury),
+        Err(Ok(Error::NoPayout))
+    );
+}
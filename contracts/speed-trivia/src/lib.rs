@@ -2,16 +2,55 @@
 //!
 //! Players compete to answer a question as quickly as possible.
 //! Rewards are shared among correct answers submitted before the deadline.
-//! The speed of submission (captured via timestamp) can be used to rank or reward players.
+//!
+//! Submission time comes from the ledger, never the caller. Correct answers
+//! are ranked by arrival (ledger sequence, then the order submissions were
+//! applied within that ledger), and the pot is split by a configurable
+//! weight per rank, or equally when no weights are set.
+//!
+//! Arrival is the only speed signal available: the answer is hidden in a
+//! commitment until after the deadline, and every reveal happens once the
+//! key is public, so ranking by reveal would only measure who reveals first.
+//! The cost is that ranks go to early commitments whether or not the player
+//! knew the answer. A player may commit a blind guess at once, or commit
+//! different answers from several addresses and keep the rank of whichever
+//! is correct. So ranked questions must charge an entry fee, every correct
+//! answer gets an equal base share, and only a bonus is split by rank,
+//! capped so the fastest answer earns at most one entry fee over the base:
+//! an extra address costs at least as much as the speed bonus it can win.
+//!
+//! Answers use salted commit-reveal: players submit
+//! `sha256(answer || salt || player)` before the deadline, the admin reveals
+//! the answer key (committed as `sha256(answer || salt)`) after it, and
+//! players then reveal their own answers within the reveal window.
+//!
+//! Winners must claim before the claim deadline; afterwards the admin can
+//! sweep the unclaimed part of the reservation back to the prize pool.
+//!
+//! Questions may charge an entry fee, debited through the balance contract
+//! at submit. Fee management charges the game's rake on the entry pot when
+//! the round is finalized; the rest is added to the seeded reward before it
+//! is split among winners.
 
 #![no_std]
 #![allow(unexpected_cfgs)]
 
 use soroban_sdk::{
     contract, contractclient, contracterror, contractevent, contractimpl, contracttype,
-    symbol_short, Address, Bytes, BytesN, Env, Symbol,
+    symbol_short, xdr::ToXdr, Address, Bytes, BytesN, Env, Symbol, Vec,
 };
 
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+/// Default time players have to reveal after the answer key is published.
+pub const DEFAULT_REVEAL_WINDOW: u64 = 600;
+/// Default time winners have to claim after a round is finalized.
+pub const DEFAULT_CLAIM_WINDOW: u64 = 604_800;
+/// Maximum number of paid ranks in a payout curve.
+pub const MAX_RANK_WEIGHTS: u32 = 10;
+
 // ---------------------------------------------------------------------------
 // External contract clients
 // ---------------------------------------------------------------------------
@@ -30,6 +69,17 @@ pub trait UserBalanceContract {
     fn balance_of(env: Env, user: Address) -> i128;
 }
 
+#[contractclient(name = "FeeManagementClient")]
+pub trait FeeManagementContract {
+    fn charge_fee_with_id(
+        env: Env,
+        game_id: Symbol,
+        amount: i128,
+        charge_id: u64,
+        token: Option<Address>,
+    ) -> i128;
+}
+
 // ---------------------------------------------------------------------------
 // Errors
 // ---------------------------------------------------------------------------
@@ -52,6 +102,19 @@ pub enum Error {
     Overflow = 12,
     InvalidDeadline = 13,
     PastDeadline = 14,
+    InvalidReveal = 15,
+    AlreadyRevealed = 16,
+    RevealWindowOpen = 17,
+    RevealWindowClosed = 18,
+    NotRevealPhase = 19,
+    SubmissionNotFound = 20,
+    DeadlineNotReached = 21,
+    ClaimWindowOpen = 22,
+    ClaimWindowClosed = 23,
+    AlreadySwept = 24,
+    FeeNotConfigured = 25,
+    InvalidWeights = 26,
+    WeightsNeedEntryFee = 27,
 }
 
 // ---------------------------------------------------------------------------
@@ -63,27 +126,69 @@ pub enum Error {
 pub enum RoundStatus {
     Open = 0,
     Finalized = 1,
+    Revealing = 2,
+    /// Finalized and past the claim deadline; unclaimed reward returned.
+    Swept = 3,
 }
 
 #[contracttype]
 #[derive(Clone)]
 pub struct RoundData {
+    /// `sha256(answer || salt)`, published by the admin at open.
     pub answer_commitment: BytesN<32>,
+    /// The answer revealed after the deadline; empty until then.
+    pub revealed_answer: Bytes,
+    /// Seeded reward reserved in the prize pool; may be 0 for paid rounds.
     pub reward_amount: i128,
+    /// Entry fee debited from each player at submit; 0 for free rounds.
+    pub entry_fee: i128,
+    /// Entry fees collected so far.
+    pub entry_pot: i128,
+    /// Fee management's cut of the entry pot, charged at finalize.
+    pub rake: i128,
+    /// Payout weights for 1st, 2nd, ... correct answers, fixed at open.
+    /// Empty splits the pot equally among all winners.
+    pub rank_weights: Vec<u32>,
+    /// Payout for every winner in equal-split rounds; in weighted rounds,
+    /// the base share of winners past the last paid rank.
     pub payout_per_winner: i128,
+    /// Weighted rounds only: base share plus rank bonus, per rank.
+    pub rank_payouts: Vec<i128>,
     pub winner_count: u32,
+    /// Submissions so far; the next one gets this arrival index.
+    pub submission_count: u32,
+    /// Arrival indexes of correct answers, fastest first.
+    pub correct_order: Vec<u32>,
     pub status: RoundStatus,
     pub deadline: u64,
     pub opened_at: u64,
+    pub reveal_deadline: u64,
+    /// Last timestamp at which rewards can be claimed; set at finalize.
+    pub claim_deadline: u64,
+    /// Total paid out to winners so far.
+    pub claimed_total: i128,
+    /// Total released back to the prize pool so far.
+    pub released: i128,
+    /// Entry funds no winner earned, credited to the admin so far.
+    pub forwarded: i128,
+    /// Part of `claimed_total` paid out of the prize pool reservation.
+    pub pool_paid: i128,
 }
 
 #[contracttype]
 #[derive(Clone)]
 pub struct Submission {
-    pub answer_hash: BytesN<32>,
+    /// `sha256(answer || salt || player)`.
+    pub commitment: BytesN<32>,
+    pub revealed: bool,
     pub correct: bool,
     pub claimed: bool,
+    /// Ledger timestamp at submission.
     pub timestamp: u64,
+    /// Ledger sequence at submission.
+    pub ledger: u32,
+    /// Arrival index within the round; breaks ties inside one ledger.
+    pub index: u32,
 }
 
 #[contracttype]
@@ -91,6 +196,11 @@ pub enum DataKey {
     Admin,
     PrizePoolContract,
     BalanceContract,
+    RevealWindow,
+    ClaimWindow,
+    FeeContract,
+    FeeGameId,
+    RankWeights,
     Round(u64),
     Submission(u64, Address),
 }
@@ -104,6 +214,7 @@ pub struct QuestionOpened {
     #[topic]
     pub round_id: u64,
     pub reward_amount: i128,
+    pub entry_fee: i128,
     pub deadline: u64,
 }
 
@@ -112,8 +223,25 @@ pub struct AnswerSubmitted {
     #[topic]
     pub round_id: u64,
     pub player: Address,
-    pub correct: bool,
     pub timestamp: u64,
+    pub ledger: u32,
+    pub index: u32,
+}
+
+#[contractevent]
+pub struct AnswerKeyRevealed {
+    #[topic]
+    pub round_id: u64,
+    pub answer: Bytes,
+    pub reveal_deadline: u64,
+}
+
+#[contractevent]
+pub struct AnswerRevealed {
+    #[topic]
+    pub round_id: u64,
+    pub player: Address,
+    pub correct: bool,
 }
 
 #[contractevent]
@@ -122,6 +250,11 @@ pub struct RoundFinalized {
     pub round_id: u64,
     pub winners: u32,
     pub payout_per_winner: i128,
+    pub rank_payouts: Vec<i128>,
+    pub released: i128,
+    pub rake: i128,
+    pub forwarded: i128,
+    pub claim_deadline: u64,
 }
 
 #[contractevent]
@@ -132,6 +265,13 @@ pub struct RewardClaimed {
     pub amount: i128,
 }
 
+#[contractevent]
+pub struct UnclaimedSwept {
+    #[topic]
+    pub round_id: u64,
+    pub amount: i128,
+}
+
 // ---------------------------------------------------------------------------
 // Contract
 // ---------------------------------------------------------------------------
@@ -162,17 +302,86 @@ impl SpeedTrivia {
         Ok(())
     }
 
+    /// Set how long players have to reveal after the answer key is
+    /// published. Admin only.
+    pub fn set_reveal_window(env: Env, window: u64) -> Result<(), Error> {
+        require_admin(&env)?;
+        env.storage().instance().set(&DataKey::RevealWindow, &window);
+        Ok(())
+    }
+
+    /// Set how long winners have to claim after a round is finalized.
+    /// Admin only; applies to rounds finalized afterwards.
+    pub fn set_claim_window(env: Env, window: u64) -> Result<(), Error> {
+        require_admin(&env)?;
+        env.storage().instance().set(&DataKey::ClaimWindow, &window);
+        Ok(())
+    }
+
+    /// Set the fee management contract and the game id under which it
+    /// charges the rake on entry fees. Admin only; the rake is whatever that
+    /// game's fee config says when a question is finalized.
+    pub fn set_fee_config(env: Env, fee_contract: Address, game_id: Symbol) -> Result<(), Error> {
+        require_admin(&env)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::FeeContract, &fee_contract);
+        env.storage().instance().set(&DataKey::FeeGameId, &game_id);
+        Ok(())
+    }
+
+    /// Set the payout curve: `weights[i]` is the relative share of the
+    /// `i + 1`-th fastest correct answer in the speed bonus, which is paid on
+    /// top of every winner's equal base share. Weights must be positive and
+    /// non-increasing, with at most `MAX_RANK_WEIGHTS` ranks; slower correct
+    /// answers get the base share only. An empty list restores the equal
+    /// split. Admin only; applies to questions opened afterwards, which must
+    /// charge an entry fee.
+    pub fn set_rank_weights(env: Env, weights: Vec<u32>) -> Result<(), Error> {
+        require_admin(&env)?;
+        if weights.len() > MAX_RANK_WEIGHTS {
+            return Err(Error::InvalidWeights);
+        }
+        let mut prev = u32::MAX;
+        for weight in weights.iter() {
+            if weight == 0 || weight > prev {
+                return Err(Error::InvalidWeights);
+            }
+            prev = weight;
+        }
+        env.storage().instance().set(&DataKey::RankWeights, &weights);
+        Ok(())
+    }
+
     /// Open a new trivia question.
     /// Added `reward_amount` to facilitate prize pool reservation.
+    /// `answer_commitment` must be `sha256(answer || salt)`; the salt stays
+    /// secret until `close_question`.
+    /// `entry_fee` is charged to every player; it or `reward_amount` may be
+    /// 0, but not both. Paid questions need the fee config to be set. While
+    /// rank weights are set, free questions fail with `WeightsNeedEntryFee`,
+    /// since blind early commitments would cost nothing.
     pub fn open_question(
         env: Env,
         round_id: u64,
         answer_commitment: BytesN<32>,
         deadline: u64,
         reward_amount: i128,
+        entry_fee: i128,
     ) -> Result<(), Error> {
         let admin = require_admin(&env)?;
-        require_positive(reward_amount)?;
+        validate_funding(reward_amount, entry_fee)?;
+        if entry_fee > 0 && !env.storage().instance().has(&DataKey::FeeContract) {
+            return Err(Error::FeeNotConfigured);
+        }
+        let rank_weights: Vec<u32> = env
+            .storage()
+            .instance()
+            .get(&DataKey::RankWeights)
+            .unwrap_or_else(|| Vec::new(&env));
+        if !rank_weights.is_empty() && entry_fee == 0 {
+            return Err(Error::WeightsNeedEntryFee);
+        }
 
         let now = env.ledger().timestamp();
         if deadline <= now {
@@ -184,38 +393,56 @@ impl SpeedTrivia {
             return Err(Error::RoundAlreadyExists);
         }
 
-        let prize_pool = get_prize_pool(&env)?;
-        let pool_client = PrizePoolClient::new(&env, &prize_pool);
-        pool_client.reserve(&admin, &round_id, &reward_amount);
+        if reward_amount > 0 {
+            let prize_pool = get_prize_pool(&env)?;
+            let pool_client = PrizePoolClient::new(&env, &prize_pool);
+            pool_client.reserve(&admin, &round_id, &reward_amount);
+        }
 
         let round = RoundData {
             answer_commitment,
+            revealed_answer: Bytes::new(&env),
             reward_amount,
+            entry_fee,
+            entry_pot: 0,
+            rake: 0,
+            rank_weights,
             payout_per_winner: 0,
+            rank_payouts: Vec::new(&env),
             winner_count: 0,
+            submission_count: 0,
+            correct_order: Vec::new(&env),
             status: RoundStatus::Open,
             deadline,
             opened_at: now,
+            reveal_deadline: 0,
+            claim_deadline: 0,
+            claimed_total: 0,
+            released: 0,
+            forwarded: 0,
+            pool_paid: 0,
         };
         env.storage().persistent().set(&key, &round);
 
         QuestionOpened {
             round_id,
             reward_amount,
+            entry_fee,
             deadline,
         }
         .publish(&env);
         Ok(())
     }
 
-    /// Submit an answer for a specific round.
-    /// `timestamp` is provide by the caller, verified to be within ledger bounds.
+    /// Submit an answer commitment for a specific round.
+    /// `commitment` must be `sha256(answer || salt || player_xdr)`.
+    /// The ledger timestamp and sequence are recorded as the submission time.
+    /// Paid questions debit the entry fee from the player.
     pub fn submit_answer(
         env: Env,
         player: Address,
         round_id: u64,
-        answer: Bytes,
-        timestamp: u64,
+        commitment: BytesN<32>,
     ) -> Result<(), Error> {
         require_initialized(&env)?;
         player.require_auth();
@@ -231,54 +458,74 @@ impl SpeedTrivia {
             return Err(Error::RoundClosed);
         }
 
-        let now = env.ledger().timestamp();
-        if now > round.deadline {
+        let timestamp = env.ledger().timestamp();
+        if timestamp > round.deadline {
             return Err(Error::PastDeadline);
         }
 
-        // Validate timestamp: shouldn't be too far in the future or drastically in the past
-        // For simplicity, we ensure it's not beyond current ledger time.
-        if timestamp > now {
-            return Err(Error::InvalidAmount); // Or a more specific error
-        }
-
         let submission_key = DataKey::Submission(round_id, player.clone());
         if env.storage().persistent().has(&submission_key) {
             return Err(Error::AlreadySubmitted);
         }
 
-        let answer_hash: BytesN<32> = env.crypto().sha256(&answer).into();
-        let correct = answer_hash == round.answer_commitment;
-
-        if correct {
-            round.winner_count = round
-                .winner_count
-                .checked_add(1)
+        if round.entry_fee > 0 {
+            let balance_contract = get_balance_contract(&env)?;
+            let balance_client = BalanceClient::new(&env, &balance_contract);
+            let contract_addr = env.current_contract_address();
+            balance_client.debit(
+                &contract_addr,
+                &player,
+                &round.entry_fee,
+                &symbol_short!("entry"),
+            );
+            balance_client.credit(
+                &contract_addr,
+                &contract_addr,
+                &round.entry_fee,
+                &symbol_short!("entry"),
+            );
+            round.entry_pot = round
+                .entry_pot
+                .checked_add(round.entry_fee)
                 .ok_or(Error::Overflow)?;
-            env.storage().persistent().set(&key, &round);
         }
 
+        let index = round.submission_count;
+        round.submission_count = index.checked_add(1).ok_or(Error::Overflow)?;
+        env.storage().persistent().set(&key, &round);
+
+        let ledger = env.ledger().sequence();
         let submission = Submission {
-            answer_hash,
-            correct,
+            commitment,
+            revealed: false,
+            correct: false,
             claimed: false,
             timestamp,
+            ledger,
+            index,
         };
         env.storage().persistent().set(&submission_key, &submission);
 
         AnswerSubmitted {
             round_id,
             player,
-            correct,
             timestamp,
+            ledger,
+            index,
         }
         .publish(&env);
         Ok(())
     }
 
-    /// Finalize the round, closing it and calculating the payout per winner.
-    pub fn finalize_round(env: Env, round_id: u64) -> Result<(), Error> {
-        let admin = require_admin(&env)?;
+    /// Publish the answer key once the deadline has passed. Admin only.
+    /// `sha256(answer || salt)` must match the commitment given at open.
+    pub fn close_question(
+        env: Env,
+        round_id: u64,
+        answer: Bytes,
+        salt: Bytes,
+    ) -> Result<(), Error> {
+        require_admin(&env)?;
         let key = DataKey::Round(round_id);
         let mut round: RoundData = env
             .storage()
@@ -289,52 +536,211 @@ impl SpeedTrivia {
         if round.status != RoundStatus::Open {
             return Err(Error::RoundClosed);
         }
+        let now = env.ledger().timestamp();
+        if now <= round.deadline {
+            return Err(Error::DeadlineNotReached);
+        }
+        if answer_commitment(&env, &answer, &salt) != round.answer_commitment {
+            return Err(Error::InvalidReveal);
+        }
+
+        let window: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::RevealWindow)
+            .unwrap_or(DEFAULT_REVEAL_WINDOW);
+        let reveal_deadline = now.checked_add(window).ok_or(Error::Overflow)?;
+
+        round.status = RoundStatus::Revealing;
+        round.reveal_deadline = reveal_deadline;
+        round.revealed_answer = answer.clone();
+        env.storage().persistent().set(&key, &round);
+
+        AnswerKeyRevealed {
+            round_id,
+            answer,
+            reveal_deadline,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Reveal a committed answer, up to and including `reveal_deadline`.
+    /// Returns whether it matches the answer key.
+    pub fn reveal_answer(
+        env: Env,
+        player: Address,
+        round_id: u64,
+        answer: Bytes,
+        salt: Bytes,
+    ) -> Result<bool, Error> {
+        require_initialized(&env)?;
+        player.require_auth();
+
+        let key = DataKey::Round(round_id);
+        let mut round: RoundData = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::RoundNotFound)?;
+
+        if round.status != RoundStatus::Revealing {
+            return Err(Error::NotRevealPhase);
+        }
+        if env.ledger().timestamp() > round.reveal_deadline {
+            return Err(Error::RevealWindowClosed);
+        }
+
+        let submission_key = DataKey::Submission(round_id, player.clone());
+        let mut submission: Submission = env
+            .storage()
+            .persistent()
+            .get(&submission_key)
+            .ok_or(Error::SubmissionNotFound)?;
+
+        if submission.revealed {
+            return Err(Error::AlreadyRevealed);
+        }
+        if player_commitment(&env, &answer, &salt, &player) != submission.commitment {
+            return Err(Error::InvalidReveal);
+        }
+
+        let correct = answer == round.revealed_answer;
+        if correct {
+            round.winner_count = round
+                .winner_count
+                .checked_add(1)
+                .ok_or(Error::Overflow)?;
+            // Indexes are unique, so this keeps `correct_order` sorted by arrival
+            // regardless of the order in which players reveal.
+            let position = match round.correct_order.binary_search(submission.index) {
+                Ok(position) | Err(position) => position,
+            };
+            round.correct_order.insert(position, submission.index);
+            env.storage().persistent().set(&key, &round);
+        }
+
+        submission.revealed = true;
+        submission.correct = correct;
+        env.storage().persistent().set(&submission_key, &submission);
+
+        AnswerRevealed {
+            round_id,
+            player,
+            correct,
+        }
+        .publish(&env);
+        Ok(correct)
+    }
+
+    /// Finalize the round, closing it and calculating payouts: per rank from
+    /// the round's weights, or per winner when it has none.
+    /// Only allowed after the reveal window; unrevealed answers count as wrong.
+    /// Fee management charges the rake on the entry pot, and the pot not
+    /// covered by winners' payouts (all of it when nobody won, otherwise the
+    /// rounding remainder) is returned: to the prize pool up to the seeded
+    /// reward, the rest to the admin. Two questions with the same entry pot
+    /// cannot be finalized in one ledger, as fee management refuses the
+    /// repeated charge.
+    pub fn finalize_round(env: Env, round_id: u64) -> Result<(), Error> {
+        let admin = require_admin(&env)?;
+        let key = DataKey::Round(round_id);
+        let mut round: RoundData = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::RoundNotFound)?;
+
+        if round.status != RoundStatus::Revealing {
+            return Err(Error::NotRevealPhase);
+        }
+        let now = env.ledger().timestamp();
+        if now <= round.reveal_deadline {
+            return Err(Error::RevealWindowOpen);
+        }
 
-        // Allow finalize even before deadline if admin chooses, or wait until after.
-        // Usually finalize happens after deadline.
+        round.rake = charge_rake(&env, round_id, round.entry_pot)?;
+        let pot = prize_pot(&round)?;
 
-        let payout_per_winner = if round.winner_count == 0 {
+        let mut payout_per_winner = 0;
+        let mut rank_payouts = Vec::new(&env);
+        let awarded = if round.winner_count == 0 {
             0
-        } else {
-            round
-                .reward_amount
+        } else if round.rank_weights.is_empty() {
+            payout_per_winner = pot
                 .checked_div(round.winner_count as i128)
+                .ok_or(Error::Overflow)?;
+            payout_per_winner
+                .checked_mul(round.winner_count as i128)
                 .ok_or(Error::Overflow)?
+        } else {
+            let (base, by_rank) = payouts_by_rank(&env, pot, &round)?;
+            payout_per_winner = base;
+            rank_payouts = by_rank;
+            let unranked = round.winner_count.saturating_sub(rank_payouts.len());
+            let mut awarded = base
+                .checked_mul(unranked as i128)
+                .ok_or(Error::Overflow)?;
+            for payout in rank_payouts.iter() {
+                awarded = awarded.checked_add(payout).ok_or(Error::Overflow)?;
+            }
+            awarded
         };
 
-        if round.winner_count == 0 {
-            let prize_pool = get_prize_pool(&env)?;
-            let pool_client = PrizePoolClient::new(&env, &prize_pool);
-            pool_client.release(&admin, &round_id, &round.reward_amount);
-        }
+        let unawarded = pot.checked_sub(awarded).ok_or(Error::Overflow)?;
+        let (released, forwarded) =
+            return_unawarded(&env, &admin, round_id, &mut round, unawarded)?;
+        forward_to_admin(&env, &admin, forwarded)?;
+
+        let window: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ClaimWindow)
+            .unwrap_or(DEFAULT_CLAIM_WINDOW);
+        let claim_deadline = now.checked_add(window).ok_or(Error::Overflow)?;
 
         round.status = RoundStatus::Finalized;
         round.payout_per_winner = payout_per_winner;
+        round.rank_payouts = rank_payouts.clone();
+        round.claim_deadline = claim_deadline;
         env.storage().persistent().set(&key, &round);
 
         RoundFinalized {
             round_id,
             winners: round.winner_count,
             payout_per_winner,
+            rank_payouts,
+            released,
+            rake: round.rake,
+            forwarded,
+            claim_deadline,
         }
         .publish(&env);
         Ok(())
     }
 
-    /// Claim reward for a correct answer.
+    /// Claim reward for a correct answer, up to and including the claim
+    /// deadline. In weighted rounds the amount depends on the answer's rank.
     pub fn claim_reward(env: Env, player: Address, round_id: u64) -> Result<i128, Error> {
         require_initialized(&env)?;
         player.require_auth();
 
-        let round: RoundData = env
+        let key = DataKey::Round(round_id);
+        let mut round: RoundData = env
             .storage()
             .persistent()
-            .get(&DataKey::Round(round_id))
+            .get(&key)
             .ok_or(Error::RoundNotFound)?;
 
+        if round.status == RoundStatus::Swept {
+            return Err(Error::ClaimWindowClosed);
+        }
         if round.status != RoundStatus::Finalized {
             return Err(Error::RoundNotOpen);
         }
+        if env.ledger().timestamp() > round.claim_deadline {
+            return Err(Error::ClaimWindowClosed);
+        }
 
         let submission_key = DataKey::Submission(round_id, player.clone());
         let mut submission: Submission = env
@@ -347,49 +753,125 @@ impl SpeedTrivia {
             return Err(Error::AlreadyClaimed);
         }
 
-        if !submission.correct || round.payout_per_winner <= 0 {
+        let payout = payout_for(&round, &submission);
+        if payout <= 0 {
             return Err(Error::NoRewardAvailable);
         }
 
-        let prize_pool = get_prize_pool(&env)?;
-        let pool_client = PrizePoolClient::new(&env, &prize_pool);
-        let admin = get_admin(&env)?;
-        pool_client.payout(&admin, &player, &round_id, &round.payout_per_winner);
+        // Draw on the question's reservation until it runs out, then on the
+        // entry pot held in escrow.
+        let from_pool = payout.min(pool_remaining(&round)?);
+        if from_pool > 0 {
+            let prize_pool = get_prize_pool(&env)?;
+            let pool_client = PrizePoolClient::new(&env, &prize_pool);
+            let admin = get_admin(&env)?;
+            pool_client.payout(&admin, &player, &round_id, &from_pool);
+        }
 
         let balance_contract = get_balance_contract(&env)?;
         let balance_client = BalanceClient::new(&env, &balance_contract);
         let contract_addr = env.current_contract_address();
 
-        // Adjust internal balance tracking
+        let contract_balance = balance_client.balance_of(&contract_addr);
+        if contract_balance < payout {
+            return Err(Error::InvalidAmount);
+        }
+
         balance_client.debit(
             &contract_addr,
             &contract_addr,
-            &round.payout_per_winner,
+            &payout,
             &symbol_short!("payout"),
         );
-        balance_client.credit(
-            &contract_addr,
-            &player,
-            &round.payout_per_winner,
-            &symbol_short!("win"),
-        );
+        balance_client.credit(&contract_addr, &player, &payout, &symbol_short!("win"));
 
         submission.claimed = true;
         env.storage().persistent().set(&submission_key, &submission);
 
+        round.claimed_total = round
+            .claimed_total
+            .checked_add(payout)
+            .ok_or(Error::Overflow)?;
+        round.pool_paid = round
+            .pool_paid
+            .checked_add(from_pool)
+            .ok_or(Error::Overflow)?;
+        env.storage().persistent().set(&key, &round);
+
         RewardClaimed {
             round_id,
             player,
-            amount: round.payout_per_winner,
+            amount: payout,
         }
         .publish(&env);
-        Ok(round.payout_per_winner)
+        Ok(payout)
+    }
+
+    /// Return the reward left unclaimed after the claim deadline: to the
+    /// prize pool up to the unpaid part of its reservation, the rest to the
+    /// admin. Admin only.
+    pub fn sweep_unclaimed(env: Env, round_id: u64) -> Result<i128, Error> {
+        let admin = require_admin(&env)?;
+        let key = DataKey::Round(round_id);
+        let mut round: RoundData = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::RoundNotFound)?;
+
+        if round.status == RoundStatus::Swept {
+            return Err(Error::AlreadySwept);
+        }
+        if round.status != RoundStatus::Finalized {
+            return Err(Error::RoundNotOpen);
+        }
+        if env.ledger().timestamp() <= round.claim_deadline {
+            return Err(Error::ClaimWindowOpen);
+        }
+
+        let amount = prize_pot(&round)?
+            .checked_sub(round.released)
+            .and_then(|v| v.checked_sub(round.forwarded))
+            .and_then(|v| v.checked_sub(round.claimed_total))
+            .ok_or(Error::Overflow)?;
+        let (_, forwarded) = return_unawarded(&env, &admin, round_id, &mut round, amount)?;
+        forward_to_admin(&env, &admin, forwarded)?;
+
+        round.status = RoundStatus::Swept;
+        env.storage().persistent().set(&key, &round);
+
+        UnclaimedSwept { round_id, amount }.publish(&env);
+        Ok(amount)
     }
 
     /// Get round data.
     pub fn get_round(env: Env, round_id: u64) -> Option<RoundData> {
         env.storage().persistent().get(&DataKey::Round(round_id))
     }
+
+    /// Get a player's submission.
+    pub fn get_submission(env: Env, round_id: u64, player: Address) -> Option<Submission> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Submission(round_id, player))
+    }
+
+    /// 1-based rank of a player's revealed correct answer, fastest first.
+    pub fn get_rank(env: Env, round_id: u64, player: Address) -> Option<u32> {
+        let round: RoundData = env.storage().persistent().get(&DataKey::Round(round_id))?;
+        let submission: Submission = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Submission(round_id, player))?;
+        if !submission.correct {
+            return None;
+        }
+        round
+            .correct_order
+            .binary_search(submission.index)
+            .ok()
+            .map(|position| position + 1)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -409,13 +891,192 @@ fn require_admin(env: &Env) -> Result<Address, Error> {
     Ok(admin)
 }
 
-fn require_positive(amount: i128) -> Result<(), Error> {
-    if amount <= 0 {
+/// A question must be funded by a reward, an entry fee, or both, and neither
+/// may be negative.
+fn validate_funding(reward_amount: i128, entry_fee: i128) -> Result<(), Error> {
+    if reward_amount < 0 || entry_fee < 0 || (reward_amount == 0 && entry_fee == 0) {
+        return Err(Error::InvalidAmount);
+    }
+    Ok(())
+}
+
+/// Split `pot` among the round's winners: a speed bonus is divided across the filled
+/// ranks by weight, and the rest equally among all winners. The bonus is
+/// capped so the first rank's share of it is at most one entry fee, which is
+/// what another early address would cost. Weights are renormalised over the
+/// ranks actually filled. Returns the base share and each rank's payout.
+fn payouts_by_rank(
+    env: &Env,
+    pot: i128,
+    round: &RoundData,
+) -> Result<(i128, Vec<i128>), Error> {
+    let weights = &round.rank_weights;
+    let winners = round.winner_count;
+    let ranks = winners.min(weights.len());
+    let mut total_weight = 0i128;
+    for rank in 0..ranks {
+        let weight = weights.get(rank).ok_or(Error::InvalidWeights)?;
+        total_weight = total_weight
+            .checked_add(weight as i128)
+            .ok_or(Error::Overflow)?;
+    }
+    let top_weight = weights.get(0).ok_or(Error::InvalidWeights)? as i128;
+    let bonus_cap = round
+        .entry_fee
+        .checked_mul(total_weight)
+        .and_then(|v| v.checked_div(top_weight))
+        .ok_or(Error::Overflow)?;
+    let bonus = pot.min(bonus_cap);
+    let base = pot
+        .checked_sub(bonus)
+        .and_then(|v| v.checked_div(winners as i128))
+        .ok_or(Error::Overflow)?;
+
+    let mut payouts = Vec::new(env);
+    for rank in 0..ranks {
+        let weight = weights.get(rank).ok_or(Error::InvalidWeights)?;
+        let payout = bonus
+            .checked_mul(weight as i128)
+            .and_then(|v| v.checked_div(total_weight))
+            .and_then(|v| v.checked_add(base))
+            .ok_or(Error::Overflow)?;
+        payouts.push_back(payout);
+    }
+    Ok((base, payouts))
+}
+
+/// Amount a finalized round owes for `submission`.
+fn payout_for(round: &RoundData, submission: &Submission) -> i128 {
+    if !submission.correct {
+        return 0;
+    }
+    if round.rank_weights.is_empty() {
+        return round.payout_per_winner;
+    }
+    match round.correct_order.binary_search(submission.index) {
+        Ok(rank) => round
+            .rank_payouts
+            .get(rank)
+            .unwrap_or(round.payout_per_winner),
+        Err(_) => 0,
+    }
+}
+
+/// What a question's winners share: its reserved reward plus the entry fees
+/// left after fee management's cut.
+fn prize_pot(round: &RoundData) -> Result<i128, Error> {
+    round
+        .reward_amount
+        .checked_add(round.entry_pot)
+        .and_then(|v| v.checked_sub(round.rake))
+        .ok_or(Error::Overflow)
+}
+
+/// Reserved reward for the question that has gone neither to a winner nor
+/// back to the prize pool.
+fn pool_remaining(round: &RoundData) -> Result<i128, Error> {
+    round
+        .reward_amount
+        .checked_sub(round.released)
+        .and_then(|v| v.checked_sub(round.pool_paid))
+        .ok_or(Error::Overflow)
+}
+
+/// Give back `amount` that no winner will receive. The prize pool gets its
+/// reservation back first; whatever exceeds it came from entry fees and is
+/// added to `forwarded` for the caller to pay to the admin. Returns
+/// `(released, forwarded)`.
+fn return_unawarded(
+    env: &Env,
+    admin: &Address,
+    round_id: u64,
+    round: &mut RoundData,
+    amount: i128,
+) -> Result<(i128, i128), Error> {
+    let released = amount.min(pool_remaining(round)?);
+    let forwarded = amount.checked_sub(released).ok_or(Error::Overflow)?;
+    if released > 0 {
+        let prize_pool = get_prize_pool(env)?;
+        let pool_client = PrizePoolClient::new(env, &prize_pool);
+        pool_client.release(admin, &round_id, &released);
+    }
+    round.released = round
+        .released
+        .checked_add(released)
+        .ok_or(Error::Overflow)?;
+    round.forwarded = round
+        .forwarded
+        .checked_add(forwarded)
+        .ok_or(Error::Overflow)?;
+    Ok((released, forwarded))
+}
+
+/// Charge the rake on a question's entry pot through fee management, keyed
+/// by the question's round id, and move it out of the contract's balance.
+/// Returns the rake.
+fn charge_rake(env: &Env, round_id: u64, entry_pot: i128) -> Result<i128, Error> {
+    if entry_pot <= 0 {
+        return Ok(0);
+    }
+    let fee_contract: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::FeeContract)
+        .ok_or(Error::FeeNotConfigured)?;
+    let game_id: Symbol = env
+        .storage()
+        .instance()
+        .get(&DataKey::FeeGameId)
+        .ok_or(Error::FeeNotConfigured)?;
+    let fee_client = FeeManagementClient::new(env, &fee_contract);
+    let net = fee_client.charge_fee_with_id(&game_id, &entry_pot, &round_id, &None);
+    let rake = entry_pot.checked_sub(net).ok_or(Error::Overflow)?;
+    if rake < 0 || rake > entry_pot {
         return Err(Error::InvalidAmount);
     }
+    if rake > 0 {
+        let balance_client = BalanceClient::new(env, &get_balance_contract(env)?);
+        let contract_addr = env.current_contract_address();
+        balance_client.debit(&contract_addr, &contract_addr, &rake, &symbol_short!("rake"));
+        balance_client.credit(&contract_addr, &fee_contract, &rake, &symbol_short!("rake"));
+    }
+    Ok(rake)
+}
+
+/// Pay entry funds left over after a question to the admin.
+fn forward_to_admin(env: &Env, admin: &Address, amount: i128) -> Result<(), Error> {
+    if amount <= 0 {
+        return Ok(());
+    }
+    let balance_client = BalanceClient::new(env, &get_balance_contract(env)?);
+    let contract_addr = env.current_contract_address();
+    balance_client.debit(&contract_addr, &contract_addr, &amount, &symbol_short!("unawarded"));
+    balance_client.credit(&contract_addr, admin, &amount, &symbol_short!("unawarded"));
     Ok(())
 }
 
+/// Admin answer key commitment: `sha256(answer || salt)`.
+fn answer_commitment(env: &Env, answer: &Bytes, salt: &Bytes) -> BytesN<32> {
+    let mut preimage = answer.clone();
+    preimage.append(salt);
+    env.crypto().sha256(&preimage).into()
+}
+
+/// Player answer commitment: `sha256(answer || salt || player_xdr)`. As the
+/// address is part of the hash, a commitment lifted from someone else's
+/// pending submission can only ever be revealed by that player.
+fn player_commitment(
+    env: &Env,
+    answer: &Bytes,
+    salt: &Bytes,
+    player: &Address,
+) -> BytesN<32> {
+    let mut preimage = answer.clone();
+    preimage.append(salt);
+    preimage.append(&player.clone().to_xdr(env));
+    env.crypto().sha256(&preimage).into()
+}
+
 fn get_admin(env: &Env) -> Result<Address, Error> {
     env.storage()
         .instance()
@@ -511,6 +1172,61 @@ mod test {
         }
     }
 
+    #[contract]
+    pub struct MockFeeManagement;
+
+    #[contracttype]
+    pub enum FeeKey {
+        Bps(Symbol),
+        Accrued(Symbol),
+        Charged(Symbol, i128, u64, u64),
+    }
+
+    #[contractimpl]
+    impl MockFeeManagement {
+        pub fn set_fee_bps(env: Env, game_id: Symbol, bps: u32) {
+            env.storage().persistent().set(&FeeKey::Bps(game_id), &bps);
+        }
+
+        pub fn charge_fee_with_id(
+            env: Env,
+            game_id: Symbol,
+            amount: i128,
+            charge_id: u64,
+            _token: Option<Address>,
+        ) -> i128 {
+            // Like fee management, refuse a repeated charge in one ledger
+            let op = FeeKey::Charged(
+                game_id.clone(),
+                amount,
+                env.ledger().timestamp(),
+                charge_id,
+            );
+            if env.storage().persistent().has(&op) {
+                panic!("duplicate operation");
+            }
+            env.storage().persistent().set(&op, &true);
+            let bps: u32 = env
+                .storage()
+                .persistent()
+                .get(&FeeKey::Bps(game_id.clone()))
+                .expect("game not found");
+            let fee = amount * bps as i128 / 10_000;
+            let accrued = Self::accrued_fees(env.clone(), game_id.clone());
+            env.storage()
+                .persistent()
+                .set(&FeeKey::Accrued(game_id), &(accrued + fee));
+            amount - fee
+        }
+
+        pub fn accrued_fees(env: Env, game_id: Symbol) -> i128 {
+            env.storage()
+                .persistent()
+                .get(&FeeKey::Accrued(game_id))
+                .unwrap_or(0)
+        }
+    }
+
     fn setup(
         env: &Env,
     ) -> (
@@ -539,8 +1255,24 @@ mod test {
         (trivia_client, admin, player, trivia_id, balance_client)
     }
 
-    fn hash_answer(env: &Env, payload: &Bytes) -> BytesN<32> {
-        env.crypto().sha256(payload).into()
+    fn salt(env: &Env, seed: u8) -> Bytes {
+        Bytes::from_array(env, &[seed; 16])
+    }
+
+    fn commit_key(env: &Env, answer: &Bytes) -> BytesN<32> {
+        answer_commitment(env, answer, &salt(env, 0xAA))
+    }
+
+    fn commit(env: &Env, answer: &Bytes, player: &Address) -> BytesN<32> {
+        player_commitment(env, answer, &salt(env, 0x11), player)
+    }
+
+    /// Move past the deadline, publish the key, and return the reveal deadline.
+    fn close(env: &Env, client: &SpeedTriviaClient, round_id: u64, answer: &Bytes) -> u64 {
+        let deadline = client.get_round(&round_id).unwrap().deadline;
+        env.ledger().set_timestamp(deadline + 1);
+        client.close_question(&round_id, answer, &salt(env, 0xAA));
+        client.get_round(&round_id).unwrap().reveal_deadline
     }
 
     #[test]
@@ -549,15 +1281,19 @@ mod test {
         let (client, _admin, player, _trivia_id, balance) = setup(&env);
 
         let deadline = env.ledger().timestamp() + 1000;
-        let payload = Bytes::from_array(&env, &[1, 2, 3]);
-        let commitment = hash_answer(&env, &payload);
+        let answer = Bytes::from_array(&env, &[1, 2, 3]);
+
+        client.open_question(&1, &commit_key(&env, &answer), &deadline, &1000, &0);
+
+        let commitment = commit(&env, &answer, &player);
+        client.submit_answer(&player, &1, &commitment);
 
-        client.open_question(&1, &commitment, &deadline, &1000);
-        
-        client.submit_answer(&player, &1, &payload, &env.ledger().timestamp());
-        
+        let reveal_deadline = close(&env, &client, 1, &answer);
+        assert!(client.reveal_answer(&player, &1, &answer, &salt(&env, 0x11)));
+
+        env.ledger().set_timestamp(reveal_deadline + 1);
         client.finalize_round(&1);
-        
+
         let reward = client.claim_reward(&player, &1);
         assert_eq!(reward, 1000);
         assert_eq!(balance.balance_of(&player), 1000);
@@ -569,15 +1305,14 @@ mod test {
         let (client, _admin, player, _trivia_id, _balance) = setup(&env);
 
         let deadline = env.ledger().timestamp() + 10;
-        let payload = Bytes::from_array(&env, &[1, 2, 3]);
-        let commitment = hash_answer(&env, &payload);
+        let answer = Bytes::from_array(&env, &[1, 2, 3]);
+
+        client.open_question(&1, &commit_key(&env, &answer), &deadline, &1000, &0);
 
-        client.open_question(&1, &commitment, &deadline, &1000);
-        
         env.ledger().set_timestamp(deadline + 1);
-        
-        let result = client.try_submit_answer(&player, &1, &payload, &env.ledger().timestamp());
-        assert!(result.is_err());
+
+        let result = client.try_submit_answer(&player, &1, &commit(&env, &answer, &player));
+        assert_eq!(result, Err(Ok(Error::PastDeadline)));
     }
 
     #[test]
@@ -586,19 +1321,19 @@ mod test {
         let (client, _admin, _player, _trivia_id, _balance) = setup(&env);
         let other = Address::generate(&env);
 
-        let commitment = hash_answer(&env, &Bytes::from_array(&env, &[1]));
-        
+        let commitment = commit_key(&env, &Bytes::from_array(&env, &[1]));
+
         // Try to open question as non-admin
         env.mock_auths(&[soroban_sdk::testutils::MockAuth {
             address: &other,
             invoke: &soroban_sdk::testutils::MockAuthInvoke {
                 contract: &client.address,
                 fn_name: "open_question",
-                args: (1u64, commitment.clone(), 1000u64, 100i128).into_val(&env),
+                args: (1u64, commitment.clone(), 1000u64, 100i128, 0i128).into_val(&env),
                 sub_invokes: &[],
             },
         }]);
-        let result = client.try_open_question(&1, &commitment, &1000, &100);
+        let result = client.try_open_question(&1, &commitment, &1000, &100, &0);
         assert!(result.is_err());
     }
 
@@ -607,13 +1342,14 @@ mod test {
         let env = Env::default();
         let (client, _admin, player, _trivia_id, _balance) = setup(&env);
 
-        let payload = Bytes::from_array(&env, &[1]);
-        let commitment = hash_answer(&env, &payload);
-        client.open_question(&1, &commitment, &(env.ledger().timestamp() + 100), &100);
+        let answer = Bytes::from_array(&env, &[1]);
+        let deadline = env.ledger().timestamp() + 100;
+        client.open_question(&1, &commit_key(&env, &answer), &deadline, &100, &0);
 
-        client.submit_answer(&player, &1, &payload, &env.ledger().timestamp());
-        let result = client.try_submit_answer(&player, &1, &payload, &env.ledger().timestamp());
-        assert!(result.is_err());
+        let commitment = commit(&env, &answer, &player);
+        client.submit_answer(&player, &1, &commitment);
+        let result = client.try_submit_answer(&player, &1, &commitment);
+        assert_eq!(result, Err(Ok(Error::AlreadySubmitted)));
     }
 
     #[test]
@@ -621,12 +1357,351 @@ mod test {
         let env = Env::default();
         let (client, _admin, player, _trivia_id, _balance) = setup(&env);
 
-        let payload = Bytes::from_array(&env, &[1]);
-        let commitment = hash_answer(&env, &payload);
-        client.open_question(&1, &commitment, &(env.ledger().timestamp() + 100), &100);
-        client.submit_answer(&player, &1, &payload, &env.ledger().timestamp());
+        let answer = Bytes::from_array(&env, &[1]);
+        let deadline = env.ledger().timestamp() + 100;
+        client.open_question(&1, &commit_key(&env, &answer), &deadline, &100, &0);
+        let commitment = commit(&env, &answer, &player);
+        client.submit_answer(&player, &1, &commitment);
+
+        let result = client.try_claim_reward(&player, &1);
+        assert!(result.is_err());
 
+        close(&env, &client, 1, &answer);
+        client.reveal_answer(&player, &1, &answer, &salt(&env, 0x11));
         let result = client.try_claim_reward(&player, &1);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_answer_key_only_after_deadline() {
+        let env = Env::default();
+        let (client, _admin, _player, _trivia_id, _balance) = setup(&env);
+
+        let answer = Bytes::from_array(&env, &[4]);
+        client.open_question(&1, &commit_key(&env, &answer), &100, &100, &0);
+
+        let result = client.try_close_question(&1, &answer, &salt(&env, 0xAA));
+        assert_eq!(result, Err(Ok(Error::DeadlineNotReached)));
+
+        env.ledger().set_timestamp(101);
+        let result = client.try_close_question(&1, &answer, &salt(&env, 0xAB));
+        assert_eq!(result, Err(Ok(Error::InvalidReveal)));
+        client.close_question(&1, &answer, &salt(&env, 0xAA));
+        assert_eq!(client.get_round(&1).unwrap().status, RoundStatus::Revealing);
+    }
+
+    #[test]
+    fn test_reveal_rules() {
+        let env = Env::default();
+        let (client, _admin, player, _trivia_id, _balance) = setup(&env);
+        let copier = Address::generate(&env);
+        let wrong_player = Address::generate(&env);
+
+        let answer = Bytes::from_array(&env, &[7]);
+        let wrong = Bytes::from_array(&env, &[8]);
+        client.open_question(&1, &commit_key(&env, &answer), &100, &100, &0);
+
+        let honest = commit(&env, &answer, &player);
+        client.submit_answer(&player, &1, &honest);
+        client.submit_answer(&copier, &1, &honest);
+        client.submit_answer(&wrong_player, &1, &commit(&env, &wrong, &wrong_player));
+
+        let result = client.try_reveal_answer(&player, &1, &answer, &salt(&env, 0x11));
+        assert_eq!(result, Err(Ok(Error::NotRevealPhase)));
+
+        let reveal_deadline = close(&env, &client, 1, &answer);
+        assert!(client.reveal_answer(&player, &1, &answer, &salt(&env, 0x11)));
+        assert!(!client.reveal_answer(&wrong_player, &1, &wrong, &salt(&env, 0x11)));
+
+        // A copied commitment is bound to the original player
+        let result = client.try_reveal_answer(&copier, &1, &answer, &salt(&env, 0x11));
+        assert_eq!(result, Err(Ok(Error::InvalidReveal)));
+        let result = client.try_reveal_answer(&player, &1, &answer, &salt(&env, 0x11));
+        assert_eq!(result, Err(Ok(Error::AlreadyRevealed)));
+
+        assert_eq!(client.try_finalize_round(&1), Err(Ok(Error::RevealWindowOpen)));
+        env.ledger().set_timestamp(reveal_deadline + 1);
+        let result = client.try_reveal_answer(&copier, &1, &answer, &salt(&env, 0x11));
+        assert_eq!(result, Err(Ok(Error::RevealWindowClosed)));
+
+        client.finalize_round(&1);
+        let round = client.get_round(&1).unwrap();
+        assert_eq!(round.winner_count, 1);
+        assert_eq!(round.payout_per_winner, 100);
+    }
+
+    #[test]
+    fn test_remainder_released_and_unclaimed_swept() {
+        let env = Env::default();
+        let (client, _admin, player, _trivia_id, _balance) = setup(&env);
+        let slow = Address::generate(&env);
+        let other = Address::generate(&env);
+
+        client.set_claim_window(&500);
+        let answer = Bytes::from_array(&env, &[3]);
+        client.open_question(&2, &commit_key(&env, &answer), &100, &1000, &0);
+        for p in [&player, &slow, &other] {
+            client.submit_answer(p, &2, &commit(&env, &answer, p));
+        }
+        let reveal_deadline = close(&env, &client, 2, &answer);
+        for p in [&player, &slow, &other] {
+            client.reveal_answer(p, &2, &answer, &salt(&env, 0x11));
+        }
+        env.ledger().set_timestamp(reveal_deadline + 1);
+        client.finalize_round(&2);
+
+        // 1000 / 3 = 333 each; the remainder goes straight back to the pool
+        let round = client.get_round(&2).unwrap();
+        assert_eq!(round.payout_per_winner, 333);
+        assert_eq!(round.released, 1);
+        assert_eq!(round.claim_deadline, reveal_deadline + 501);
+        assert_eq!(client.try_sweep_unclaimed(&2), Err(Ok(Error::ClaimWindowOpen)));
+
+        client.claim_reward(&player, &2);
+        client.claim_reward(&other, &2);
+
+        env.ledger().set_timestamp(round.claim_deadline + 1);
+        assert_eq!(client.try_claim_reward(&slow, &2), Err(Ok(Error::ClaimWindowClosed)));
+        assert_eq!(client.sweep_unclaimed(&2), 333);
+
+        let round = client.get_round(&2).unwrap();
+        assert_eq!(round.status, RoundStatus::Swept);
+        assert_eq!(round.claimed_total + round.released, 1000);
+        assert_eq!(client.try_sweep_unclaimed(&2), Err(Ok(Error::AlreadySwept)));
+    }
+
+    #[test]
+    fn test_no_winners_releases_full_reservation() {
+        let env = Env::default();
+        let (client, _admin, player, _trivia_id, _balance) = setup(&env);
+
+        let answer = Bytes::from_array(&env, &[3]);
+        let wrong = Bytes::from_array(&env, &[4]);
+        client.open_question(&3, &commit_key(&env, &answer), &100, &600, &0);
+        client.submit_answer(&player, &3, &commit(&env, &wrong, &player));
+        let reveal_deadline = close(&env, &client, 3, &answer);
+        client.reveal_answer(&player, &3, &wrong, &salt(&env, 0x11));
+        env.ledger().set_timestamp(reveal_deadline + 1);
+        client.finalize_round(&3);
+
+        let round = client.get_round(&3).unwrap();
+        assert_eq!(round.winner_count, 0);
+        assert_eq!(round.released, 600);
+    }
+
+    #[test]
+    fn test_paid_entry_question_splits_pot_after_rake() {
+        let env = Env::default();
+        let (client, admin, player, _trivia_id, balance) = setup(&env);
+        let fees = env.register(MockFeeManagement, ());
+        let fee_client = MockFeeManagementClient::new(&env, &fees);
+        let game = symbol_short!("speed");
+        fee_client.set_fee_bps(&game, &500);
+        let other = Address::generate(&env);
+        let wrong_player = Address::generate(&env);
+
+        let answer = Bytes::from_array(&env, &[5]);
+        let wrong = Bytes::from_array(&env, &[6]);
+        let commitment = commit_key(&env, &answer);
+        let result = client.try_open_question(&4, &commitment, &100, &0, &100);
+        assert_eq!(result, Err(Ok(Error::FeeNotConfigured)));
+
+        client.set_fee_config(&fees, &game);
+        client.open_question(&4, &commitment, &100, &0, &100);
+        for (p, a) in [(&player, &answer), (&other, &answer), (&wrong_player, &wrong)] {
+            balance.set_balance(p, &100);
+            client.submit_answer(p, &4, &commit(&env, a, p));
+            assert_eq!(balance.balance_of(p), 0);
+        }
+        let reveal_deadline = close(&env, &client, 4, &answer);
+        for (p, a) in [(&player, &answer), (&other, &answer), (&wrong_player, &wrong)] {
+            client.reveal_answer(p, &4, a, &salt(&env, 0x11));
+        }
+        env.ledger().set_timestamp(reveal_deadline + 1);
+        client.finalize_round(&4);
+
+        // 300 in entries, 5% rake: (300 - 15) / 2 = 142, remainder to the admin
+        let round = client.get_round(&4).unwrap();
+        assert_eq!(round.entry_pot, 300);
+        assert_eq!(round.rake, 15);
+        assert_eq!(round.payout_per_winner, 142);
+        assert_eq!(round.released, 0);
+        assert_eq!(round.forwarded, 1);
+        assert_eq!(balance.balance_of(&fees), 15);
+        assert_eq!(fee_client.accrued_fees(&game), 15);
+        assert_eq!(balance.balance_of(&admin), 1);
+
+        assert_eq!(client.claim_reward(&player, &4), 142);
+        assert_eq!(balance.balance_of(&player), 142);
+        assert_eq!(client.get_round(&4).unwrap().pool_paid, 0);
+    }
+
+    #[test]
+    fn test_equal_entry_pots_finalized_in_one_ledger() {
+        let env = Env::default();
+        let (client, _admin, player, _trivia_id, balance) = setup(&env);
+        let fees = env.register(MockFeeManagement, ());
+        let fee_client = MockFeeManagementClient::new(&env, &fees);
+        let game = symbol_short!("speed");
+        fee_client.set_fee_bps(&game, &500);
+        client.set_fee_config(&fees, &game);
+
+        let answer = Bytes::from_array(&env, &[5]);
+        for round_id in [8, 9] {
+            client.open_question(&round_id, &commit_key(&env, &answer), &100, &0, &100);
+            balance.set_balance(&player, &100);
+            client.submit_answer(&player, &round_id, &commit(&env, &answer, &player));
+        }
+        close(&env, &client, 8, &answer);
+        let reveal_deadline = close(&env, &client, 9, &answer);
+        for round_id in [8, 9] {
+            client.reveal_answer(&player, &round_id, &answer, &salt(&env, 0x11));
+        }
+
+        // Both 100 pots are raked in the same ledger
+        env.ledger().set_timestamp(reveal_deadline + 1);
+        client.finalize_round(&8);
+        client.finalize_round(&9);
+        assert_eq!(client.get_round(&8).unwrap().rake, 5);
+        assert_eq!(client.get_round(&9).unwrap().rake, 5);
+        assert_eq!(fee_client.accrued_fees(&game), 10);
+    }
+
+    #[test]
+    fn test_submission_time_comes_from_ledger() {
+        let env = Env::default();
+        let (client, _admin, player, _trivia_id, _balance) = setup(&env);
+
+        let answer = Bytes::from_array(&env, &[1]);
+        client.open_question(&5, &commit_key(&env, &answer), &100, &100, &0);
+        env.ledger().with_mut(|l| {
+            l.timestamp = 42;
+            l.sequence_number = 7;
+        });
+        client.submit_answer(&player, &5, &commit(&env, &answer, &player));
+
+        let submission = client.get_submission(&5, &player).unwrap();
+        assert_eq!(submission.timestamp, 42);
+        assert_eq!(submission.ledger, 7);
+        assert_eq!(submission.index, 0);
+    }
+
+    #[test]
+    fn test_rank_weights_validation() {
+        let env = Env::default();
+        let (client, _admin, _player, _trivia_id, _balance) = setup(&env);
+
+        for bad in [
+            soroban_sdk::vec![&env, 3u32, 5],
+            soroban_sdk::vec![&env, 5u32, 0],
+            soroban_sdk::vec![&env, 1u32, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1],
+        ] {
+            assert_eq!(client.try_set_rank_weights(&bad), Err(Ok(Error::InvalidWeights)));
+        }
+        client.set_rank_weights(&soroban_sdk::vec![&env, 5u32, 5, 2]);
+
+        // Blind early commitments would be free
+        let answer = Bytes::from_array(&env, &[1]);
+        assert_eq!(
+            client.try_open_question(&5, &commit_key(&env, &answer), &100, &1000, &0),
+            Err(Ok(Error::WeightsNeedEntryFee))
+        );
+        client.set_rank_weights(&Vec::new(&env));
+        client.open_question(&5, &commit_key(&env, &answer), &100, &1000, &0);
+    }
+
+    /// Enable a zero-rake fee config so questions may charge entry fees.
+    fn free_rake(env: &Env, client: &SpeedTriviaClient) {
+        let fees = env.register(MockFeeManagement, ());
+        let game = symbol_short!("speed");
+        MockFeeManagementClient::new(env, &fees).set_fee_bps(&game, &0);
+        client.set_fee_config(&fees, &game);
+    }
+
+    #[test]
+    fn test_ranked_payouts_follow_arrival_order() {
+        let env = Env::default();
+        let (client, _admin, _player, _trivia_id, balance) = setup(&env);
+        free_rake(&env, &client);
+        let first = Address::generate(&env);
+        let second = Address::generate(&env);
+        let third = Address::generate(&env);
+        let fourth = Address::generate(&env);
+        let wrong_player = Address::generate(&env);
+
+        client.set_rank_weights(&soroban_sdk::vec![&env, 5u32, 3, 2]);
+        let answer = Bytes::from_array(&env, &[9]);
+        let wrong = Bytes::from_array(&env, &[8]);
+        client.open_question(&6, &commit_key(&env, &answer), &100, &1000, &100);
+
+        let submit_at = |sequence: u32, player: &Address, a: &Bytes| {
+            env.ledger().with_mut(|l| {
+                l.sequence_number = sequence;
+                l.timestamp = sequence as u64;
+            });
+            balance.set_balance(player, &100);
+            client.submit_answer(player, &6, &commit(&env, a, player));
+        };
+        submit_at(5, &wrong_player, &wrong);
+        submit_at(10, &first, &answer);
+        // Same ledger: applied first, ranked first
+        submit_at(20, &second, &answer);
+        submit_at(20, &third, &answer);
+        submit_at(30, &fourth, &answer);
+
+        // Reveal order does not affect ranks
+        let reveal_deadline = close(&env, &client, 6, &answer);
+        for p in [&fourth, &third, &wrong_player, &second, &first] {
+            let a = if p == &wrong_player { &wrong } else { &answer };
+            client.reveal_answer(p, &6, a, &salt(&env, 0x11));
+        }
+        env.ledger().set_timestamp(reveal_deadline + 1);
+        client.finalize_round(&6);
+
+        let round = client.get_round(&6).unwrap();
+        assert_eq!(round.winner_count, 4);
+        // Pot 1500; the bonus is capped at 100 × 10/5 = 200, the base is
+        // 1300 / 4 = 325 and the bonus splits 100, 60, 40
+        assert_eq!(round.payout_per_winner, 325);
+        assert_eq!(round.rank_payouts, soroban_sdk::vec![&env, 425i128, 385, 365]);
+        assert_eq!(client.get_rank(&6, &first), Some(1));
+        assert_eq!(client.get_rank(&6, &third), Some(3));
+        assert_eq!(client.get_rank(&6, &wrong_player), None);
+
+        assert_eq!(client.claim_reward(&third, &6), 365);
+        assert_eq!(client.claim_reward(&first, &6), 425);
+        assert_eq!(client.claim_reward(&second, &6), 385);
+        assert_eq!(client.claim_reward(&fourth, &6), 325);
+        assert_eq!(
+            client.try_claim_reward(&wrong_player, &6),
+            Err(Ok(Error::NoRewardAvailable))
+        );
+    }
+
+    #[test]
+    fn test_ranked_payouts_renormalise_when_few_winners() {
+        let env = Env::default();
+        let (client, _admin, player, _trivia_id, balance) = setup(&env);
+        free_rake(&env, &client);
+        let other = Address::generate(&env);
+
+        client.set_rank_weights(&soroban_sdk::vec![&env, 5u32, 3, 2]);
+        let answer = Bytes::from_array(&env, &[9]);
+        client.open_question(&7, &commit_key(&env, &answer), &100, &1000, &100);
+        for p in [&player, &other] {
+            balance.set_balance(p, &100);
+            client.submit_answer(p, &7, &commit(&env, &answer, p));
+        }
+        let reveal_deadline = close(&env, &client, 7, &answer);
+        client.reveal_answer(&player, &7, &answer, &salt(&env, 0x11));
+        client.reveal_answer(&other, &7, &answer, &salt(&env, 0x11));
+        env.ledger().set_timestamp(reveal_deadline + 1);
+        client.finalize_round(&7);
+
+        // Bonus cap 100 × 8/5 = 160 over the filled ranks, split 100 and
+        // 60 on a base of (1200 - 160) / 2 = 520
+        let round = client.get_round(&7).unwrap();
+        assert_eq!(round.rank_payouts, soroban_sdk::vec![&env, 620i128, 580]);
+        assert_eq!(round.released, 0);
+    }
 }
@@ -1,16 +1,32 @@
 //! Stellarcade Wordle Clone Contract
 //!
-//! A daily on-chain Wordle game where players submit up to 6 guesses for a
-//! hidden 5-letter word. Each guess is scored against the answer after the
-//! puzzle is finalized by the admin using commit-reveal.
+//! A daily on-chain Wordle game where players submit up to `max_attempts`
+//! guesses for a hidden word of `word_length` letters. Each guess is scored
+//! against the answer after the puzzle is finalized by the admin using
+//! commit-reveal. Daily puzzles use `WORD_LENGTH` (5) and `MAX_ATTEMPTS` (6);
+//! themed puzzles can change the word length, attempt limit and alphabet, and
+//! enable hard mode (see `PuzzleConfig`).
 //!
 //! ## Game Flow
-//! 1. Admin calls `create_daily_puzzle` with SHA-256(answer) as `answer_commitment`.
-//! 2. Players call `submit_attempt` with their 5-letter guess (up to 6 times).
+//! 1. Admin calls `create_daily_puzzle` (or `create_configured_puzzle`) with
+//!    SHA-256(answer) as `answer_commitment`.
+//! 2. Players call `submit_attempt` with a guess of `word_length` letters, up
+//!    to `max_attempts` times.
 //! 3. Admin calls `reveal_answer` with the plaintext answer (verifies commitment).
-//! 4. Admin calls `finalize_result(player, puzzle_id)` per the issue interface;
-//!    all player attempts are scored and winners recorded.
-//! 5. Players call `get_attempts` to read their scored attempt history.
+//! 4. Attempts are scored and winners recorded, per player. Any player may
+//!    call `finalize_self`, the admin may page through players with
+//!    `finalize_batch`, or finalize everyone left with `finalize_result`.
+//!    The puzzle is `Finalized` once every player has been scored.
+//! 5. Players call `get_attempts` / `get_attempt` to read their scored attempts.
+//! 6. If the admin funded the puzzle with `fund_puzzle`, winners call
+//!    `claim_reward` before the claim deadline; `sweep_unclaimed` then
+//!    releases what is left of the prize pool reservation.
+//!
+//! ## Rewards
+//! A funded puzzle's `reward_budget` is reserved in the prize pool and split
+//! among winners by attempt weight: solving on guess `n` earns
+//! `attempt_weights[n - 1]`, and each winner receives
+//! `reward_budget * weight / total_weight`. Fewer guesses, bigger share.
 //!
 //! ## Guess Scoring
 //! Each character in a guess is scored per position:
@@ -22,17 +38,34 @@
 //! first, then remaining answer characters are consumed for PRESENT matches,
 //! so each answer character accounts for at most one PRESENT mark.
 //!
+//! ## Hard Mode
+//! In hard-mode puzzles every guess must reuse the hints revealed by earlier
+//! guesses: CORRECT letters stay in place and PRESENT letters are reused
+//! (see `satisfies_hints`). This is checked at finalization. A violating
+//! attempt is disqualified, and so is every later attempt, so the player
+//! cannot win the puzzle after breaking the rule.
+//!
+//! ## Dictionary
+//! The admin may commit to a Merkle root of allowed words with
+//! `set_dictionary_root`. Puzzles created afterwards only accept guesses that
+//! come with a valid inclusion proof (see `verify_word`), so nonsense strings
+//! cannot be used to probe letters. The word list itself stays off-chain.
+//!
 //! ## Storage Strategy
 //! - `instance()` storage: contract-level config (Admin, PrizePoolContract,
-//!   BalanceContract). Small, bounded, stored in a single ledger entry.
+//!   BalanceContract, DictionaryRoot, AttemptWeights, ClaimWindow). Small,
+//!   bounded, stored in a single ledger entry.
 //! - `persistent()` storage: per-puzzle and per-player data (Puzzle, AttemptList,
-//!   Winner). Each key is an independent ledger entry with its own TTL extended
-//!   on every write (~30 days).
+//!   Winner, PlayerFinalized, SolvedAt, Claimed). Each key is an independent
+//!   ledger entry with its own TTL extended on every write (~30 days).
 //!
 //! ## Security
-//! - Only the admin may create puzzles, reveal answers, or finalize results.
-//! - Players may submit at most `MAX_ATTEMPTS` (6) guesses per puzzle.
-//! - Guesses must be exactly `WORD_LENGTH` (5) bytes.
+//! - Only the admin may create puzzles, reveal answers, or finalize other
+//!   players' results; finalization is idempotent per player.
+//! - Players may submit at most the puzzle's `max_attempts` guesses.
+//! - Guesses must be exactly the puzzle's `word_length` bytes, drawn from its
+//!   alphabet if one is set and, when the puzzle has a dictionary, proven to
+//!   be in it.
 //! - Finalization verifies the commitment before scoring, preventing answer
 //!   manipulation after guesses are locked in.
 //! - All arithmetic uses `checked_*` to prevent overflow.
@@ -40,8 +73,8 @@
 #![allow(unexpected_cfgs)]
 
 use soroban_sdk::{
-    contract, contracterror, contractevent, contractimpl, contracttype, Address, Bytes, BytesN,
-    Env, Vec,
+    contract, contractclient, contracterror, contractevent, contractimpl, contracttype, Address,
+    Bytes, BytesN, Env, Vec,
 };
 
 // ---------------------------------------------------------------------------
@@ -49,17 +82,62 @@ use soroban_sdk::{
 // ---------------------------------------------------------------------------
 
 /// Number of characters in every guess/answer (standard Wordle word length).
+/// Used by `create_daily_puzzle`.
 pub const WORD_LENGTH: u32 = 5;
 
-/// Maximum guesses a player may submit per puzzle.
+/// Maximum guesses a player may submit per puzzle. Used by
+/// `create_daily_puzzle`.
 pub const MAX_ATTEMPTS: u32 = 6;
 
+/// Shortest word length a configured puzzle may use.
+pub const MIN_WORD_LENGTH: u32 = 4;
+
+/// Longest word length a configured puzzle may use.
+pub const MAX_WORD_LENGTH: u32 = 8;
+
+/// Upper bound on a configured puzzle's attempt limit.
+pub const MAX_ATTEMPTS_LIMIT: u32 = 10;
+
+/// Longest alphabet a configured puzzle may restrict guesses to.
+pub const MAX_ALPHABET_LEN: u32 = 64;
+
 /// Maximum players allowed per puzzle (bounds O(n) iteration in finalize_result).
 pub const MAX_PLAYERS_PER_PUZZLE: u32 = 1_000;
 
+/// Maximum players finalized by one `finalize_batch` call.
+pub const MAX_FINALIZE_BATCH: u32 = 100;
+
 /// Persistent storage TTL (~30 days at 5 s/ledger).
 pub const PERSISTENT_BUMP_LEDGERS: u32 = 518_400;
 
+/// Maximum dictionary proof length (a tree of up to 2^20 words).
+pub const MAX_PROOF_DEPTH: u32 = 20;
+
+/// Largest attempt weight; keeps the summed weight of a full puzzle in a `u32`.
+pub const MAX_ATTEMPT_WEIGHT: u32 = u32::MAX / MAX_PLAYERS_PER_PUZZLE;
+
+/// Default reward weight of a solve on guess 1, 2, ... `MAX_ATTEMPTS`.
+pub const DEFAULT_ATTEMPT_WEIGHTS: [u32; MAX_ATTEMPTS as usize] = [6, 5, 4, 3, 2, 1];
+
+/// Default time winners have to claim after a puzzle is finalized (7 days).
+pub const DEFAULT_CLAIM_WINDOW: u64 = 604_800;
+
+/// Domain-separation prefix hashed in front of a dictionary word.
+const LEAF_PREFIX: u8 = 0x00;
+/// Domain-separation prefix hashed in front of two child hashes.
+const NODE_PREFIX: u8 = 0x01;
+
+// ---------------------------------------------------------------------------
+// External contract clients
+// ---------------------------------------------------------------------------
+
+#[contractclient(name = "PrizePoolClient")]
+pub trait PrizePoolContract {
+    fn reserve(env: Env, admin: Address, game_id: u64, amount: i128);
+    fn release(env: Env, admin: Address, game_id: u64, amount: i128);
+    fn payout(env: Env, admin: Address, to: Address, game_id: u64, amount: i128);
+}
+
 // ---------------------------------------------------------------------------
 // Errors
 // ---------------------------------------------------------------------------
@@ -81,6 +159,20 @@ pub enum Error {
     Overflow = 11,
     PuzzleFull = 12,
     AnswerNotRevealed = 13,
+    NotInDictionary = 14,
+    InvalidProof = 15,
+    InvalidBatch = 16,
+    InvalidAmount = 17,
+    InvalidWeights = 18,
+    PuzzleAlreadyFunded = 19,
+    PuzzleNotFinalized = 20,
+    NoRewardAvailable = 21,
+    AlreadyClaimed = 22,
+    ClaimWindowOpen = 23,
+    ClaimWindowClosed = 24,
+    AlreadySwept = 25,
+    InvalidConfig = 26,
+    InvalidCharacter = 27,
 }
 
 // ---------------------------------------------------------------------------
@@ -104,12 +196,26 @@ pub const SCORE_CORRECT: u32 = 2;
 pub enum PuzzleStatus {
     /// Accepting player guesses.
     Open = 0,
-    /// Admin has revealed the answer; waiting for finalization.
+    /// Admin has revealed the answer; players are being finalized.
     Revealed = 1,
-    /// All attempts scored; puzzle complete.
+    /// Every player's attempts scored; puzzle complete.
     Finalized = 2,
 }
 
+/// Per-puzzle rules, fixed at creation.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PuzzleConfig {
+    /// Bytes per guess and answer, `MIN_WORD_LENGTH..=MAX_WORD_LENGTH`.
+    pub word_length: u32,
+    /// Guesses allowed per player, `1..=MAX_ATTEMPTS_LIMIT`.
+    pub max_attempts: u32,
+    /// Bytes a guess may contain; empty allows any byte.
+    pub alphabet: Bytes,
+    /// Later guesses must reuse earlier hints (checked at finalization).
+    pub hard_mode: bool,
+}
+
 /// Puzzle metadata and result summary.
 #[contracttype]
 #[derive(Clone)]
@@ -117,32 +223,57 @@ pub struct PuzzleData {
     /// SHA-256(plaintext_answer) committed at creation time.
     pub answer_commitment: BytesN<32>,
     pub status: PuzzleStatus,
+    pub config: PuzzleConfig,
     /// Plaintext answer — empty until `reveal_answer` is called.
     pub answer: Bytes,
     /// Number of players who solved the puzzle.
     pub winner_count: u32,
     /// Number of distinct players who submitted at least one attempt.
     pub player_count: u32,
+    /// Number of players whose attempts have been scored.
+    pub finalized_count: u32,
+    /// Merkle root of allowed guesses, snapshotted from the contract's
+    /// dictionary at creation. `None` accepts any word.
+    pub dictionary_root: Option<BytesN<32>>,
+    /// Reward reserved in the prize pool by `fund_puzzle` (0 = unfunded).
+    pub reward_budget: i128,
+    /// Reward weight per solving guess, snapshotted at creation.
+    pub attempt_weights: Vec<u32>,
+    /// Sum of the weights of all winners finalized so far.
+    pub total_weight: u32,
+    /// Total paid out through `claim_reward`.
+    pub claimed_total: i128,
+    /// Reservation given back to the prize pool.
+    pub released: i128,
+    /// Last timestamp at which rewards can be claimed; set at finalization.
+    pub claim_deadline: u64,
+    /// Set once `sweep_unclaimed` has run.
+    pub swept: bool,
 }
 
 /// A single scored guess.
 #[contracttype]
 #[derive(Clone)]
 pub struct Attempt {
-    /// The 5-letter guess submitted by the player.
+    /// The guess submitted by the player (`word_length` bytes).
     pub guess: Bytes,
-    /// Per-character scores: Vec of SCORE_* constants, length == WORD_LENGTH.
+    /// Per-character scores: Vec of SCORE_* constants, length == word_length.
     /// Empty until the puzzle is finalized.
     pub scores: Vec<u32>,
+    /// Set at finalization of a hard-mode puzzle if this or an earlier
+    /// attempt ignored a revealed hint. Disqualified attempts cannot win.
+    pub disqualified: bool,
 }
 
 /// Storage key discriminants.
 ///
-/// Instance keys (Admin, PrizePoolContract, BalanceContract) live in a single
-/// ledger entry and hold small, fixed-size contract config.
+/// Instance keys (Admin, PrizePoolContract, BalanceContract, DictionaryRoot,
+/// AttemptWeights, ClaimWindow) live in a single ledger entry and hold small,
+/// fixed-size contract config.
 ///
-/// Persistent keys (Puzzle, PlayerList, Attempts, Winner) are per-puzzle and
-/// per-player, each stored as an independent ledger entry with its own TTL.
+/// Persistent keys (Puzzle, PlayerList, Attempts, Winner, PlayerFinalized,
+/// SolvedAt, Claimed) are per-puzzle and per-player, each stored as an
+/// independent ledger entry with its own TTL.
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
@@ -150,6 +281,12 @@ pub enum DataKey {
     Admin,
     PrizePoolContract,
     BalanceContract,
+    /// Merkle root of allowed words applied to newly created puzzles.
+    DictionaryRoot,
+    /// Vec<u32> of reward weights applied to newly created puzzles.
+    AttemptWeights,
+    /// Claim window in seconds applied to puzzles finalized afterwards.
+    ClaimWindow,
     // --- persistent() keys ---
     /// PuzzleData keyed by puzzle_id.
     Puzzle(u64),
@@ -159,6 +296,12 @@ pub enum DataKey {
     Attempts(u64, Address),
     /// Set to `true` when a player solves the puzzle.
     Winner(u64, Address),
+    /// Set to `true` once a player's attempts have been scored.
+    PlayerFinalized(u64, Address),
+    /// 1-based number of the guess on which a winner first solved the puzzle.
+    SolvedAt(u64, Address),
+    /// Set to `true` once a winner has claimed their reward.
+    Claimed(u64, Address),
 }
 
 // ---------------------------------------------------------------------------
@@ -170,6 +313,12 @@ pub struct PuzzleCreated {
     #[topic]
     pub puzzle_id: u64,
     pub answer_commitment: BytesN<32>,
+    pub config: PuzzleConfig,
+}
+
+#[contractevent]
+pub struct DictionaryRootSet {
+    pub root: Option<BytesN<32>>,
 }
 
 #[contractevent]
@@ -189,12 +338,52 @@ pub struct AnswerRevealed {
     pub puzzle_id: u64,
 }
 
+#[contractevent]
+pub struct PlayerResultFinalized {
+    #[topic]
+    pub puzzle_id: u64,
+    #[topic]
+    pub player: Address,
+    pub solved: bool,
+}
+
 #[contractevent]
 pub struct PuzzleFinalized {
     #[topic]
     pub puzzle_id: u64,
     pub answer: Bytes,
     pub winner_count: u32,
+    pub total_weight: u32,
+    pub claim_deadline: u64,
+}
+
+#[contractevent]
+pub struct PuzzleFunded {
+    #[topic]
+    pub puzzle_id: u64,
+    pub reward_budget: i128,
+}
+
+#[contractevent]
+pub struct AttemptWeightsSet {
+    pub weights: Vec<u32>,
+}
+
+#[contractevent]
+pub struct RewardClaimed {
+    #[topic]
+    pub puzzle_id: u64,
+    #[topic]
+    pub player: Address,
+    pub solved_at: u32,
+    pub amount: i128,
+}
+
+#[contractevent]
+pub struct UnclaimedSwept {
+    #[topic]
+    pub puzzle_id: u64,
+    pub amount: i128,
 }
 
 // ---------------------------------------------------------------------------
@@ -237,6 +426,75 @@ impl WordleClone {
         Ok(())
     }
 
+    // -----------------------------------------------------------------------
+    // set_dictionary_root
+    // -----------------------------------------------------------------------
+
+    /// Commit to the Merkle root of allowed words. Admin only.
+    ///
+    /// Applies to puzzles created afterwards; existing puzzles keep the root
+    /// they were created with. `None` removes the dictionary check.
+    ///
+    /// Emits `DictionaryRootSet`.
+    pub fn set_dictionary_root(env: Env, root: Option<BytesN<32>>) -> Result<(), Error> {
+        let admin = get_admin(&env)?;
+        admin.require_auth();
+
+        match &root {
+            Some(root) => env.storage().instance().set(&DataKey::DictionaryRoot, root),
+            None => env.storage().instance().remove(&DataKey::DictionaryRoot),
+        }
+
+        DictionaryRootSet { root }.publish(&env);
+
+        Ok(())
+    }
+
+    // -----------------------------------------------------------------------
+    // set_attempt_weights / set_claim_window
+    // -----------------------------------------------------------------------
+
+    /// Set the reward weight of a solve on guess 1, 2, ... Admin only.
+    ///
+    /// Weights must be positive, at most `MAX_ATTEMPT_WEIGHT` and non-increasing,
+    /// with 1 to `MAX_ATTEMPTS_LIMIT` entries; solves on a guess past the last
+    /// entry earn nothing. Applies to puzzles created afterwards.
+    ///
+    /// Emits `AttemptWeightsSet`.
+    pub fn set_attempt_weights(env: Env, weights: Vec<u32>) -> Result<(), Error> {
+        let admin = get_admin(&env)?;
+        admin.require_auth();
+
+        if weights.is_empty() || weights.len() > MAX_ATTEMPTS_LIMIT {
+            return Err(Error::InvalidWeights);
+        }
+        let mut previous = MAX_ATTEMPT_WEIGHT;
+        for weight in weights.iter() {
+            if weight == 0 || weight > previous {
+                return Err(Error::InvalidWeights);
+            }
+            previous = weight;
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::AttemptWeights, &weights);
+        AttemptWeightsSet { weights }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Set how long winners have to claim after a puzzle is finalized, in
+    /// seconds. Admin only. Applies to puzzles finalized afterwards.
+    pub fn set_claim_window(env: Env, window: u64) -> Result<(), Error> {
+        let admin = get_admin(&env)?;
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::ClaimWindow, &window);
+
+        Ok(())
+    }
+
     // -----------------------------------------------------------------------
     // create_daily_puzzle
     // -----------------------------------------------------------------------
@@ -245,7 +503,8 @@ impl WordleClone {
     ///
     /// `puzzle_id` must be unique. `answer_commitment` is `SHA-256(answer_bytes)`
     /// computed off-chain. The plaintext answer is never stored until the admin
-    /// calls `reveal_answer`.
+    /// calls `reveal_answer`. The current dictionary root, if any, is fixed for
+    /// the puzzle's lifetime.
     ///
     /// Emits `PuzzleCreated`.
     pub fn create_daily_puzzle(
@@ -253,31 +512,78 @@ impl WordleClone {
         puzzle_id: u64,
         answer_commitment: BytesN<32>,
     ) -> Result<(), Error> {
+        let config = PuzzleConfig {
+            word_length: WORD_LENGTH,
+            max_attempts: MAX_ATTEMPTS,
+            alphabet: Bytes::new(&env),
+            hard_mode: false,
+        };
+        create_puzzle(&env, puzzle_id, answer_commitment, config)
+    }
+
+    // -----------------------------------------------------------------------
+    // create_configured_puzzle
+    // -----------------------------------------------------------------------
+
+    /// Create a puzzle with its own word length, attempt limit, alphabet and
+    /// hard mode. Admin only.
+    ///
+    /// `word_length` must be within `MIN_WORD_LENGTH..=MAX_WORD_LENGTH`,
+    /// `max_attempts` within `1..=MAX_ATTEMPTS_LIMIT`, and `alphabet` at most
+    /// `MAX_ALPHABET_LEN` bytes (empty allows any byte).
+    ///
+    /// Emits `PuzzleCreated`.
+    pub fn create_configured_puzzle(
+        env: Env,
+        puzzle_id: u64,
+        answer_commitment: BytesN<32>,
+        config: PuzzleConfig,
+    ) -> Result<(), Error> {
+        if config.word_length < MIN_WORD_LENGTH
+            || config.word_length > MAX_WORD_LENGTH
+            || config.max_attempts == 0
+            || config.max_attempts > MAX_ATTEMPTS_LIMIT
+            || config.alphabet.len() > MAX_ALPHABET_LEN
+        {
+            return Err(Error::InvalidConfig);
+        }
+        create_puzzle(&env, puzzle_id, answer_commitment, config)
+    }
+
+    // -----------------------------------------------------------------------
+    // fund_puzzle
+    // -----------------------------------------------------------------------
+
+    /// Reserve `reward_budget` in the prize pool for an open puzzle. Admin only.
+    ///
+    /// A puzzle can be funded once, before its answer is revealed. The prize
+    /// pool reservation uses `puzzle_id` as its game id.
+    ///
+    /// Emits `PuzzleFunded`.
+    pub fn fund_puzzle(env: Env, puzzle_id: u64, reward_budget: i128) -> Result<(), Error> {
         let admin = get_admin(&env)?;
         admin.require_auth();
 
-        if env.storage().persistent().has(&DataKey::Puzzle(puzzle_id)) {
-            return Err(Error::PuzzleAlreadyExists);
+        if reward_budget <= 0 {
+            return Err(Error::InvalidAmount);
         }
 
-        let puzzle = PuzzleData {
-            answer_commitment: answer_commitment.clone(),
-            status: PuzzleStatus::Open,
-            answer: Bytes::new(&env),
-            winner_count: 0,
-            player_count: 0,
-        };
+        let mut puzzle = load_puzzle(&env, puzzle_id)?;
+        if puzzle.status != PuzzleStatus::Open {
+            return Err(Error::PuzzleNotOpen);
+        }
+        if puzzle.reward_budget > 0 {
+            return Err(Error::PuzzleAlreadyFunded);
+        }
+
+        prize_pool_client(&env)?.reserve(&admin, &puzzle_id, &reward_budget);
 
+        puzzle.reward_budget = reward_budget;
         persist_set(&env, DataKey::Puzzle(puzzle_id), &puzzle);
-        persist_set(
-            &env,
-            DataKey::PlayerList(puzzle_id),
-            &Vec::<Address>::new(&env),
-        );
 
-        PuzzleCreated {
+        PuzzleFunded {
             puzzle_id,
-            answer_commitment,
+            reward_budget,
         }
         .publish(&env);
 
@@ -288,11 +594,14 @@ impl WordleClone {
     // submit_attempt
     // -----------------------------------------------------------------------
 
-    /// Submit a 5-letter guess for an open puzzle.
+    /// Submit a guess for an open puzzle.
     ///
-    /// A player may submit up to `MAX_ATTEMPTS` (6) guesses. Guesses must be
-    /// exactly `WORD_LENGTH` (5) bytes. Scores are computed after finalization;
-    /// the `scores` field is empty until then.
+    /// A player may submit up to the puzzle's `max_attempts` guesses. Guesses
+    /// must be exactly `word_length` bytes, each in the puzzle's alphabet if it
+    /// has one (`InvalidCharacter` otherwise). If the puzzle has a dictionary,
+    /// `proof` must show the guess is one of its words (see `verify_word`);
+    /// otherwise it is ignored. Scores are computed after finalization; the
+    /// `scores` field is empty until then.
     ///
     /// Emits `AttemptSubmitted`.
     pub fn submit_attempt(
@@ -300,13 +609,10 @@ impl WordleClone {
         player: Address,
         puzzle_id: u64,
         attempt: Bytes,
+        proof: Vec<BytesN<32>>,
     ) -> Result<(), Error> {
         player.require_auth();
 
-        if attempt.len() != WORD_LENGTH {
-            return Err(Error::InvalidWordLength);
-        }
-
         let mut puzzle: PuzzleData = env
             .storage()
             .persistent()
@@ -317,6 +623,23 @@ impl WordleClone {
             return Err(Error::PuzzleNotOpen);
         }
 
+        if attempt.len() != puzzle.config.word_length {
+            return Err(Error::InvalidWordLength);
+        }
+        let alphabet = &puzzle.config.alphabet;
+        if !alphabet.is_empty() && attempt.iter().any(|c| !alphabet.iter().any(|a| a == c)) {
+            return Err(Error::InvalidCharacter);
+        }
+
+        if let Some(root) = &puzzle.dictionary_root {
+            if proof.len() > MAX_PROOF_DEPTH {
+                return Err(Error::InvalidProof);
+            }
+            if !verify_word(&env, root, &attempt, &proof) {
+                return Err(Error::NotInDictionary);
+            }
+        }
+
         let mut attempts: Vec<Attempt> = env
             .storage()
             .persistent()
@@ -324,7 +647,7 @@ impl WordleClone {
             .unwrap_or_else(|| Vec::new(&env));
 
         let attempt_number = attempts.len();
-        if attempt_number >= MAX_ATTEMPTS {
+        if attempt_number >= puzzle.config.max_attempts {
             return Err(Error::TooManyAttempts);
         }
 
@@ -348,6 +671,7 @@ impl WordleClone {
         attempts.push_back(Attempt {
             guess: attempt.clone(),
             scores: Vec::new(&env),
+            disqualified: false,
         });
         persist_set(
             &env,
@@ -374,21 +698,21 @@ impl WordleClone {
     ///
     /// Verifies `SHA-256(answer) == answer_commitment`. Transitions the puzzle
     /// to `Revealed` state; no new player guesses are accepted after this call.
-    /// Must be called before `finalize_result`.
+    /// Must be called before any finalization.
     pub fn reveal_answer(env: Env, puzzle_id: u64, answer: Bytes) -> Result<(), Error> {
         let admin = get_admin(&env)?;
         admin.require_auth();
 
-        if answer.len() != WORD_LENGTH {
-            return Err(Error::InvalidWordLength);
-        }
-
         let mut puzzle: PuzzleData = env
             .storage()
             .persistent()
             .get(&DataKey::Puzzle(puzzle_id))
             .ok_or(Error::PuzzleNotFound)?;
 
+        if answer.len() != puzzle.config.word_length {
+            return Err(Error::InvalidWordLength);
+        }
+
         if puzzle.status != PuzzleStatus::Open {
             return Err(Error::PuzzleAlreadyFinalized);
         }
@@ -411,26 +735,22 @@ impl WordleClone {
     // finalize_result
     // -----------------------------------------------------------------------
 
-    /// Score all player attempts and record winners. Admin only.
+    /// Score every remaining player's attempts and record winners. Admin only.
     ///
     /// The `player` parameter is included per the issue interface; the contract
-    /// scores ALL players in a single pass for consistency, then transitions
+    /// scores ALL players not yet finalized in a single pass, then transitions
     /// the puzzle to `Finalized`. Must be called after `reveal_answer`.
     ///
-    /// Iterates all submissions (bounded by `MAX_PLAYERS_PER_PUZZLE × MAX_ATTEMPTS`).
+    /// Iterates all submissions (bounded by `MAX_PLAYERS_PER_PUZZLE × max_attempts`).
     /// A player is a winner if any of their attempts matches the answer exactly.
+    /// For large puzzles prefer `finalize_self` / `finalize_batch`.
     ///
     /// Emits `PuzzleFinalized`.
     pub fn finalize_result(env: Env, player: Address, puzzle_id: u64) -> Result<(), Error> {
         let admin = get_admin(&env)?;
         admin.require_auth();
 
-        let mut puzzle: PuzzleData = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Puzzle(puzzle_id))
-            .ok_or(Error::PuzzleNotFound)?;
-
+        let mut puzzle = load_puzzle(&env, puzzle_id)?;
         if puzzle.status == PuzzleStatus::Finalized {
             return Err(Error::PuzzleAlreadyFinalized);
         }
@@ -442,60 +762,195 @@ impl WordleClone {
         // public interface but scoring covers all players.
         let _ = player;
 
-        let answer = puzzle.answer.clone();
+        let players = load_players(&env, puzzle_id);
+        for p in players.iter() {
+            finalize_player(&env, puzzle_id, &mut puzzle, &p)?;
+        }
+        save_finalization(&env, puzzle_id, &mut puzzle)?;
 
-        let players: Vec<Address> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::PlayerList(puzzle_id))
-            .unwrap_or_else(|| Vec::new(&env));
+        Ok(())
+    }
 
-        let mut winner_count: u32 = 0;
+    // -----------------------------------------------------------------------
+    // finalize_self
+    // -----------------------------------------------------------------------
 
-        for p in players.iter() {
-            let attempts: Vec<Attempt> = env
-                .storage()
-                .persistent()
-                .get(&DataKey::Attempts(puzzle_id, p.clone()))
-                .unwrap_or_else(|| Vec::new(&env));
+    /// Score the caller's own attempts once the answer is revealed.
+    ///
+    /// Permissionless: any player may finalize themselves without waiting for
+    /// the admin. Idempotent — calling it again, or after the puzzle is
+    /// finalized, has no effect. Returns whether the player solved the puzzle.
+    ///
+    /// Emits `PlayerResultFinalized` on first call, and `PuzzleFinalized` if
+    /// this was the last player.
+    pub fn finalize_self(env: Env, player: Address, puzzle_id: u64) -> Result<bool, Error> {
+        player.require_auth();
 
-            let len = attempts.len();
-            let mut scored: Vec<Attempt> = Vec::new(&env);
-            let mut player_won = false;
-
-            for i in 0..len {
-                let att = attempts.get(i).unwrap();
-                let scores = score_guess(&env, &att.guess, &answer);
-                let solved = is_all_correct(&scores);
-                if solved {
-                    player_won = true;
-                }
-                scored.push_back(Attempt {
-                    guess: att.guess,
-                    scores,
-                });
-            }
+        let mut puzzle = load_puzzle(&env, puzzle_id)?;
+        if puzzle.status == PuzzleStatus::Open {
+            return Err(Error::AnswerNotRevealed);
+        }
 
-            persist_set(&env, DataKey::Attempts(puzzle_id, p.clone()), &scored);
+        if puzzle.status == PuzzleStatus::Revealed
+            && finalize_player(&env, puzzle_id, &mut puzzle, &player)?
+        {
+            save_finalization(&env, puzzle_id, &mut puzzle)?;
+        }
+
+        Ok(Self::is_winner(env, puzzle_id, player))
+    }
 
-            if player_won {
-                persist_set(&env, DataKey::Winner(puzzle_id, p.clone()), &true);
-                winner_count = winner_count.checked_add(1).ok_or(Error::Overflow)?;
+    // -----------------------------------------------------------------------
+    // finalize_batch
+    // -----------------------------------------------------------------------
+
+    /// Score up to `count` players of `PlayerList`, starting at index `start`.
+    /// Admin only.
+    ///
+    /// Players already finalized are skipped, so overlapping or repeated pages
+    /// are harmless. `count` must be between 1 and `MAX_FINALIZE_BATCH`; a
+    /// `start` past the end of the list finalizes nobody. Returns the number of
+    /// players newly finalized.
+    ///
+    /// Emits `PlayerResultFinalized` per player, and `PuzzleFinalized` once the
+    /// last player is scored.
+    pub fn finalize_batch(env: Env, puzzle_id: u64, start: u32, count: u32) -> Result<u32, Error> {
+        let admin = get_admin(&env)?;
+        admin.require_auth();
+
+        if count == 0 || count > MAX_FINALIZE_BATCH {
+            return Err(Error::InvalidBatch);
+        }
+
+        let mut puzzle = load_puzzle(&env, puzzle_id)?;
+        if puzzle.status == PuzzleStatus::Open {
+            return Err(Error::AnswerNotRevealed);
+        }
+        if puzzle.status == PuzzleStatus::Finalized {
+            return Ok(0);
+        }
+
+        let players = load_players(&env, puzzle_id);
+        let end = start.saturating_add(count).min(players.len());
+        let mut finalized: u32 = 0;
+        for i in start..end {
+            let p = players.get(i).ok_or(Error::InvalidBatch)?;
+            if finalize_player(&env, puzzle_id, &mut puzzle, &p)? {
+                finalized = finalized.checked_add(1).ok_or(Error::Overflow)?;
             }
         }
+        if finalized > 0 || puzzle.player_count == 0 {
+            save_finalization(&env, puzzle_id, &mut puzzle)?;
+        }
 
-        puzzle.status = PuzzleStatus::Finalized;
-        puzzle.winner_count = winner_count;
+        Ok(finalized)
+    }
+
+    // -----------------------------------------------------------------------
+    // claim_reward
+    // -----------------------------------------------------------------------
+
+    /// Claim a winner's share of a finalized puzzle's reward budget.
+    ///
+    /// The share is `reward_budget * weight / total_weight`, where `weight` is
+    /// the attempt weight of the guess the player solved on. It is paid to the
+    /// player straight out of the puzzle's prize pool reservation. Claims close
+    /// at `claim_deadline`.
+    ///
+    /// Emits `RewardClaimed`.
+    pub fn claim_reward(env: Env, player: Address, puzzle_id: u64) -> Result<i128, Error> {
+        player.require_auth();
+
+        let mut puzzle = load_puzzle(&env, puzzle_id)?;
+        if puzzle.status != PuzzleStatus::Finalized {
+            return Err(Error::PuzzleNotFinalized);
+        }
+        if puzzle.swept {
+            return Err(Error::AlreadySwept);
+        }
+        if env.ledger().timestamp() > puzzle.claim_deadline {
+            return Err(Error::ClaimWindowClosed);
+        }
+
+        let claimed_key = DataKey::Claimed(puzzle_id, player.clone());
+        if env.storage().persistent().has(&claimed_key) {
+            return Err(Error::AlreadyClaimed);
+        }
+
+        let solved_at: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SolvedAt(puzzle_id, player.clone()))
+            .ok_or(Error::NoRewardAvailable)?;
+        let amount = reward_for(&puzzle, solved_at)?;
+        if amount <= 0 {
+            return Err(Error::NoRewardAvailable);
+        }
+
+        // Record the claim before any external call.
+        persist_set(&env, claimed_key, &true);
+        puzzle.claimed_total = puzzle
+            .claimed_total
+            .checked_add(amount)
+            .ok_or(Error::Overflow)?;
         persist_set(&env, DataKey::Puzzle(puzzle_id), &puzzle);
 
-        PuzzleFinalized {
+        let admin = get_admin(&env)?;
+        prize_pool_client(&env)?.payout(&admin, &player, &puzzle_id, &amount);
+
+        RewardClaimed {
             puzzle_id,
-            answer,
-            winner_count,
+            player,
+            solved_at,
+            amount,
         }
         .publish(&env);
 
-        Ok(())
+        Ok(amount)
+    }
+
+    // -----------------------------------------------------------------------
+    // sweep_unclaimed
+    // -----------------------------------------------------------------------
+
+    /// Release the unclaimed part of a puzzle's reservation back to the prize
+    /// pool once the claim deadline has passed. Admin only. A puzzle nobody
+    /// solved has nothing to claim, so it may be swept as soon as it is
+    /// finalized.
+    ///
+    /// Returns the amount released (unclaimed shares plus rounding remainder).
+    ///
+    /// Emits `UnclaimedSwept`.
+    pub fn sweep_unclaimed(env: Env, puzzle_id: u64) -> Result<i128, Error> {
+        let admin = get_admin(&env)?;
+        admin.require_auth();
+
+        let mut puzzle = load_puzzle(&env, puzzle_id)?;
+        if puzzle.status != PuzzleStatus::Finalized {
+            return Err(Error::PuzzleNotFinalized);
+        }
+        if puzzle.swept {
+            return Err(Error::AlreadySwept);
+        }
+        if puzzle.total_weight > 0 && env.ledger().timestamp() <= puzzle.claim_deadline {
+            return Err(Error::ClaimWindowOpen);
+        }
+
+        let amount = puzzle
+            .reward_budget
+            .checked_sub(puzzle.claimed_total)
+            .and_then(|v| v.checked_sub(puzzle.released))
+            .ok_or(Error::Overflow)?;
+        if amount > 0 {
+            prize_pool_client(&env)?.release(&admin, &puzzle_id, &amount);
+            puzzle.released = puzzle.released.checked_add(amount).ok_or(Error::Overflow)?;
+        }
+        puzzle.swept = true;
+        persist_set(&env, DataKey::Puzzle(puzzle_id), &puzzle);
+
+        UnclaimedSwept { puzzle_id, amount }.publish(&env);
+
+        Ok(amount)
     }
 
     // -----------------------------------------------------------------------
@@ -510,6 +965,11 @@ impl WordleClone {
             .unwrap_or_else(|| Vec::new(&env))
     }
 
+    /// Return a single attempt by 0-based index, or `None` if out of range.
+    pub fn get_attempt(env: Env, player: Address, puzzle_id: u64, index: u32) -> Option<Attempt> {
+        Self::get_attempts(env, player, puzzle_id).get(index)
+    }
+
     // -----------------------------------------------------------------------
     // View helpers
     // -----------------------------------------------------------------------
@@ -519,6 +979,11 @@ impl WordleClone {
         env.storage().persistent().get(&DataKey::Puzzle(puzzle_id))
     }
 
+    /// Returns the dictionary root applied to new puzzles, if any.
+    pub fn get_dictionary_root(env: Env) -> Option<BytesN<32>> {
+        env.storage().instance().get(&DataKey::DictionaryRoot)
+    }
+
     /// Returns `true` if the player solved the puzzle.
     pub fn is_winner(env: Env, puzzle_id: u64, player: Address) -> bool {
         env.storage()
@@ -526,6 +991,35 @@ impl WordleClone {
             .get(&DataKey::Winner(puzzle_id, player))
             .unwrap_or(false)
     }
+
+    /// Returns the reward a player can claim (or has claimed) for a finalized
+    /// puzzle, or 0.
+    pub fn get_reward(env: Env, puzzle_id: u64, player: Address) -> i128 {
+        let Some(puzzle) = Self::get_puzzle(env.clone(), puzzle_id) else {
+            return 0;
+        };
+        if puzzle.status != PuzzleStatus::Finalized {
+            return 0;
+        }
+        env.storage()
+            .persistent()
+            .get::<DataKey, u32>(&DataKey::SolvedAt(puzzle_id, player))
+            .and_then(|solved_at| reward_for(&puzzle, solved_at).ok())
+            .unwrap_or(0)
+    }
+
+    /// Returns the reward weights applied to new puzzles.
+    pub fn get_attempt_weights(env: Env) -> Vec<u32> {
+        attempt_weights(&env)
+    }
+
+    /// Returns `true` once the player's attempts have been scored.
+    pub fn is_player_finalized(env: Env, puzzle_id: u64, player: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PlayerFinalized(puzzle_id, player))
+            .unwrap_or(false)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -538,13 +1032,18 @@ impl WordleClone {
 /// 2. Second pass: for remaining positions, check if the guess letter exists
 ///    in the remaining answer characters (PRESENT), consuming each answer
 ///    letter at most once.
+///
+/// Works for any word length up to `MAX_WORD_LENGTH`; the result has one
+/// score per answer character.
 pub fn score_guess(env: &Env, guess: &Bytes, answer: &Bytes) -> Vec<u32> {
-    let mut scores: [u32; 5] = [SCORE_ABSENT; 5];
-    let mut answer_used: [bool; 5] = [false; 5];
-    let mut guess_matched: [bool; 5] = [false; 5];
+    const MAX: usize = MAX_WORD_LENGTH as usize;
+    let len = (answer.len() as usize).min(MAX);
+    let mut scores: [u32; MAX] = [SCORE_ABSENT; MAX];
+    let mut answer_used: [bool; MAX] = [false; MAX];
+    let mut guess_matched: [bool; MAX] = [false; MAX];
 
     // Pass 1 — exact matches.
-    for i in 0..WORD_LENGTH as usize {
+    for i in 0..len {
         if guess.get(i as u32) == answer.get(i as u32) {
             scores[i] = SCORE_CORRECT;
             answer_used[i] = true;
@@ -553,12 +1052,12 @@ pub fn score_guess(env: &Env, guess: &Bytes, answer: &Bytes) -> Vec<u32> {
     }
 
     // Pass 2 — present-but-wrong-position.
-    for i in 0..WORD_LENGTH as usize {
+    for i in 0..len {
         if guess_matched[i] {
             continue;
         }
         let g = guess.get(i as u32).unwrap_or(0);
-        for (j, used) in answer_used.iter_mut().enumerate() {
+        for (j, used) in answer_used[..len].iter_mut().enumerate() {
             if *used {
                 continue;
             }
@@ -569,22 +1068,307 @@ pub fn score_guess(env: &Env, guess: &Bytes, answer: &Bytes) -> Vec<u32> {
             }
         }
     }
-
-    let mut result: Vec<u32> = Vec::new(env);
-    for &s in scores.iter() {
-        result.push_back(s);
+
+    let mut result: Vec<u32> = Vec::new(env);
+    for &s in scores[..len].iter() {
+        result.push_back(s);
+    }
+    result
+}
+
+/// Returns `true` if `guess` respects the hints `prev_scores` gave for
+/// `prev_guess` (the hard-mode rule).
+///
+/// Every CORRECT letter must stay in its position, and every letter marked
+/// CORRECT or PRESENT must appear in `guess` at least as many times as it
+/// was marked.
+pub fn satisfies_hints(prev_guess: &Bytes, prev_scores: &Vec<u32>, guess: &Bytes) -> bool {
+    for (i, score) in prev_scores.iter().enumerate() {
+        let i = i as u32;
+        let letter = prev_guess.get(i);
+        if score == SCORE_CORRECT && guess.get(i) != letter {
+            return false;
+        }
+        if score == SCORE_ABSENT {
+            continue;
+        }
+        let Some(letter) = letter else {
+            return false;
+        };
+        let marked = prev_guess
+            .iter()
+            .zip(prev_scores.iter())
+            .filter(|(c, s)| *c == letter && *s != SCORE_ABSENT)
+            .count();
+        let reused = guess.iter().filter(|c| *c == letter).count();
+        if reused < marked {
+            return false;
+        }
+    }
+    true
+}
+
+/// Hash a dictionary word into a Merkle leaf: `SHA-256(0x00 || word)`.
+pub fn dictionary_leaf(env: &Env, word: &Bytes) -> BytesN<32> {
+    let mut data = Bytes::from_array(env, &[LEAF_PREFIX]);
+    data.append(word);
+    env.crypto().sha256(&data).into()
+}
+
+/// Hash two sibling nodes into their parent: `SHA-256(0x01 || lo || hi)`.
+///
+/// The pair is sorted before hashing, so proofs need no left/right flags.
+pub fn dictionary_node(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+    let (lo, hi) = if a.to_array() <= b.to_array() {
+        (a, b)
+    } else {
+        (b, a)
+    };
+    let mut data = Bytes::from_array(env, &[NODE_PREFIX]);
+    data.append(&Bytes::from(lo.clone()));
+    data.append(&Bytes::from(hi.clone()));
+    env.crypto().sha256(&data).into()
+}
+
+/// Returns `true` if `proof` (sibling hashes from leaf to root) links `word`
+/// to `root`.
+///
+/// Leaves and inner nodes use different prefixes, so an inner node cannot be
+/// passed off as a word.
+pub fn verify_word(env: &Env, root: &BytesN<32>, word: &Bytes, proof: &Vec<BytesN<32>>) -> bool {
+    let mut node = dictionary_leaf(env, word);
+    for sibling in proof.iter() {
+        node = dictionary_node(env, &node, &sibling);
+    }
+    node == *root
+}
+
+/// Returns `true` when the vec holds `word_length` scores, all `SCORE_CORRECT`.
+fn is_all_correct(scores: &Vec<u32>, word_length: u32) -> bool {
+    for i in 0..scores.len() {
+        if scores.get(i).unwrap_or(0) != SCORE_CORRECT {
+            return false;
+        }
+    }
+    scores.len() == word_length
+}
+
+/// Store and announce a new puzzle. Shared by `create_daily_puzzle` and
+/// `create_configured_puzzle`.
+fn create_puzzle(
+    env: &Env,
+    puzzle_id: u64,
+    answer_commitment: BytesN<32>,
+    config: PuzzleConfig,
+) -> Result<(), Error> {
+    let admin = get_admin(env)?;
+    admin.require_auth();
+
+    if env.storage().persistent().has(&DataKey::Puzzle(puzzle_id)) {
+        return Err(Error::PuzzleAlreadyExists);
+    }
+
+    let puzzle = PuzzleData {
+        answer_commitment: answer_commitment.clone(),
+        status: PuzzleStatus::Open,
+        config: config.clone(),
+        answer: Bytes::new(env),
+        winner_count: 0,
+        player_count: 0,
+        finalized_count: 0,
+        dictionary_root: env.storage().instance().get(&DataKey::DictionaryRoot),
+        reward_budget: 0,
+        attempt_weights: attempt_weights(env),
+        total_weight: 0,
+        claimed_total: 0,
+        released: 0,
+        claim_deadline: 0,
+        swept: false,
+    };
+
+    persist_set(env, DataKey::Puzzle(puzzle_id), &puzzle);
+    persist_set(
+        env,
+        DataKey::PlayerList(puzzle_id),
+        &Vec::<Address>::new(env),
+    );
+
+    PuzzleCreated {
+        puzzle_id,
+        answer_commitment,
+        config,
+    }
+    .publish(env);
+
+    Ok(())
+}
+
+/// Score one player's attempts and record a win. Returns `false` without
+/// touching storage if the player was already finalized.
+///
+/// Updates `puzzle.finalized_count` / `winner_count` in memory; callers persist
+/// the puzzle with `save_finalization`.
+fn finalize_player(
+    env: &Env,
+    puzzle_id: u64,
+    puzzle: &mut PuzzleData,
+    player: &Address,
+) -> Result<bool, Error> {
+    let done_key = DataKey::PlayerFinalized(puzzle_id, player.clone());
+    if env.storage().persistent().has(&done_key) {
+        return Ok(false);
+    }
+
+    let attempts_key = DataKey::Attempts(puzzle_id, player.clone());
+    let Some(attempts) = env
+        .storage()
+        .persistent()
+        .get::<DataKey, Vec<Attempt>>(&attempts_key)
+    else {
+        // Not a registered player; nothing to score.
+        return Ok(false);
+    };
+
+    let mut scored: Vec<Attempt> = Vec::new(env);
+    let mut solved_at: Option<u32> = None;
+    let mut disqualified = false;
+    for (i, att) in attempts.iter().enumerate() {
+        let scores = score_guess(env, &att.guess, &puzzle.answer);
+        // Hard mode: once a guess ignores an earlier hint, the run is over.
+        if puzzle.config.hard_mode && !disqualified {
+            disqualified = scored
+                .iter()
+                .any(|prev| !satisfies_hints(&prev.guess, &prev.scores, &att.guess));
+        }
+        if solved_at.is_none()
+            && !disqualified
+            && is_all_correct(&scores, puzzle.config.word_length)
+        {
+            solved_at = Some(i as u32 + 1);
+        }
+        scored.push_back(Attempt {
+            guess: att.guess,
+            scores,
+            disqualified,
+        });
+    }
+    persist_set(env, attempts_key, &scored);
+    persist_set(env, done_key, &true);
+
+    let solved = solved_at.is_some();
+    if let Some(solved_at) = solved_at {
+        persist_set(env, DataKey::Winner(puzzle_id, player.clone()), &true);
+        persist_set(
+            env,
+            DataKey::SolvedAt(puzzle_id, player.clone()),
+            &solved_at,
+        );
+        puzzle.winner_count = puzzle.winner_count.checked_add(1).ok_or(Error::Overflow)?;
+        puzzle.total_weight = puzzle
+            .total_weight
+            .checked_add(attempt_weight(puzzle, solved_at))
+            .ok_or(Error::Overflow)?;
+    }
+    puzzle.finalized_count = puzzle
+        .finalized_count
+        .checked_add(1)
+        .ok_or(Error::Overflow)?;
+
+    PlayerResultFinalized {
+        puzzle_id,
+        player: player.clone(),
+        solved,
     }
-    result
+    .publish(env);
+
+    Ok(true)
 }
 
-/// Returns `true` when every score in the vec is `SCORE_CORRECT`.
-fn is_all_correct(scores: &Vec<u32>) -> bool {
-    for i in 0..scores.len() {
-        if scores.get(i).unwrap_or(0) != SCORE_CORRECT {
-            return false;
+/// Persist the puzzle after finalizing players, completing it once every
+/// player has been scored.
+///
+/// On completion the claim window opens. Nothing is released here, since any
+/// player may complete the puzzle through `finalize_self`; if no winner earned
+/// a weight the admin releases the budget with `sweep_unclaimed`.
+fn save_finalization(env: &Env, puzzle_id: u64, puzzle: &mut PuzzleData) -> Result<(), Error> {
+    let complete = puzzle.finalized_count >= puzzle.player_count;
+    if complete {
+        puzzle.status = PuzzleStatus::Finalized;
+        let window: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ClaimWindow)
+            .unwrap_or(DEFAULT_CLAIM_WINDOW);
+        puzzle.claim_deadline = env
+            .ledger()
+            .timestamp()
+            .checked_add(window)
+            .ok_or(Error::Overflow)?;
+    }
+    persist_set(env, DataKey::Puzzle(puzzle_id), puzzle);
+
+    if complete {
+        PuzzleFinalized {
+            puzzle_id,
+            answer: puzzle.answer.clone(),
+            winner_count: puzzle.winner_count,
+            total_weight: puzzle.total_weight,
+            claim_deadline: puzzle.claim_deadline,
         }
+        .publish(env);
+    }
+    Ok(())
+}
+
+/// Reward weights for new puzzles: the configured table or the default one.
+fn attempt_weights(env: &Env) -> Vec<u32> {
+    env.storage()
+        .instance()
+        .get(&DataKey::AttemptWeights)
+        .unwrap_or_else(|| Vec::from_array(env, DEFAULT_ATTEMPT_WEIGHTS))
+}
+
+/// Weight of a solve on guess `solved_at` (1-based); 0 past the table.
+fn attempt_weight(puzzle: &PuzzleData, solved_at: u32) -> u32 {
+    solved_at
+        .checked_sub(1)
+        .and_then(|i| puzzle.attempt_weights.get(i))
+        .unwrap_or(0)
+}
+
+/// A winner's share: `reward_budget * weight / total_weight`.
+fn reward_for(puzzle: &PuzzleData, solved_at: u32) -> Result<i128, Error> {
+    if puzzle.total_weight == 0 {
+        return Ok(0);
     }
-    scores.len() == WORD_LENGTH
+    puzzle
+        .reward_budget
+        .checked_mul(attempt_weight(puzzle, solved_at) as i128)
+        .and_then(|v| v.checked_div(puzzle.total_weight as i128))
+        .ok_or(Error::Overflow)
+}
+
+fn prize_pool_client(env: &Env) -> Result<PrizePoolClient<'_>, Error> {
+    let address: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::PrizePoolContract)
+        .ok_or(Error::NotInitialized)?;
+    Ok(PrizePoolClient::new(env, &address))
+}
+
+fn load_puzzle(env: &Env, puzzle_id: u64) -> Result<PuzzleData, Error> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Puzzle(puzzle_id))
+        .ok_or(Error::PuzzleNotFound)
+}
+
+fn load_players(env: &Env, puzzle_id: u64) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PlayerList(puzzle_id))
+        .unwrap_or_else(|| Vec::new(env))
 }
 
 /// Persist a value in persistent storage and extend its TTL.
@@ -609,7 +1393,101 @@ fn get_admin(env: &Env) -> Result<Address, Error> {
 #[cfg(test)]
 mod test {
     use super::*;
-    use soroban_sdk::{testutils::Address as _, Bytes, BytesN, Env, IntoVal};
+    use soroban_sdk::{
+        contract, contractimpl,
+        testutils::{Address as _, Ledger as _, MockAuth, MockAuthInvoke},
+        Bytes, BytesN, Env, IntoVal, Symbol,
+    };
+
+    /// Prize pool stand-in that tracks each game's reservation and rejects
+    /// payouts beyond it. Like the real pool, reserving and releasing need the
+    /// admin's signature.
+    #[contract]
+    pub struct MockPrizePool;
+
+    #[contracttype]
+    pub enum PoolKey {
+        Reserved(u64),
+        Paid(u64),
+    }
+
+    #[contractimpl]
+    impl MockPrizePool {
+        pub fn reserve(env: Env, admin: Address, game_id: u64, amount: i128) {
+            admin.require_auth();
+            env.storage()
+                .persistent()
+                .set(&PoolKey::Reserved(game_id), &amount);
+        }
+
+        pub fn release(env: Env, admin: Address, game_id: u64, amount: i128) {
+            admin.require_auth();
+            let reserved = Self::reserved(env.clone(), game_id);
+            env.storage()
+                .persistent()
+                .set(&PoolKey::Reserved(game_id), &(reserved - amount));
+        }
+
+        pub fn payout(env: Env, _admin: Address, _to: Address, game_id: u64, amount: i128) {
+            let paid = Self::paid(env.clone(), game_id) + amount;
+            if paid > Self::reserved(env.clone(), game_id) {
+                panic!("payout exceeds reservation");
+            }
+            env.storage()
+                .persistent()
+                .set(&PoolKey::Paid(game_id), &paid);
+        }
+
+        pub fn reserved(env: Env, game_id: u64) -> i128 {
+            env.storage()
+                .persistent()
+                .get(&PoolKey::Reserved(game_id))
+                .unwrap_or(0)
+        }
+
+        pub fn paid(env: Env, game_id: u64) -> i128 {
+            env.storage()
+                .persistent()
+                .get(&PoolKey::Paid(game_id))
+                .unwrap_or(0)
+        }
+    }
+
+    /// Balance stand-in that rejects overdrafts.
+    #[contract]
+    pub struct MockBalance;
+
+    #[contracttype]
+    pub enum BalanceKey {
+        Balance(Address),
+    }
+
+    #[contractimpl]
+    impl MockBalance {
+        pub fn credit(env: Env, _game: Address, user: Address, amount: i128, _reason: Symbol) {
+            let bal = Self::balance_of(env.clone(), user.clone());
+            env.storage()
+                .persistent()
+                .set(&BalanceKey::Balance(user), &(bal + amount));
+        }
+
+        pub fn debit(env: Env, _game: Address, user: Address, amount: i128, _reason: Symbol) {
+            let bal = Self::balance_of(env.clone(), user.clone());
+            if bal < amount {
+                panic!("insufficient balance");
+            }
+            env.storage()
+                .persistent()
+                .set(&BalanceKey::Balance(user), &(bal - amount));
+        }
+
+        pub fn balance_of(env: Env, user: Address) -> i128 {
+            env.storage()
+                .persistent()
+                .get(&BalanceKey::Balance(user))
+                .unwrap_or(0)
+        }
+    }
 
     fn sha256_of(env: &Env, data: &[u8]) -> BytesN<32> {
         let b = Bytes::from_slice(env, data);
@@ -620,12 +1498,27 @@ mod test {
         Bytes::from_slice(env, data)
     }
 
+    /// Build a four-word dictionary. Returns the root and each word's proof.
+    fn dictionary(env: &Env, words: [&[u8; 5]; 4]) -> (BytesN<32>, [Vec<BytesN<32>>; 4]) {
+        let leaves = words.map(|w| dictionary_leaf(env, &bytes5(env, w)));
+        let left = dictionary_node(env, &leaves[0], &leaves[1]);
+        let right = dictionary_node(env, &leaves[2], &leaves[3]);
+        let root = dictionary_node(env, &left, &right);
+        let proofs = [
+            soroban_sdk::vec![env, leaves[1].clone(), right.clone()],
+            soroban_sdk::vec![env, leaves[0].clone(), right.clone()],
+            soroban_sdk::vec![env, leaves[3].clone(), left.clone()],
+            soroban_sdk::vec![env, leaves[2].clone(), left.clone()],
+        ];
+        (root, proofs)
+    }
+
     fn setup(env: &Env) -> (WordleCloneClient<'_>, Address, Address, Address) {
         let id = env.register(WordleClone, ());
         let client = WordleCloneClient::new(env, &id);
         let admin = Address::generate(env);
-        let prize_pool = Address::generate(env);
-        let balance = Address::generate(env);
+        let prize_pool = env.register(MockPrizePool, ());
+        let balance = env.register(MockBalance, ());
         env.mock_all_auths();
         client.init(&admin, &prize_pool, &balance);
         (client, admin, prize_pool, balance)
@@ -648,8 +1541,8 @@ mod test {
         let winner = Address::generate(&env);
         let loser = Address::generate(&env);
 
-        client.submit_attempt(&winner, &1u64, &bytes5(&env, b"CRANE"));
-        client.submit_attempt(&loser, &1u64, &bytes5(&env, b"STALE"));
+        client.submit_attempt(&winner, &1u64, &bytes5(&env, b"CRANE"), &Vec::new(&env));
+        client.submit_attempt(&loser, &1u64, &bytes5(&env, b"STALE"), &Vec::new(&env));
 
         client.reveal_answer(&1u64, &bytes5(&env, b"CRANE"));
         client.finalize_result(&winner, &1u64);
@@ -676,7 +1569,7 @@ mod test {
         client.create_daily_puzzle(&2u64, &commitment);
 
         let player = Address::generate(&env);
-        client.submit_attempt(&player, &2u64, &bytes5(&env, b"PIANO"));
+        client.submit_attempt(&player, &2u64, &bytes5(&env, b"PIANO"), &Vec::new(&env));
 
         client.reveal_answer(&2u64, &bytes5(&env, b"PIANO"));
         client.finalize_result(&player, &2u64);
@@ -704,7 +1597,7 @@ mod test {
         client.create_daily_puzzle(&3u64, &commitment);
 
         let player = Address::generate(&env);
-        client.submit_attempt(&player, &3u64, &bytes5(&env, b"NACRE"));
+        client.submit_attempt(&player, &3u64, &bytes5(&env, b"NACRE"), &Vec::new(&env));
 
         client.reveal_answer(&3u64, &bytes5(&env, b"CRANE"));
         client.finalize_result(&player, &3u64);
@@ -733,10 +1626,11 @@ mod test {
 
         let player = Address::generate(&env);
         for _ in 0..MAX_ATTEMPTS {
-            client.submit_attempt(&player, &4u64, &bytes5(&env, b"STALE"));
+            client.submit_attempt(&player, &4u64, &bytes5(&env, b"STALE"), &Vec::new(&env));
         }
 
-        let result = client.try_submit_attempt(&player, &4u64, &bytes5(&env, b"STALE"));
+        let result =
+            client.try_submit_attempt(&player, &4u64, &bytes5(&env, b"STALE"), &Vec::new(&env));
         assert!(result.is_err());
     }
 
@@ -755,7 +1649,7 @@ mod test {
 
         let player = Address::generate(&env);
         let short = Bytes::from_slice(&env, b"HI");
-        let result = client.try_submit_attempt(&player, &5u64, &short);
+        let result = client.try_submit_attempt(&player, &5u64, &short, &Vec::new(&env));
         assert!(result.is_err());
     }
 
@@ -770,7 +1664,8 @@ mod test {
         env.mock_all_auths();
 
         let player = Address::generate(&env);
-        let result = client.try_submit_attempt(&player, &99u64, &bytes5(&env, b"CRANE"));
+        let result =
+            client.try_submit_attempt(&player, &99u64, &bytes5(&env, b"CRANE"), &Vec::new(&env));
         assert!(result.is_err());
     }
 
@@ -915,7 +1810,8 @@ mod test {
         client.reveal_answer(&11u64, &bytes5(&env, b"CRANE"));
 
         let late = Address::generate(&env);
-        let result = client.try_submit_attempt(&late, &11u64, &bytes5(&env, b"CRANE"));
+        let result =
+            client.try_submit_attempt(&late, &11u64, &bytes5(&env, b"CRANE"), &Vec::new(&env));
         assert!(result.is_err());
     }
 
@@ -937,7 +1833,8 @@ mod test {
         client.finalize_result(&dummy, &20u64);
 
         let late = Address::generate(&env);
-        let result = client.try_submit_attempt(&late, &20u64, &bytes5(&env, b"CRANE"));
+        let result =
+            client.try_submit_attempt(&late, &20u64, &bytes5(&env, b"CRANE"), &Vec::new(&env));
         assert!(result.is_err());
     }
 
@@ -977,9 +1874,9 @@ mod test {
 
         let player = Address::generate(&env);
         for _ in 0..(MAX_ATTEMPTS - 1) {
-            client.submit_attempt(&player, &13u64, &bytes5(&env, b"STALE"));
+            client.submit_attempt(&player, &13u64, &bytes5(&env, b"STALE"), &Vec::new(&env));
         }
-        client.submit_attempt(&player, &13u64, &bytes5(&env, b"CRANE"));
+        client.submit_attempt(&player, &13u64, &bytes5(&env, b"CRANE"), &Vec::new(&env));
 
         client.reveal_answer(&13u64, &bytes5(&env, b"CRANE"));
         client.finalize_result(&player, &13u64);
@@ -1071,9 +1968,9 @@ mod test {
         let w2 = Address::generate(&env);
         let loser = Address::generate(&env);
 
-        client.submit_attempt(&w1, &16u64, &bytes5(&env, b"CRANE"));
-        client.submit_attempt(&w2, &16u64, &bytes5(&env, b"CRANE"));
-        client.submit_attempt(&loser, &16u64, &bytes5(&env, b"STALE"));
+        client.submit_attempt(&w1, &16u64, &bytes5(&env, b"CRANE"), &Vec::new(&env));
+        client.submit_attempt(&w2, &16u64, &bytes5(&env, b"CRANE"), &Vec::new(&env));
+        client.submit_attempt(&loser, &16u64, &bytes5(&env, b"STALE"), &Vec::new(&env));
 
         client.reveal_answer(&16u64, &bytes5(&env, b"CRANE"));
         client.finalize_result(&w1, &16u64);
@@ -1084,4 +1981,565 @@ mod test {
         assert!(client.is_winner(&16u64, &w2));
         assert!(!client.is_winner(&16u64, &loser));
     }
+
+    // ------------------------------------------------------------------
+    // 20. Dictionary proofs — verify_word unit test
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_verify_word() {
+        let env = Env::default();
+        let words = [b"CRANE", b"STALE", b"PIANO", b"SPEED"];
+        let (root, proofs) = dictionary(&env, words);
+
+        for (word, proof) in words.iter().zip(proofs.iter()) {
+            assert!(verify_word(&env, &root, &bytes5(&env, word), proof));
+        }
+
+        // A real proof for another word does not carry a non-word.
+        assert!(!verify_word(
+            &env,
+            &root,
+            &bytes5(&env, b"AEIOU"),
+            &proofs[0]
+        ));
+        // Proofs are bound to their word.
+        assert!(!verify_word(
+            &env,
+            &root,
+            &bytes5(&env, b"STALE"),
+            &proofs[0]
+        ));
+        // The root alone is not a proof of anything.
+        assert!(!verify_word(
+            &env,
+            &root,
+            &bytes5(&env, b"CRANE"),
+            &Vec::new(&env)
+        ));
+    }
+
+    // ------------------------------------------------------------------
+    // 21. Dictionary puzzles reject guesses without a valid proof
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_dictionary_rejects_unknown_words() {
+        let env = Env::default();
+        let (client, _, _, _) = setup(&env);
+        env.mock_all_auths();
+
+        let commitment = sha256_of(&env, b"CRANE");
+        client.create_daily_puzzle(&30u64, &commitment);
+
+        let (root, proofs) = dictionary(&env, [b"CRANE", b"STALE", b"PIANO", b"SPEED"]);
+        client.set_dictionary_root(&Some(root.clone()));
+        assert_eq!(client.get_dictionary_root(), Some(root.clone()));
+        client.create_daily_puzzle(&31u64, &commitment);
+
+        // The earlier puzzle keeps accepting any word.
+        assert_eq!(client.get_puzzle(&30u64).unwrap().dictionary_root, None);
+        let player = Address::generate(&env);
+        client.submit_attempt(&player, &30u64, &bytes5(&env, b"AEIOU"), &Vec::new(&env));
+
+        assert_eq!(
+            client.get_puzzle(&31u64).unwrap().dictionary_root,
+            Some(root)
+        );
+        let result =
+            client.try_submit_attempt(&player, &31u64, &bytes5(&env, b"AEIOU"), &proofs[0]);
+        assert_eq!(result, Err(Ok(Error::NotInDictionary)));
+        let result =
+            client.try_submit_attempt(&player, &31u64, &bytes5(&env, b"STALE"), &Vec::new(&env));
+        assert_eq!(result, Err(Ok(Error::NotInDictionary)));
+
+        let mut too_long = proofs[1].clone();
+        for _ in 0..MAX_PROOF_DEPTH {
+            too_long.push_back(sha256_of(&env, b"PAD"));
+        }
+        let result = client.try_submit_attempt(&player, &31u64, &bytes5(&env, b"STALE"), &too_long);
+        assert_eq!(result, Err(Ok(Error::InvalidProof)));
+        assert_eq!(client.get_attempts(&player, &31u64).len(), 0);
+
+        client.submit_attempt(&player, &31u64, &bytes5(&env, b"STALE"), &proofs[1]);
+        client.submit_attempt(&player, &31u64, &bytes5(&env, b"CRANE"), &proofs[0]);
+        client.reveal_answer(&31u64, &bytes5(&env, b"CRANE"));
+        client.finalize_result(&player, &31u64);
+        assert!(client.is_winner(&31u64, &player));
+
+        // Clearing the root lifts the check for new puzzles only.
+        client.set_dictionary_root(&None);
+        assert_eq!(client.get_dictionary_root(), None);
+    }
+
+    // ------------------------------------------------------------------
+    // 22. Players finalize themselves, idempotently
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_finalize_self() {
+        let env = Env::default();
+        let (client, _, _, _) = setup(&env);
+        env.mock_all_auths();
+
+        let commitment = sha256_of(&env, b"CRANE");
+        client.create_daily_puzzle(&40u64, &commitment);
+
+        let solver = Address::generate(&env);
+        let loser = Address::generate(&env);
+        client.submit_attempt(&solver, &40u64, &bytes5(&env, b"STALE"), &Vec::new(&env));
+        client.submit_attempt(&solver, &40u64, &bytes5(&env, b"CRANE"), &Vec::new(&env));
+        client.submit_attempt(&loser, &40u64, &bytes5(&env, b"STALE"), &Vec::new(&env));
+
+        let result = client.try_finalize_self(&solver, &40u64);
+        assert_eq!(result, Err(Ok(Error::AnswerNotRevealed)));
+
+        client.reveal_answer(&40u64, &bytes5(&env, b"CRANE"));
+        assert!(client.finalize_self(&solver, &40u64));
+        assert!(client.is_player_finalized(&40u64, &solver));
+        assert!(!client.is_player_finalized(&40u64, &loser));
+
+        let puzzle = client.get_puzzle(&40u64).unwrap();
+        assert_eq!(puzzle.status, PuzzleStatus::Revealed);
+        assert_eq!(puzzle.finalized_count, 1);
+        assert_eq!(puzzle.winner_count, 1);
+
+        // Repeat calls change nothing.
+        assert!(client.finalize_self(&solver, &40u64));
+        assert_eq!(client.get_puzzle(&40u64).unwrap().winner_count, 1);
+
+        let first = client.get_attempt(&solver, &40u64, &0u32).unwrap();
+        assert_eq!(first.guess, bytes5(&env, b"STALE"));
+        assert_eq!(first.scores.len(), WORD_LENGTH);
+        assert!(client.get_attempt(&solver, &40u64, &2u32).is_none());
+
+        // The last player completes the puzzle.
+        assert!(!client.finalize_self(&loser, &40u64));
+        let puzzle = client.get_puzzle(&40u64).unwrap();
+        assert_eq!(puzzle.status, PuzzleStatus::Finalized);
+        assert_eq!(puzzle.winner_count, 1);
+        assert!(!client.finalize_self(&loser, &40u64));
+    }
+
+    // ------------------------------------------------------------------
+    // 23. Admin pages through players with finalize_batch
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_finalize_batch() {
+        let env = Env::default();
+        let (client, _, _, _) = setup(&env);
+        env.mock_all_auths();
+
+        let commitment = sha256_of(&env, b"CRANE");
+        client.create_daily_puzzle(&41u64, &commitment);
+
+        let mut players = Vec::new(&env);
+        for _ in 0..5 {
+            let player = Address::generate(&env);
+            client.submit_attempt(&player, &41u64, &bytes5(&env, b"CRANE"), &Vec::new(&env));
+            players.push_back(player);
+        }
+
+        let result = client.try_finalize_batch(&41u64, &0u32, &2u32);
+        assert_eq!(result, Err(Ok(Error::AnswerNotRevealed)));
+        client.reveal_answer(&41u64, &bytes5(&env, b"CRANE"));
+
+        let result = client.try_finalize_batch(&41u64, &0u32, &0u32);
+        assert_eq!(result, Err(Ok(Error::InvalidBatch)));
+        let result = client.try_finalize_batch(&41u64, &0u32, &(MAX_FINALIZE_BATCH + 1));
+        assert_eq!(result, Err(Ok(Error::InvalidBatch)));
+
+        assert_eq!(client.finalize_batch(&41u64, &0u32, &2u32), 2);
+        // Overlapping pages skip players already finalized.
+        assert_eq!(client.finalize_batch(&41u64, &1u32, &2u32), 1);
+        assert_eq!(client.finalize_batch(&41u64, &50u32, &2u32), 0);
+        client.finalize_self(&players.get(4).unwrap(), &41u64);
+        assert_eq!(
+            client.get_puzzle(&41u64).unwrap().status,
+            PuzzleStatus::Revealed
+        );
+
+        assert_eq!(client.finalize_batch(&41u64, &3u32, &10u32), 1);
+        let puzzle = client.get_puzzle(&41u64).unwrap();
+        assert_eq!(puzzle.status, PuzzleStatus::Finalized);
+        assert_eq!(puzzle.finalized_count, 5);
+        assert_eq!(puzzle.winner_count, 5);
+        assert_eq!(client.finalize_batch(&41u64, &0u32, &5u32), 0);
+        for player in players.iter() {
+            assert!(client.is_winner(&41u64, &player));
+        }
+    }
+
+    // ------------------------------------------------------------------
+    // 24. Rewards are weighted by the guess a puzzle was solved on
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_rewards_weighted_by_attempts() {
+        let env = Env::default();
+        let (client, _, prize_pool, balance) = setup(&env);
+        env.mock_all_auths();
+        let pool = MockPrizePoolClient::new(&env, &prize_pool);
+        let ledger = MockBalanceClient::new(&env, &balance);
+
+        let commitment = sha256_of(&env, b"CRANE");
+        client.create_daily_puzzle(&50u64, &commitment);
+        client.fund_puzzle(&50u64, &1_200i128);
+        assert_eq!(pool.reserved(&50u64), 1_200);
+
+        // Solves on guesses 1, 3 and 6 weigh 6, 4 and 1 by default.
+        let first = Address::generate(&env);
+        let third = Address::generate(&env);
+        let sixth = Address::generate(&env);
+        let loser = Address::generate(&env);
+        let no_proof = Vec::new(&env);
+        client.submit_attempt(&first, &50u64, &bytes5(&env, b"CRANE"), &no_proof);
+        for _ in 0..2 {
+            client.submit_attempt(&third, &50u64, &bytes5(&env, b"STALE"), &no_proof);
+        }
+        client.submit_attempt(&third, &50u64, &bytes5(&env, b"CRANE"), &no_proof);
+        for _ in 0..(MAX_ATTEMPTS - 1) {
+            client.submit_attempt(&sixth, &50u64, &bytes5(&env, b"STALE"), &no_proof);
+        }
+        client.submit_attempt(&sixth, &50u64, &bytes5(&env, b"CRANE"), &no_proof);
+        client.submit_attempt(&loser, &50u64, &bytes5(&env, b"STALE"), &no_proof);
+
+        client.reveal_answer(&50u64, &bytes5(&env, b"CRANE"));
+        let result = client.try_claim_reward(&first, &50u64);
+        assert_eq!(result, Err(Ok(Error::PuzzleNotFinalized)));
+        client.finalize_result(&first, &50u64);
+
+        let puzzle = client.get_puzzle(&50u64).unwrap();
+        assert_eq!(puzzle.total_weight, 11);
+        assert_eq!(puzzle.claim_deadline, DEFAULT_CLAIM_WINDOW);
+        assert_eq!(client.get_reward(&50u64, &sixth), 109);
+
+        // 1200 * 6 / 11, 1200 * 4 / 11, paid by the prize pool alone.
+        assert_eq!(client.claim_reward(&first, &50u64), 654);
+        assert_eq!(client.claim_reward(&third, &50u64), 436);
+        assert_eq!(pool.paid(&50u64), 1_090);
+        assert_eq!(ledger.balance_of(&client.address), 0);
+        let result = client.try_claim_reward(&first, &50u64);
+        assert_eq!(result, Err(Ok(Error::AlreadyClaimed)));
+        let result = client.try_claim_reward(&loser, &50u64);
+        assert_eq!(result, Err(Ok(Error::NoRewardAvailable)));
+
+        let result = client.try_sweep_unclaimed(&50u64);
+        assert_eq!(result, Err(Ok(Error::ClaimWindowOpen)));
+
+        env.ledger()
+            .with_mut(|l| l.timestamp = DEFAULT_CLAIM_WINDOW + 1);
+        let result = client.try_claim_reward(&sixth, &50u64);
+        assert_eq!(result, Err(Ok(Error::ClaimWindowClosed)));
+
+        // The unclaimed share plus the rounding remainder go back to the pool.
+        assert_eq!(client.sweep_unclaimed(&50u64), 110);
+        assert_eq!(pool.reserved(&50u64), 1_090);
+        assert_eq!(pool.paid(&50u64), 1_090);
+        let result = client.try_sweep_unclaimed(&50u64);
+        assert_eq!(result, Err(Ok(Error::AlreadySwept)));
+    }
+
+    // ------------------------------------------------------------------
+    // 25. Funding rules; unsolved puzzles can be swept at once
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_funding_and_unsolved_release() {
+        let env = Env::default();
+        let (client, admin, prize_pool, _) = setup(&env);
+        env.mock_all_auths();
+        let pool = MockPrizePoolClient::new(&env, &prize_pool);
+
+        let commitment = sha256_of(&env, b"CRANE");
+        client.create_daily_puzzle(&51u64, &commitment);
+
+        let result = client.try_fund_puzzle(&51u64, &0i128);
+        assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+        let result = client.try_fund_puzzle(&99u64, &500i128);
+        assert_eq!(result, Err(Ok(Error::PuzzleNotFound)));
+        client.fund_puzzle(&51u64, &500i128);
+        let result = client.try_fund_puzzle(&51u64, &500i128);
+        assert_eq!(result, Err(Ok(Error::PuzzleAlreadyFunded)));
+
+        let player = Address::generate(&env);
+        client.submit_attempt(&player, &51u64, &bytes5(&env, b"STALE"), &Vec::new(&env));
+        client.reveal_answer(&51u64, &bytes5(&env, b"CRANE"));
+
+        client.create_daily_puzzle(&52u64, &commitment);
+        client.reveal_answer(&52u64, &bytes5(&env, b"CRANE"));
+        let result = client.try_fund_puzzle(&52u64, &500i128);
+        assert_eq!(result, Err(Ok(Error::PuzzleNotOpen)));
+
+        // The last player finalizing needs only their own signature.
+        env.mock_auths(&[MockAuth {
+            address: &player,
+            invoke: &MockAuthInvoke {
+                contract: &client.address,
+                fn_name: "finalize_self",
+                args: (&player, 51u64).into_val(&env),
+                sub_invokes: &[],
+            },
+        }]);
+        client.finalize_self(&player, &51u64);
+        let puzzle = client.get_puzzle(&51u64).unwrap();
+        assert_eq!(puzzle.status, PuzzleStatus::Finalized);
+        assert_eq!(puzzle.released, 0);
+        assert_eq!(pool.reserved(&51u64), 500);
+
+        // Nobody can claim, so the admin sweeps without waiting for the deadline.
+        env.mock_auths(&[MockAuth {
+            address: &admin,
+            invoke: &MockAuthInvoke {
+                contract: &client.address,
+                fn_name: "sweep_unclaimed",
+                args: (51u64,).into_val(&env),
+                sub_invokes: &[MockAuthInvoke {
+                    contract: &prize_pool,
+                    fn_name: "release",
+                    args: (&admin, 51u64, 500i128).into_val(&env),
+                    sub_invokes: &[],
+                }],
+            },
+        }]);
+        assert_eq!(client.sweep_unclaimed(&51u64), 500);
+        assert_eq!(client.get_puzzle(&51u64).unwrap().released, 500);
+        assert_eq!(pool.reserved(&51u64), 0);
+    }
+
+    // ------------------------------------------------------------------
+    // 26. Custom attempt weights
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_custom_attempt_weights() {
+        let env = Env::default();
+        let (client, _, _, _) = setup(&env);
+        env.mock_all_auths();
+
+        let invalid = [
+            Vec::new(&env),
+            soroban_sdk::vec![&env, 3, 0],
+            soroban_sdk::vec![&env, 1, 2],
+            soroban_sdk::vec![&env, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1],
+            // A full puzzle of these would overflow `total_weight`
+            soroban_sdk::vec![&env, MAX_ATTEMPT_WEIGHT + 1],
+        ];
+        for weights in invalid.iter() {
+            let result = client.try_set_attempt_weights(weights);
+            assert_eq!(result, Err(Ok(Error::InvalidWeights)));
+        }
+        assert_eq!(client.get_attempt_weights().len(), MAX_ATTEMPTS);
+        client.set_attempt_weights(&soroban_sdk::vec![&env, MAX_ATTEMPT_WEIGHT]);
+        assert!(MAX_ATTEMPT_WEIGHT
+            .checked_mul(MAX_PLAYERS_PER_PUZZLE)
+            .is_some());
+
+        client.set_attempt_weights(&soroban_sdk::vec![&env, 3, 1]);
+        let commitment = sha256_of(&env, b"CRANE");
+        client.create_daily_puzzle(&53u64, &commitment);
+        client.fund_puzzle(&53u64, &400i128);
+
+        let quick = Address::generate(&env);
+        let slow = Address::generate(&env);
+        let no_proof = Vec::new(&env);
+        client.submit_attempt(&quick, &53u64, &bytes5(&env, b"CRANE"), &no_proof);
+        for _ in 0..2 {
+            client.submit_attempt(&slow, &53u64, &bytes5(&env, b"STALE"), &no_proof);
+        }
+        client.submit_attempt(&slow, &53u64, &bytes5(&env, b"CRANE"), &no_proof);
+
+        client.reveal_answer(&53u64, &bytes5(&env, b"CRANE"));
+        client.finalize_batch(&53u64, &0u32, &2u32);
+
+        // A solve past the weight table still wins, but earns nothing.
+        assert!(client.is_winner(&53u64, &slow));
+        assert_eq!(client.claim_reward(&quick, &53u64), 400);
+        let result = client.try_claim_reward(&slow, &53u64);
+        assert_eq!(result, Err(Ok(Error::NoRewardAvailable)));
+    }
+
+    // ------------------------------------------------------------------
+    // 27. Scoring generalises to other word lengths
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_score_guess_other_lengths() {
+        let env = Env::default();
+
+        let scores = score_guess(
+            &env,
+            &Bytes::from_slice(&env, b"ODOR"),
+            &Bytes::from_slice(&env, b"DOOR"),
+        );
+        assert_eq!(
+            scores,
+            soroban_sdk::vec![
+                &env,
+                SCORE_PRESENT,
+                SCORE_PRESENT,
+                SCORE_CORRECT,
+                SCORE_CORRECT
+            ]
+        );
+
+        let scores = score_guess(
+            &env,
+            &Bytes::from_slice(&env, b"TRIANGLE"),
+            &Bytes::from_slice(&env, b"INTEGRAL"),
+        );
+        assert_eq!(scores.len(), 8);
+        assert_eq!(scores.get(0), Some(SCORE_PRESENT)); // T
+        assert_eq!(scores.get(5), Some(SCORE_PRESENT)); // G
+        assert_eq!(scores.get(6), Some(SCORE_PRESENT)); // L
+        assert_eq!(scores.get(7), Some(SCORE_PRESENT)); // E
+    }
+
+    // ------------------------------------------------------------------
+    // 28. Configured puzzle enforces its length, limit and alphabet
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_configured_puzzle_rules() {
+        let env = Env::default();
+        let (client, _, _, _) = setup(&env);
+        env.mock_all_auths();
+
+        let config = PuzzleConfig {
+            word_length: 4,
+            max_attempts: 3,
+            alphabet: Bytes::from_slice(&env, b"ABCDEFGHIJKLMNOPQRSTUVWXYZ"),
+            hard_mode: false,
+        };
+        let commitment = sha256_of(&env, b"DOOR");
+        client.create_configured_puzzle(&60u64, &commitment, &config);
+        assert_eq!(client.get_puzzle(&60u64).unwrap().config, config);
+
+        let player = Address::generate(&env);
+        let no_proof = Vec::new(&env);
+        let result = client.try_submit_attempt(&player, &60u64, &bytes5(&env, b"DOORS"), &no_proof);
+        assert_eq!(result, Err(Ok(Error::InvalidWordLength)));
+        let lower = Bytes::from_slice(&env, b"door");
+        let result = client.try_submit_attempt(&player, &60u64, &lower, &no_proof);
+        assert_eq!(result, Err(Ok(Error::InvalidCharacter)));
+
+        let guess = Bytes::from_slice(&env, b"ROAD");
+        for _ in 0..3 {
+            client.submit_attempt(&player, &60u64, &guess, &no_proof);
+        }
+        let result = client.try_submit_attempt(&player, &60u64, &guess, &no_proof);
+        assert_eq!(result, Err(Ok(Error::TooManyAttempts)));
+
+        let result = client.try_reveal_answer(&60u64, &bytes5(&env, b"DOORS"));
+        assert_eq!(result, Err(Ok(Error::InvalidWordLength)));
+        client.reveal_answer(&60u64, &Bytes::from_slice(&env, b"DOOR"));
+        client.finalize_result(&player, &60u64);
+        assert_eq!(
+            client
+                .get_attempts(&player, &60u64)
+                .get(0)
+                .unwrap()
+                .scores
+                .len(),
+            4
+        );
+    }
+
+    // ------------------------------------------------------------------
+    // 29. Hard mode disqualifies guesses that ignore hints
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_hard_mode_disqualifies() {
+        let env = Env::default();
+        let (client, _, _, _) = setup(&env);
+        env.mock_all_auths();
+
+        let config = PuzzleConfig {
+            word_length: WORD_LENGTH,
+            max_attempts: MAX_ATTEMPTS,
+            alphabet: Bytes::new(&env),
+            hard_mode: true,
+        };
+        let commitment = sha256_of(&env, b"CRANE");
+        client.create_configured_puzzle(&61u64, &commitment, &config);
+
+        let careless = Address::generate(&env);
+        let careful = Address::generate(&env);
+        let no_proof = Vec::new(&env);
+        for word in [b"STALE", b"BOOKS", b"CRANE"] {
+            client.submit_attempt(&careless, &61u64, &bytes5(&env, word), &no_proof);
+        }
+        for word in [b"STALE", b"GRATE", b"CRANE"] {
+            client.submit_attempt(&careful, &61u64, &bytes5(&env, word), &no_proof);
+        }
+
+        client.reveal_answer(&61u64, &bytes5(&env, b"CRANE"));
+        client.finalize_batch(&61u64, &0u32, &2u32);
+
+        // STALE reveals A and E in place; BOOKS drops both.
+        let attempts = client.get_attempts(&careless, &61u64);
+        assert!(!attempts.get(0).unwrap().disqualified);
+        assert!(attempts.get(1).unwrap().disqualified);
+        assert!(attempts.get(2).unwrap().disqualified);
+        assert!(!client.is_winner(&61u64, &careless));
+
+        let attempts = client.get_attempts(&careful, &61u64);
+        assert!(attempts.iter().all(|a| !a.disqualified));
+        assert!(client.is_winner(&61u64, &careful));
+        assert_eq!(client.get_puzzle(&61u64).unwrap().winner_count, 1);
+    }
+
+    // ------------------------------------------------------------------
+    // 30. Invalid configurations and hint checks
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_invalid_config_and_hints() {
+        let env = Env::default();
+        let (client, _, _, _) = setup(&env);
+        env.mock_all_auths();
+
+        let commitment = sha256_of(&env, b"CRANE");
+        let valid = PuzzleConfig {
+            word_length: 5,
+            max_attempts: 6,
+            alphabet: Bytes::new(&env),
+            hard_mode: false,
+        };
+        let invalid = [
+            PuzzleConfig {
+                word_length: 3,
+                ..valid.clone()
+            },
+            PuzzleConfig {
+                word_length: 9,
+                ..valid.clone()
+            },
+            PuzzleConfig {
+                max_attempts: 0,
+                ..valid.clone()
+            },
+            PuzzleConfig {
+                max_attempts: 11,
+                ..valid.clone()
+            },
+            PuzzleConfig {
+                alphabet: Bytes::from_array(&env, &[b'A'; 65]),
+                ..valid.clone()
+            },
+        ];
+        for config in invalid.iter() {
+            let result = client.try_create_configured_puzzle(&62u64, &commitment, config);
+            assert_eq!(result, Err(Ok(Error::InvalidConfig)));
+        }
+
+        // SPEED against ERASE: E present, S present, E present.
+        let prev = bytes5(&env, b"SPEED");
+        let scores = score_guess(&env, &prev, &bytes5(&env, b"ERASE"));
+        assert!(satisfies_hints(&prev, &scores, &bytes5(&env, b"ESSEX")));
+        assert!(!satisfies_hints(&prev, &scores, &bytes5(&env, b"STEAK")));
+        assert!(!satisfies_hints(&prev, &scores, &bytes5(&env, b"BLEAK")));
+    }
 }